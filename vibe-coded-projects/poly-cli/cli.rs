@@ -0,0 +1,1413 @@
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use clap::{ArgAction, Args, Parser, Subcommand};
+use poly_core::{
+    sort_rows, AlertRule, Column, Condition, DisplayZone, EventQuery, LeaderboardMetric, LeaderboardWindow,
+    MarketStatus, OutputFormat, PriceBand, Row, RowFilter, Side, SignatureType, SortKey, SortSpec, Venue,
+};
+
+use crate::config::{Fields, Profile};
+use crate::notify::NotifyTarget;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "poly",
+    about = "Polymarket dashboard: highest volume markets + 24h change",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+
+    // Running without a subcommand behaves like `poly top`.
+    #[command(flatten)]
+    pub top: TopArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    // Hands a --profile's filters and sort to whichever command takes them.
+    pub fn apply_profile(&mut self, profile: &Profile) {
+        let (filter, sort) = match &mut self.command {
+            None => (&mut self.top.filter, Some(&mut self.top.sort)),
+            Some(Command::Top(args) | Command::Watch(WatchArgs { top: args, .. })) => {
+                (&mut args.filter, Some(&mut args.sort))
+            }
+            Some(Command::Search(args)) => (&mut args.filter, Some(&mut args.sort)),
+            Some(Command::Closing(args)) => (&mut args.filter, None),
+            Some(Command::Movers(args)) => (&mut args.filter, None),
+            Some(Command::Arb(args)) => (&mut args.filter, None),
+            Some(_) => return,
+        };
+        filter.fill_from(profile);
+        if let Some(sort) = sort {
+            sort.fill_from(profile);
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct GlobalArgs {
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = ArgAction::Count,
+        help = "Log requests, timings, retries and parse warnings to stderr; -vv adds cache and rate-limit \
+                detail, -vvv everything (without -v, RUST_LOG is honoured)"
+    )]
+    pub verbose: u8,
+
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "json",
+        help = "Output format: table, json, yaml, csv, html, or ndjson [default: table]"
+    )]
+    pub format: Option<OutputFormat>,
+
+    #[arg(long, global = true, help = "Emit results as JSON (shorthand for --format json)")]
+    pub json: bool,
+
+    #[arg(long = "no-color", global = true, help = "Disable ANSI colors in terminal output")]
+    pub no_color: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_delimiter = ',',
+        help = "Market-row columns to show, in order, for every format: rank, venue, title, event, slug, url, \
+                price, volume, volume24h, change, liquidity, end, result, trend, momentum, spike, quote, spread, mid \
+                [default: rank,title,price,volume,volume24h,change,liquidity,end plus any extras requested]"
+    )]
+    pub columns: Vec<Column>,
+
+    #[arg(
+        long,
+        global = true,
+        value_delimiter = ',',
+        value_name = "FIELDS",
+        help = "Keep only these fields of each JSON or NDJSON result, by key, e.g. title,volume24h"
+    )]
+    pub select: Vec<String>,
+
+    #[arg(
+        long = "where",
+        global = true,
+        value_name = "EXPR",
+        help = "Keep only JSON or NDJSON results where <field> <op> <value> holds, e.g. \"volume24h > 10k\" or \
+                \"venue == kalshi\" (repeatable, all must match)"
+    )]
+    pub filter: Vec<Condition>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "ZONE",
+        help = "Timezone for end dates: local, UTC, or an IANA name such as America/New_York [default: UTC]"
+    )]
+    pub tz: Option<DisplayZone>,
+
+    #[arg(
+        long = "relative-end",
+        global = true,
+        overrides_with = "no_relative_end",
+        help = "Show end dates as time left or since, e.g. \"in 3d 4h\" or \"ended 2h ago\""
+    )]
+    pub relative_end: bool,
+
+    #[arg(
+        long = "no-relative-end",
+        global = true,
+        overrides_with = "relative_end",
+        help = "Show end dates as timestamps, even when the config sets relative-end"
+    )]
+    pub no_relative_end: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Config file path (default: ~/.config/poly-cli/config.toml)"
+    )]
+    pub config: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Use a saved setup from the config file's [profiles.NAME] table; flags given here still win"
+    )]
+    pub profile: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "Save every raw API response into this directory as a timestamped JSON file"
+    )]
+    pub record: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        conflicts_with = "record",
+        help = "Answer API requests from a --record directory instead of the network"
+    )]
+    pub replay: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        conflicts_with_all = ["record", "replay", "no_cache"],
+        help = "Answer every API request from the response cache, however old, without touching the network"
+    )]
+    pub offline: bool,
+
+    #[arg(
+        long = "no-cache",
+        global = true,
+        help = "Always hit the API instead of reusing responses saved by recent runs"
+    )]
+    pub no_cache: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Extra attempts after a network error, timeout, 429 or 5xx from the API [default: 2]"
+    )]
+    pub retries: Option<u32>,
+
+    #[arg(
+        long = "retry-backoff",
+        global = true,
+        value_name = "SECS",
+        value_parser = parse_non_negative,
+        help = "Wait before the first retry, doubled (with jitter) for each one after [default: 0.5]"
+    )]
+    pub retry_backoff: Option<f64>,
+
+    #[arg(
+        long = "rate-limit",
+        global = true,
+        value_name = "RPS",
+        value_parser = parse_non_negative,
+        help = "Most API requests per second, shared by every endpoint; 0 for no limit [default: 20]"
+    )]
+    pub rate_limit: Option<f64>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "URL",
+        help = "Send API requests through this proxy: http://, https://, socks5:// or socks5h:// \
+                [default: HTTPS_PROXY / ALL_PROXY from the environment]"
+    )]
+    pub proxy: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Take an exclusive lock on this file first, and quietly exit 0 if another run already holds it \
+                (keeps cron runs from piling up)"
+    )]
+    pub lock: Option<PathBuf>,
+
+    #[arg(
+        long = "max-runtime",
+        global = true,
+        value_name = "SECS",
+        value_parser = parse_runtime,
+        help = "Give up with exit code 10 when the command runs longer than this, e.g. on a hung fetch"
+    )]
+    pub max_runtime: Option<std::time::Duration>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    #[command(about = "Show the highest volume markets")]
+    Top(TopArgs),
+
+    #[command(about = "Continuously refresh the top markets dashboard, or follow a single market")]
+    Watch(WatchArgs),
+
+    #[command(about = "Find markets whose title or event matches a pattern")]
+    Search(SearchArgs),
+
+    #[command(about = "List the tags on open events with market counts and volume, for --tag")]
+    Tags(TagsArgs),
+
+    #[command(about = "Show every market in a single event")]
+    Event(EventArgs),
+
+    #[command(about = "Show outcome prices, book top, and details for one market")]
+    Market(MarketArgs),
+
+    #[command(about = "Show CLOB bid/ask depth for an outcome token")]
+    Orderbook(OrderbookArgs),
+
+    #[command(about = "Print a tape of recent fills for a market")]
+    Trades(TradesArgs),
+
+    #[command(about = "Show the latest comments on an event, or tail new ones")]
+    Comments(CommentsArgs),
+
+    #[command(about = "Serve market data as a JSON HTTP API")]
+    Serve(ServeArgs),
+
+    #[command(about = "Record snapshots on a schedule while serving the HTTP API, /status and /metrics")]
+    Daemon(DaemonArgs),
+
+    #[command(about = "Show what a running `poly daemon` is doing")]
+    Status(StatusArgs),
+
+    #[command(about = "Sign and place a limit order on the CLOB (needs a private key)")]
+    Order(OrderArgs),
+
+    #[command(about = "Show a wallet's USDC and POL balances and its trading approvals on Polygon")]
+    Balance(BalanceArgs),
+
+    #[command(about = "Check a market's reported resolution against the Conditional Tokens payouts on chain")]
+    Verify(VerifyArgs),
+
+    #[command(about = "Estimate the average fill, slippage and fees of a market order")]
+    Impact(ImpactArgs),
+
+    #[command(about = "Suggest Kelly stakes for a probability estimate against a market price")]
+    Size(SizeArgs),
+
+    #[command(about = "Quote both sides around an outcome's midpoint, optionally placing the orders")]
+    Quote(QuoteArgs),
+
+    #[command(about = "List or cancel your open CLOB orders")]
+    Orders(OrdersArgs),
+
+    #[command(about = "Set up CLOB API credentials for `order` and other trading commands")]
+    Auth(AuthArgs),
+
+    #[command(about = "Manage the markets shown by `--watchlist`")]
+    Watchlist(WatchlistArgs),
+
+    #[command(about = "Rank traders by profit or volume over a window")]
+    Leaderboard(LeaderboardArgs),
+
+    #[command(about = "List the largest holders of each outcome of a market")]
+    Holders(HoldersArgs),
+
+    #[command(about = "Show a wallet's open positions with entry, mark, and unrealized PnL")]
+    Portfolio(PortfolioArgs),
+
+    #[command(about = "Chart an outcome's price history with summary stats")]
+    History(HistoryArgs),
+
+    #[command(about = "Flag markets whose outcome prices don't sum to $1")]
+    Arb(ArbArgs),
+
+    #[command(about = "Correlate two markets' price histories")]
+    Correlate(CorrelateArgs),
+
+    #[command(about = "Show markets side by side: price, volume, liquidity, spread, and end date")]
+    Compare(CompareArgs),
+
+    #[command(name = "compare-venues", about = "Match a topic's markets across two venues")]
+    CompareVenues(CompareVenuesArgs),
+
+    #[command(about = "Find the same market priced differently across venues")]
+    Xarb(XarbArgs),
+
+    #[command(about = "List newly created markets with their opening and current prices")]
+    New(NewArgs),
+
+    #[command(about = "Show markets ending soon, soonest first")]
+    Closing(ClosingArgs),
+
+    #[command(about = "Biggest 24h price moves, gainers and losers in separate sections")]
+    Movers(MoversArgs),
+
+    #[command(about = "Upcoming games in a league as head-to-head lines with both sides' prices")]
+    Sports(SportsArgs),
+
+    #[command(about = "Compare two snapshots: markets entering or leaving the top N, and price and volume moves")]
+    Diff(DiffArgs),
+
+    #[command(about = "List recently resolved markets with the winner and final price")]
+    Resolved(ResolvedArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    // Following one market only takes the refresh and alert options; the rest shape the table.
+    #[arg(
+        conflicts_with_all = [
+            "top", "fetch_limit", "tags", "exclude_tags", "include_closed", "resolved_only", "min_volume",
+            "min_volume_24h", "min_liquidity", "price_between", "longshots", "near_certain", "limit_per_event",
+            "sort", "desc", "asc", "watch", "deltas", "stream", "db", "output", "log_csv", "venues", "watchlist",
+            "sparklines", "momentum", "spreads", "spike",
+        ],
+        help = "Follow just this market: price, spread, volume, recent trades and a rolling price sparkline"
+    )]
+    pub slug: Option<String>,
+
+    #[arg(long, default_value_t = 10, requires = "slug", help = "Recent trades shown when following one market")]
+    pub trades: usize,
+
+    #[command(flatten)]
+    pub top: TopArgs,
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct TopArgs {
+    #[arg(long, help = "Number of markets to display [default: 20]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of events to fetch from API (higher = broader coverage) [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+
+    #[command(flatten)]
+    pub sort: SortArgs,
+
+    #[arg(long, help = "Continuously refresh the dashboard")]
+    pub watch: bool,
+
+    #[arg(long, help = "Refresh interval seconds in watch mode [default: 30]")]
+    pub interval: Option<u64>,
+
+    #[arg(long, help = "In watch mode, show price and volume change since the session started")]
+    pub deltas: bool,
+
+    #[arg(
+        long = "alert",
+        help = "Alert when a market matches, e.g. \"change24h > 5\" or \"volume24h > 1m\" (repeatable)"
+    )]
+    pub alerts: Vec<AlertRule>,
+
+    #[arg(long, help = "Ring the terminal bell when an alert fires")]
+    pub beep: bool,
+
+    #[arg(
+        long,
+        help = "Also deliver fired alerts here: desktop, discord:<webhook>, slack:<webhook>, \
+                telegram:<bot_token>:<chat_id> (repeatable)"
+    )]
+    pub notify: Vec<NotifyTarget>,
+
+    #[arg(long, help = "POST each refreshed snapshot and every fired alert as JSON to this URL")]
+    pub webhook: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["watch", "log_csv", "output"],
+        help = "Stream live prices over the CLOB WebSocket instead of polling"
+    )]
+    pub stream: bool,
+
+    #[arg(long, help = "Record every fetched market into this SQLite database on each refresh")]
+    pub db: Option<PathBuf>,
+
+    #[arg(
+        long,
+        short,
+        value_name = "PATH",
+        conflicts_with = "stream",
+        help = "Write the output to this file instead of stdout, replacing it atomically on every refresh"
+    )]
+    pub output: Option<PathBuf>,
+
+    #[arg(
+        long = "log-csv",
+        value_name = "PATH",
+        help = "Append every fetched market to this CSV file on each refresh, one timestamped line per market"
+    )]
+    pub log_csv: Option<PathBuf>,
+
+    #[arg(
+        long = "venue",
+        value_delimiter = ',',
+        conflicts_with = "watchlist",
+        help = "Where to list markets from: polymarket, kalshi, manifold, or several (polymarket,manifold) \
+                [default: polymarket]"
+    )]
+    pub venues: Vec<Venue>,
+
+    #[arg(long, help = "Show only the markets on your watchlist, regardless of volume rank")]
+    pub watchlist: bool,
+
+    #[arg(long, help = "Add a 24h price trend sparkline column (one extra request per displayed market)")]
+    pub sparklines: bool,
+
+    #[arg(
+        long,
+        help = "Add a momentum column (↑↑/↑/→/↓/↓↓): direction over 24h, doubled when the last hour is \
+                moving faster than the 24h pace"
+    )]
+    pub momentum: bool,
+
+    #[arg(
+        long,
+        help = "Add best bid/ask, spread, and midpoint columns from the CLOB book \
+                (one extra request per displayed market)"
+    )]
+    pub spreads: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = parse_multiple,
+        help = "Flag markets whose 24h volume is at least N times their trailing 7-day daily average, from the \
+                history in --db or --log-csv, in a Spike column"
+    )]
+    pub spike: Option<f64>,
+
+    #[arg(
+        long,
+        help = "How much to download per refresh: minimal fetches a 50-event pool instead of 150, allows no \
+                per-market extras, and when following one market skips the order book and cuts recent trades to 3, \
+                to save data on metered links [default: full]"
+    )]
+    pub fields: Option<Fields>,
+}
+
+#[derive(Args, Debug)]
+pub struct SearchArgs {
+    #[arg(help = "Substring (case-insensitive) or regex to match against market and event titles")]
+    pub pattern: String,
+
+    #[arg(long, help = "Treat the pattern as a regular expression")]
+    pub regex: bool,
+
+    #[arg(long, help = "Maximum number of matches to display [default: 20]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of events to search through [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+
+    #[command(flatten)]
+    pub sort: SortArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct TagsArgs {
+    #[arg(long, default_value_t = 50, help = "Maximum number of tags to display")]
+    pub top: usize,
+
+    #[arg(
+        long = "fetch-limit",
+        default_value_t = 500,
+        help = "Number of open events, by volume, to collect tags from"
+    )]
+    pub fetch_limit: usize,
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct SortArgs {
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Sort keys in priority order: volume, volume24h, change, end, liquidity [default: volume]"
+    )]
+    pub sort: Vec<SortKey>,
+
+    #[arg(long, conflicts_with = "asc", help = "Sort descending (default for all keys except end)")]
+    pub desc: bool,
+
+    #[arg(long, help = "Sort ascending (default for end)")]
+    pub asc: bool,
+}
+
+impl SortArgs {
+    // The profile's sort, unless one was given on the command line.
+    fn fill_from(&mut self, profile: &Profile) {
+        if self.sort.is_empty() && !self.asc && !self.desc {
+            self.sort = profile.sort.clone();
+            self.asc = profile.asc;
+            self.desc = profile.desc;
+        }
+    }
+
+    // --asc/--desc apply to every key; otherwise each key uses its natural direction.
+    pub fn specs(&self) -> Vec<SortSpec> {
+        let keys = if self.sort.is_empty() { vec![SortKey::Volume] } else { self.sort.clone() };
+        keys.into_iter()
+            .map(|key| SortSpec {
+                key,
+                descending: if self.asc {
+                    false
+                } else {
+                    self.desc || key.default_descending()
+                },
+            })
+            .collect()
+    }
+
+    pub fn label(&self) -> String {
+        self.specs().iter().map(|s| s.key.label()).collect::<Vec<_>>().join(", ")
+    }
+
+    pub fn apply(&self, rows: &mut [Row]) {
+        sort_rows(rows, &self.specs());
+    }
+}
+
+// Event/market filters shared by the list-style subcommands.
+#[derive(Args, Debug, Clone, Default)]
+pub struct FilterArgs {
+    #[arg(
+        long = "tag",
+        value_delimiter = ',',
+        help = "Only include events with one of these tags (slug or label), e.g. politics,sports"
+    )]
+    pub tags: Vec<String>,
+
+    #[arg(
+        long = "exclude-tag",
+        value_delimiter = ',',
+        help = "Drop events carrying any of these tags"
+    )]
+    pub exclude_tags: Vec<String>,
+
+    #[arg(
+        long = "include-closed",
+        conflicts_with = "resolved_only",
+        help = "Include closed markets alongside live ones"
+    )]
+    pub include_closed: bool,
+
+    #[arg(long = "resolved-only", help = "Only show closed markets with a known winning outcome")]
+    pub resolved_only: bool,
+
+    #[arg(long = "min-volume", help = "Drop markets with total volume below this (USD)")]
+    pub min_volume: Option<f64>,
+
+    #[arg(long = "min-volume-24h", help = "Drop markets with 24h volume below this (USD)")]
+    pub min_volume_24h: Option<f64>,
+
+    #[arg(long = "min-liquidity", help = "Drop markets with liquidity below this (USD)")]
+    pub min_liquidity: Option<f64>,
+
+    #[arg(
+        long = "price-between",
+        num_args = 2,
+        value_names = ["MIN", "MAX"],
+        value_parser = parse_price,
+        conflicts_with_all = ["longshots", "near_certain"],
+        help = "Only markets whose price is in this range, e.g. 0.05 0.95 to drop near-resolved ones"
+    )]
+    pub price_between: Vec<f64>,
+
+    #[arg(
+        long,
+        conflicts_with = "near_certain",
+        help = "Only longshots: priced at 10¢ or less, with at least $100k traded unless --min-volume says otherwise"
+    )]
+    pub longshots: bool,
+
+    #[arg(long = "near-certain", help = "Only markets priced at 95¢ or more")]
+    pub near_certain: bool,
+
+    #[arg(
+        long = "limit-per-event",
+        value_parser = parse_count,
+        help = "Keep only the N highest-volume markets of each event before the top-N cut"
+    )]
+    pub limit_per_event: Option<usize>,
+}
+
+// The lifetime volume `--longshots` asks for when --min-volume isn't given, so the list is
+// markets people actually trade rather than every abandoned 1¢ question.
+const LONGSHOT_MIN_VOLUME: f64 = 100_000.0;
+
+impl FilterArgs {
+    // Fills each filter the command line left unset from the profile.
+    fn fill_from(&mut self, profile: &Profile) {
+        if self.tags.is_empty() {
+            self.tags = profile.tags.clone();
+        }
+        if self.exclude_tags.is_empty() {
+            self.exclude_tags = profile.exclude_tags.clone();
+        }
+        self.min_volume = self.min_volume.or(profile.min_volume);
+        self.min_volume_24h = self.min_volume_24h.or(profile.min_volume_24h);
+        self.min_liquidity = self.min_liquidity.or(profile.min_liquidity);
+        self.limit_per_event = self.limit_per_event.or(profile.limit_per_event);
+        // A price preset on the command line counts as choosing the price band.
+        if self.price_between.is_empty() && !self.longshots && !self.near_certain {
+            self.price_between = profile.price_between.map(Vec::from).unwrap_or_default();
+        }
+    }
+
+    pub fn event_query(&self, fetch_limit: usize) -> EventQuery {
+        EventQuery {
+            tags: self.tags.clone(),
+            exclude_tags: self.exclude_tags.clone(),
+            status: if self.resolved_only {
+                MarketStatus::Resolved
+            } else if self.include_closed {
+                MarketStatus::All
+            } else {
+                MarketStatus::Open
+            },
+            ..EventQuery::new(fetch_limit)
+        }
+    }
+
+    pub fn row_filter(&self) -> RowFilter {
+        RowFilter {
+            min_volume: self.min_volume.or(self.longshots.then_some(LONGSHOT_MIN_VOLUME)),
+            min_volume_24h: self.min_volume_24h,
+            min_liquidity: self.min_liquidity,
+            price: self.price_band(),
+            per_event: self.limit_per_event,
+        }
+    }
+
+    fn price_band(&self) -> Option<PriceBand> {
+        match self.price_between.as_slice() {
+            // Either order reads the same.
+            [a, b] => Some(PriceBand { min: a.min(*b), max: a.max(*b) }),
+            _ if self.longshots => Some(PriceBand::LONGSHOTS),
+            _ if self.near_certain => Some(PriceBand::NEAR_CERTAIN),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct EventArgs {
+    #[arg(help = "Event slug, e.g. presidential-election-winner-2028")]
+    pub slug: String,
+}
+
+#[derive(Args, Debug)]
+pub struct MarketArgs {
+    #[arg(help = "Market slug")]
+    pub slug: String,
+}
+
+#[derive(Args, Debug)]
+pub struct OrderbookArgs {
+    #[arg(help = "CLOB token id of the outcome (see clobTokenIds in `market --json`)")]
+    pub token_id: String,
+
+    #[arg(long, default_value_t = 10, help = "Number of price levels to show per side")]
+    pub depth: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct TradesArgs {
+    #[arg(help = "Market slug or 0x condition id")]
+    pub market: String,
+
+    #[arg(long, default_value_t = 50, help = "Number of recent trades to fetch")]
+    pub limit: usize,
+
+    #[arg(long, help = "Keep polling and append new trades as they happen")]
+    pub follow: bool,
+
+    #[arg(long, default_value_t = 5, help = "Poll interval seconds with --follow")]
+    pub interval: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct CommentsArgs {
+    #[arg(help = "Event slug")]
+    pub slug: String,
+
+    #[arg(long, default_value_t = 30, help = "Number of recent comments to fetch")]
+    pub limit: usize,
+
+    #[arg(long, help = "Keep polling and append new comments as they're posted")]
+    pub follow: bool,
+
+    #[arg(long, default_value_t = 15, help = "Poll interval seconds with --follow")]
+    pub interval: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct OrderArgs {
+    #[arg(help = "buy or sell")]
+    pub side: Side,
+
+    #[arg(help = "CLOB token id of the outcome (see clobTokenIds in `market --json`)")]
+    pub token_id: String,
+
+    #[arg(long, help = "Limit price per share, on the market's tick grid, e.g. 0.42")]
+    pub price: f64,
+
+    #[arg(long, help = "Number of shares, in hundredths at most")]
+    pub size: f64,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        help = "Proxy wallet or Safe that holds the funds, when it isn't the key's own address"
+    )]
+    pub funder: Option<String>,
+
+    #[arg(long = "signature-type", help = "Who --funder is: eoa, proxy or safe [default: eoa]")]
+    pub signature_type: Option<SignatureType>,
+
+    #[arg(long = "dry-run", help = "Sign the order and print it without submitting")]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct LeaderboardArgs {
+    #[arg(long, default_value = "30d", help = "Period to rank over: 1d, 7d, 30d or all")]
+    pub window: LeaderboardWindow,
+
+    #[arg(long, default_value = "profit", help = "Rank by profit or volume")]
+    pub by: LeaderboardMetric,
+
+    #[arg(long, default_value_t = 20, help = "Number of traders to show")]
+    pub limit: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct HoldersArgs {
+    #[arg(help = "Market slug")]
+    pub slug: String,
+
+    #[arg(long, default_value_t = 20, help = "Holders to list per outcome")]
+    pub limit: usize,
+
+    #[arg(long = "no-ens", help = "Skip looking up ENS names on Ethereum mainnet")]
+    pub no_ens: bool,
+
+    #[arg(
+        long = "ens-rpc-url",
+        value_name = "URL",
+        help = "Ethereum mainnet JSON-RPC endpoint for ENS [default: https://eth.llamarpc.com]"
+    )]
+    pub ens_rpc_url: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct BalanceArgs {
+    #[arg(help = "Wallet address, 0x followed by 40 hex digits (the funder, for proxy wallets)")]
+    pub address: String,
+
+    #[arg(
+        long = "rpc-url",
+        value_name = "URL",
+        help = "Polygon JSON-RPC endpoint [default: rpc-url from the config, else https://polygon-rpc.com]"
+    )]
+    pub rpc_url: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    #[arg(help = "Market slug")]
+    pub slug: String,
+
+    #[arg(
+        long = "rpc-url",
+        value_name = "URL",
+        help = "Polygon JSON-RPC endpoint [default: rpc-url from the config, else https://polygon-rpc.com]"
+    )]
+    pub rpc_url: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ImpactArgs {
+    #[arg(help = "CLOB token id of the outcome (see clobTokenIds in `market --json`)")]
+    pub token_id: String,
+
+    #[arg(long, help = "Shares to buy or sell")]
+    pub size: f64,
+
+    #[arg(long, default_value = "buy", help = "buy walks the asks, sell the bids")]
+    pub side: Side,
+}
+
+#[derive(Args, Debug)]
+pub struct SizeArgs {
+    #[arg(long, value_parser = parse_probability, help = "Your probability that the outcome wins, e.g. 0.65")]
+    pub prob: f64,
+
+    #[arg(
+        long,
+        value_parser = parse_probability,
+        required_unless_present = "market",
+        conflicts_with = "market",
+        help = "Price of the outcome, e.g. 0.55"
+    )]
+    pub price: Option<f64>,
+
+    #[arg(long, value_name = "SLUG", help = "Take the price from this market instead of --price")]
+    pub market: Option<String>,
+
+    #[arg(long, requires = "market", help = "Outcome of --market to size, e.g. No [default: first outcome]")]
+    pub outcome: Option<String>,
+
+    #[arg(long, help = "Bankroll in dollars")]
+    pub bankroll: f64,
+}
+
+#[derive(Args, Debug)]
+pub struct QuoteArgs {
+    #[arg(help = "CLOB token id of the outcome (see clobTokenIds in `market --json`)")]
+    pub token_id: String,
+
+    #[arg(long, value_parser = parse_spread, help = "Gap between bid and ask: 2c, or a price such as 0.02")]
+    pub spread: f64,
+
+    #[arg(long, help = "Shares on each side")]
+    pub size: f64,
+
+    #[arg(long, default_value_t = 15, help = "Seconds between requotes")]
+    pub interval: u64,
+
+    #[arg(long, help = "Place the quotes, replacing them when the midpoint moves (needs a private key)")]
+    pub execute: bool,
+
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        requires = "execute",
+        help = "Proxy wallet or Safe that holds the funds, when it isn't the key's own address"
+    )]
+    pub funder: Option<String>,
+
+    #[arg(
+        long = "signature-type",
+        requires = "execute",
+        help = "Who --funder is: eoa, proxy or safe [default: eoa]"
+    )]
+    pub signature_type: Option<SignatureType>,
+}
+
+#[derive(Args, Debug)]
+pub struct OrdersArgs {
+    #[command(subcommand)]
+    pub command: OrdersCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum OrdersCommand {
+    #[command(about = "Show open orders with their market, price and fill")]
+    List {
+        #[arg(long, value_name = "CONDITION_ID", help = "Only orders in this market (0x condition id)")]
+        market: Option<String>,
+    },
+
+    #[command(about = "Cancel one open order, or all of them with --all")]
+    Cancel {
+        #[arg(required_unless_present = "all", help = "Order id, as `orders list` shows it")]
+        id: Option<String>,
+
+        #[arg(long, conflicts_with = "id", help = "Cancel every open order")]
+        all: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct AuthArgs {
+    #[command(subcommand)]
+    pub command: AuthCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AuthCommand {
+    #[command(
+        name = "create-key",
+        about = "Create (or recover) API credentials from a wallet signature and store them in the OS keyring"
+    )]
+    CreateKey {
+        #[arg(long, default_value_t = 0, help = "Key nonce; each nonce yields a separate set of credentials")]
+        nonce: u64,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct WatchlistArgs {
+    #[command(subcommand)]
+    pub command: WatchlistCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WatchlistCommand {
+    #[command(about = "Add market slugs to the watchlist")]
+    Add {
+        #[arg(required = true, help = "Market slugs, e.g. will-the-fed-cut-rates-in-december")]
+        slugs: Vec<String>,
+    },
+
+    #[command(about = "Remove market slugs from the watchlist")]
+    Remove {
+        #[arg(required = true)]
+        slugs: Vec<String>,
+    },
+
+    #[command(about = "Print the watchlist")]
+    List,
+}
+
+#[derive(Args, Debug)]
+pub struct PortfolioArgs {
+    #[arg(help = "Wallet (proxy) address, 0x followed by 40 hex digits")]
+    pub address: String,
+
+    #[arg(long, help = "Replay trade history to show realized and unrealized PnL per market")]
+    pub pnl: bool,
+
+    #[arg(
+        long,
+        requires = "pnl",
+        value_parser = parse_since,
+        help = "Only count realized PnL from sells in this window: 7d, 24h, 30m, 2w, or a date (2024-06-01)"
+    )]
+    pub since: Option<DateTime<Utc>>,
+}
+
+// A non-negative number, fractions allowed: `0.5`, `2`. Used for seconds and rates.
+fn parse_non_negative(text: &str) -> Result<f64, String> {
+    match text.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value >= 0.0 => Ok(value),
+        _ => Err(format!("expected a non-negative number, got '{text}'")),
+    }
+}
+
+// A multiple above zero, fractions allowed: `1.5`, `3`. Zero would flag every market.
+fn parse_multiple(text: &str) -> Result<f64, String> {
+    match text.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+        _ => Err(format!("expected a number above 0, got '{text}'")),
+    }
+}
+
+// A positive number of seconds that fits a Duration, fractions allowed: `0.5`, `600`.
+fn parse_runtime(text: &str) -> Result<std::time::Duration, String> {
+    match text.trim().parse::<f64>().ok().map(std::time::Duration::try_from_secs_f64) {
+        Some(Ok(limit)) if !limit.is_zero() => Ok(limit),
+        _ => Err(format!("expected a positive number of seconds, got '{text}'")),
+    }
+}
+
+// A share price: a number from 0 to 1, e.g. 0.05.
+fn parse_price(text: &str) -> Result<f64, String> {
+    match text.trim().parse::<f64>() {
+        Ok(p) if (0.0..=1.0).contains(&p) => Ok(p),
+        _ => Err(format!("invalid price '{text}' (expected a number from 0 to 1, e.g. 0.05)")),
+    }
+}
+
+// A whole number of at least 1.
+fn parse_count(text: &str) -> Result<usize, String> {
+    match text.trim().parse::<usize>() {
+        Ok(value) if value >= 1 => Ok(value),
+        _ => Err(format!("expected a whole number of at least 1, got '{text}'")),
+    }
+}
+
+// A span written as a count and unit: `30m`, `24h`, `7d`, `2w`.
+fn parse_window(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (amount, unit) = text.split_at(split);
+    let invalid = || format!("invalid duration '{text}' (use e.g. 30m, 24h, 7d, 2w)");
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let window = match unit {
+        "m" => Duration::try_minutes(amount),
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => Duration::try_weeks(amount),
+        _ => return Err(invalid()),
+    };
+    let window = window.ok_or_else(|| format!("duration '{text}' is too long"))?;
+    if window <= Duration::zero() {
+        return Err(invalid());
+    }
+    Ok(window)
+}
+
+// A probability or price strictly between 0 and 1.
+fn parse_probability(text: &str) -> Result<f64, String> {
+    match text.trim().parse::<f64>() {
+        Ok(p) if p > 0.0 && p < 1.0 => Ok(p),
+        _ => Err(format!("invalid value '{text}' (expected a number between 0 and 1, e.g. 0.55)")),
+    }
+}
+
+// A price gap in cents (`2c`, `1.5c`) or as a price (`0.02`).
+fn parse_spread(text: &str) -> Result<f64, String> {
+    let text = text.trim();
+    let invalid = || format!("invalid spread '{text}' (use e.g. 2c or 0.02)");
+    let spread = match text.strip_suffix('c') {
+        Some(cents) => cents.trim().parse::<f64>().map_err(|_| invalid())? / 100.0,
+        None => text.parse::<f64>().map_err(|_| invalid())?,
+    };
+    if !(spread > 0.0 && spread < 1.0) {
+        return Err(invalid());
+    }
+    Ok(spread)
+}
+
+// A relative window back from now (`7d`, `24h`, `30m`, `2w`) or an absolute date/RFC 3339 time.
+fn parse_since(text: &str) -> Result<DateTime<Utc>, String> {
+    let text = text.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Ok(date.and_time(NaiveTime::MIN).and_utc());
+    }
+    let window = parse_window(text)?;
+    Utc::now().checked_sub_signed(window).ok_or_else(|| format!("'{text}' reaches back past any representable date"))
+}
+
+#[derive(Args, Debug)]
+pub struct HistoryArgs {
+    #[arg(help = "Market slug or CLOB token id")]
+    pub market: String,
+
+    #[arg(long, help = "Outcome to chart when given a slug, e.g. No [default: first outcome]")]
+    pub outcome: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "1h",
+        value_parser = parse_window,
+        help = "Spacing between price samples: 1m, 1h, 1d, ..."
+    )]
+    pub interval: Duration,
+
+    #[arg(
+        long,
+        default_value = "7d",
+        value_parser = parse_window,
+        help = "How far back to chart: 24h, 7d, 4w, ..."
+    )]
+    pub range: Duration,
+}
+
+#[derive(Args, Debug)]
+pub struct CorrelateArgs {
+    #[arg(help = "First market slug or CLOB token id (its first outcome is used)")]
+    pub a: String,
+
+    #[arg(help = "Second market slug or CLOB token id")]
+    pub b: String,
+
+    #[arg(
+        long,
+        default_value = "1h",
+        value_parser = parse_window,
+        help = "Spacing between price samples: 1m, 1h, 1d, ..."
+    )]
+    pub interval: Duration,
+
+    #[arg(
+        long,
+        default_value = "7d",
+        value_parser = parse_window,
+        help = "How far back to compare: 24h, 7d, 4w, ..."
+    )]
+    pub range: Duration,
+}
+
+#[derive(Args, Debug)]
+pub struct ClosingArgs {
+    #[arg(
+        long,
+        default_value = "48h",
+        value_parser = parse_window,
+        help = "Only markets ending within this window: 6h, 48h, 7d, ..."
+    )]
+    pub within: Duration,
+
+    #[arg(long, help = "Maximum number of markets to display [default: 20]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of soonest-ending events to scan [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct MoversArgs {
+    #[arg(long, help = "Number of markets in each section [default: 10]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of events, by volume, to look for movers in [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    #[arg(
+        value_name = "SNAPSHOT",
+        num_args = 0..=2,
+        help = "Earlier and later snapshot files saved with --format json or ndjson"
+    )]
+    pub snapshots: Vec<PathBuf>,
+
+    #[arg(
+        long,
+        conflicts_with = "snapshots",
+        requires = "from",
+        help = "Compare refreshes recorded with `top --db` instead of files"
+    )]
+    pub db: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_parser = parse_since,
+        requires = "db",
+        help = "With --db, the earlier point: RFC 3339, a date (2024-11-05), or a span back from now (24h)"
+    )]
+    pub from: Option<DateTime<Utc>>,
+
+    #[arg(
+        long,
+        value_parser = parse_since,
+        requires = "db",
+        help = "With --db, the later point, as --from [default: now]"
+    )]
+    pub to: Option<DateTime<Utc>>,
+
+    #[arg(long, default_value_t = 20, help = "Size of the top list compared")]
+    pub top: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct SportsArgs {
+    #[arg(long, help = "League code, e.g. nba, nfl, epl; omit to list the leagues available")]
+    pub league: Option<String>,
+
+    #[arg(long, default_value_t = 100, help = "Number of the league's open events to look through for games")]
+    pub limit: usize,
+}
+
+#[derive(Args, Debug)]
+pub struct ResolvedArgs {
+    #[arg(
+        long,
+        default_value = "7d",
+        value_parser = parse_window,
+        help = "How recently the market must have closed: 24h, 7d, 4w, ..."
+    )]
+    pub since: Duration,
+
+    #[arg(long, help = "Maximum number of markets to display [default: 20]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of most recently ended events to scan [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+
+    #[arg(
+        long = "tag",
+        value_delimiter = ',',
+        help = "Only include events with one of these tags (slug or label), e.g. politics,sports"
+    )]
+    pub tags: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    #[arg(
+        long,
+        default_value = "24h",
+        value_parser = parse_window,
+        help = "How recently the market must have been created: 6h, 24h, 7d, ..."
+    )]
+    pub since: Duration,
+
+    #[arg(long, help = "Maximum number of markets to display [default: 20]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of newest events to scan [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+
+    #[arg(
+        long = "tag",
+        value_delimiter = ',',
+        help = "Only include events with one of these tags (slug or label), e.g. politics,sports"
+    )]
+    pub tags: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    #[arg(required = true, num_args = 2.., help = "Market slugs to compare, shown left to right")]
+    pub slugs: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct CompareVenuesArgs {
+    #[arg(help = "Substring (case-insensitive) the market or event title must contain on both venues")]
+    pub topic: String,
+
+    #[arg(
+        long = "venue",
+        value_delimiter = ',',
+        help = "The two venues to match, of polymarket, kalshi, manifold [default: polymarket,kalshi]"
+    )]
+    pub venues: Vec<Venue>,
+
+    #[arg(long, help = "Maximum number of matched pairs to display [default: 20]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of events to fetch from each venue [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+
+    #[arg(
+        long = "min-similarity",
+        default_value_t = 0.3,
+        help = "How much of two titles' wording must overlap to count as the same market, 0 to 1"
+    )]
+    pub min_similarity: f64,
+}
+
+#[derive(Args, Debug)]
+pub struct XarbArgs {
+    #[arg(
+        long = "venue",
+        value_delimiter = ',',
+        help = "Venues to compare, at least two of polymarket, kalshi, manifold [default: polymarket,kalshi]"
+    )]
+    pub venues: Vec<Venue>,
+
+    #[arg(
+        long,
+        default_value_t = 0.05,
+        help = "Smallest price difference worth reporting, in price units (0.05 = 5 points)"
+    )]
+    pub threshold: f64,
+
+    #[arg(
+        long = "min-similarity",
+        default_value_t = 0.5,
+        help = "How much of two titles' wording must overlap to count as the same market, 0 to 1"
+    )]
+    pub min_similarity: f64,
+
+    #[arg(long, help = "Maximum number of gaps to display [default: 20]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of events to fetch from each venue [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct ArbArgs {
+    #[arg(long, help = "Maximum number of markets to display [default: 20]")]
+    pub top: Option<usize>,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of events to scan [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+
+    #[arg(
+        long,
+        default_value_t = 0.01,
+        help = "Fees and slippage per $1 set a gap must clear, as a fraction (0.01 = 1¢)"
+    )]
+    pub fee: f64,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct ServeArgs {
+    #[arg(long, default_value_t = 8080, help = "Port to listen on")]
+    pub port: u16,
+
+    #[arg(
+        long,
+        default_value = "127.0.0.1",
+        help = "Address to bind (use 0.0.0.0 to expose on the LAN)"
+    )]
+    pub host: IpAddr,
+
+    #[arg(long = "cache-ttl", default_value_t = 30, help = "Seconds to reuse fetched data between requests")]
+    pub cache_ttl: u64,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of events to fetch per refresh [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct DaemonArgs {
+    #[arg(
+        long,
+        value_parser = parse_window,
+        default_value = "5m",
+        help = "Time between snapshots: 30m, 1h, ..."
+    )]
+    pub every: Duration,
+
+    #[arg(long, value_name = "PATH", help = "SQLite database to record snapshots into (created if missing)")]
+    pub db: PathBuf,
+
+    #[arg(long, default_value_t = 8080, help = "Port to serve the API, /status and /metrics on")]
+    pub port: u16,
+
+    #[arg(
+        long,
+        default_value = "127.0.0.1",
+        help = "Address to bind (use 0.0.0.0 to expose on the LAN)"
+    )]
+    pub host: IpAddr,
+
+    #[arg(
+        long = "fetch-limit",
+        help = "Number of events to fetch per snapshot [default: 150]"
+    )]
+    pub fetch_limit: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct StatusArgs {
+    #[arg(
+        long,
+        default_value = "http://127.0.0.1:8080",
+        help = "Where the daemon is serving"
+    )]
+    pub url: String,
+}
@@ -0,0 +1,48 @@
+use poly_core::{fetch_markets, find_mispricings, format_price, paint, render_arb, OutputFormat, C};
+
+use crate::cli::ArbArgs;
+use crate::commands::{emit, Context, DEFAULT_FETCH_LIMIT, DEFAULT_TOP};
+
+pub async fn run(args: &ArbArgs, ctx: &Context) -> i32 {
+    let top = args.top.or(ctx.config.top).unwrap_or(DEFAULT_TOP);
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    if top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+    if !(0.0..1.0).contains(&args.fee) {
+        return ctx.invalid("--fee must be between 0 and 1");
+    }
+    // Outcome prices are a list per market, which CSV and HTML rows can't hold.
+    if let Some(code) = ctx.reject_formats("arb", &[OutputFormat::Csv, OutputFormat::Html]) {
+        return code;
+    }
+
+    let rows = match fetch_markets(&args.filter.event_query(fetch_limit)).await {
+        Ok(r) => args.filter.row_filter().apply(r),
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+    let mut found = find_mispricings(&rows, args.fee);
+    found.truncate(top);
+
+    if !ctx.table() {
+        return emit(ctx, &found);
+    }
+
+    let title = paint("Polymarket outcome-sum arbitrage", &(String::from(C::BOLD) + C::CYAN), ctx.color);
+    let summary = paint(
+        &format!("{} markets scanned, fee allowance {}", rows.len(), format_price(Some(args.fee))),
+        C::DIM,
+        ctx.color,
+    );
+    println!("{title}  |  {summary}");
+    if found.is_empty() {
+        let none = "No market's outcome prices sum away from $1 by more than the fee.";
+        println!("{}", paint(none, C::YELLOW, ctx.color));
+    } else {
+        println!("{}", render_arb(&found, ctx.color));
+    }
+    0
+}
@@ -0,0 +1,81 @@
+use std::io::{self, BufRead};
+
+use poly_core::{create_api_key, derive_api_key, OutputFormat, PolyError, Wallet};
+use serde::Serialize;
+use tracing::debug;
+
+use crate::cli::{AuthArgs, AuthCommand};
+use crate::commands::{emit_document, Context};
+use crate::credentials;
+
+// What create-key reports; the secret and passphrase stay in the keyring.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredKey {
+    address: String,
+    api_key: String,
+    nonce: u64,
+}
+
+pub async fn run(args: &AuthArgs, ctx: &Context) -> i32 {
+    match &args.command {
+        AuthCommand::CreateKey { nonce } => create_key(*nonce, ctx).await,
+    }
+}
+
+// The key from POLY_PRIVATE_KEY, the keyring, or else typed in (hidden) or piped on stdin.
+fn private_key() -> Result<String, PolyError> {
+    if let Some(key) = credentials::private_key_from_env() {
+        return Ok(key);
+    }
+    if let Some(key) = credentials::stored_private_key()? {
+        return Ok(key);
+    }
+    let read = if atty::is(atty::Stream::Stdin) {
+        rpassword::prompt_password("Private key (hex): ")
+    } else {
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line).map(|_| line)
+    };
+    read.map_err(|e| PolyError::Auth(format!("failed to read the private key: {e}")))
+}
+
+async fn create_key(nonce: u64, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("auth", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    let key = match private_key() {
+        Ok(k) => k,
+        Err(e) => return ctx.fail("load private key", &e),
+    };
+    let wallet = match Wallet::from_private_key(&key) {
+        Ok(w) => w,
+        Err(e) => return ctx.fail("load private key", &e),
+    };
+    // The CLOB refuses to create a second set for a nonce, but hands the first one back.
+    let creds = match create_api_key(&wallet, nonce).await {
+        Ok(c) => Ok(c),
+        Err(e) => {
+            debug!(error = %e, nonce, "create-api-key refused; deriving the existing credentials");
+            derive_api_key(&wallet, nonce).await
+        }
+    };
+    let creds = match creds {
+        Ok(c) => c,
+        Err(e) => return ctx.fail("create API credentials", &e),
+    };
+    let address = wallet.address();
+    let stored = credentials::store_private_key(&key).and_then(|()| credentials::store_creds(&address, &creds));
+    if let Err(e) = stored {
+        return ctx.fail("store credentials", &e);
+    }
+
+    let report = StoredKey { address, api_key: creds.api_key, nonce };
+    if let Some(code) = emit_document(ctx, &report) {
+        return code;
+    }
+    println!("Stored the private key and API key {} for {} in the OS keyring", report.api_key, report.address);
+    println!("`poly order` and the other trading commands now work without POLY_PRIVATE_KEY");
+    0
+}
@@ -0,0 +1,55 @@
+use poly_core::{fetch_funding, paint, OutputFormat, C};
+
+use crate::cli::BalanceArgs;
+use crate::commands::portfolio::is_address;
+use crate::commands::{emit_document, Context};
+
+pub async fn run(args: &BalanceArgs, ctx: &Context) -> i32 {
+    if !is_address(&args.address) {
+        let address = &args.address;
+        return ctx.invalid(&format!("'{address}' is not a wallet address (expected 0x followed by 40 hex digits)"));
+    }
+    if let Some(code) = ctx.reject_formats("balance", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    let rpc = ctx.rpc(args.rpc_url.as_deref());
+    let funding = match fetch_funding(&rpc, &args.address).await {
+        Ok(f) => f,
+        Err(e) => return ctx.fail("query Polygon", &e),
+    };
+
+    if let Some(code) = emit_document(ctx, &funding) {
+        return code;
+    }
+
+    let title = format!("Balance {}", funding.address);
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    println!("USDC: {}   POL: {:.4}", paint(&format!("${:.2}", funding.usdc), C::CYAN, ctx.color), funding.pol);
+    println!();
+    println!("{:<22} {:>16}   {}", "Spender", "USDC allowance", "Outcome shares");
+    for approval in &funding.approvals {
+        let allowance = match approval.usdc_allowance {
+            None => paint(&format!("{:>16}", "unlimited"), C::GREEN, ctx.color),
+            Some(a) if a > 0.0 => format!("{:>16}", format!("${a:.2}")),
+            Some(_) => paint(&format!("{:>16}", "none"), C::RED, ctx.color),
+        };
+        let shares = if approval.shares_approved {
+            paint("approved", C::GREEN, ctx.color)
+        } else {
+            paint("not approved", C::RED, ctx.color)
+        };
+        println!("{:<22} {allowance}   {shares}", approval.spender);
+    }
+    println!();
+    if funding.ready() {
+        println!("{}", paint("Approvals are in place for buying and selling", C::GREEN, ctx.color));
+    } else {
+        let note = "Missing approvals: buying needs a USDC allowance, selling needs outcome shares approved";
+        println!("{}", paint(note, C::YELLOW, ctx.color));
+    }
+    if funding.usdc <= 0.0 {
+        println!("{}", paint("No USDC to trade with", C::YELLOW, ctx.color));
+    }
+    0
+}
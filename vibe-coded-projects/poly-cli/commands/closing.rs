@@ -0,0 +1,48 @@
+use chrono::Utc;
+use poly_core::{fetch_markets, paint, sort_rows, EventOrder, EventQuery, SortKey, SortSpec, C};
+
+use crate::cli::ClosingArgs;
+use crate::commands::{emit_rows, Context, DEFAULT_FETCH_LIMIT, DEFAULT_TOP};
+
+pub async fn run(args: &ClosingArgs, ctx: &Context) -> i32 {
+    let top = args.top.or(ctx.config.top).unwrap_or(DEFAULT_TOP);
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    if top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+
+    let now = Utc::now();
+    let Some(end_before) = now.checked_add_signed(args.within) else {
+        return ctx.invalid("--within reaches past any representable date");
+    };
+    let query = EventQuery {
+        order: EventOrder::Ending,
+        end_after: Some(now),
+        end_before: Some(end_before),
+        ..args.filter.event_query(fetch_limit)
+    };
+    // Event end dates bound the fetch, but markets inside an event can end at other times.
+    let mut rows = match fetch_markets(&query).await {
+        Ok(r) => args.filter.row_filter().apply(r),
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+    rows.retain(|row| row.ends_within(args.within));
+    sort_rows(&mut rows, &[SortSpec::new(SortKey::End)]);
+    rows.truncate(top);
+
+    let title = format!("Polymarket closing within {}h", args.within.num_hours());
+    if !ctx.table() {
+        return emit_rows(ctx, &rows, &title);
+    }
+
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if rows.is_empty() {
+        println!("{}", paint("No open markets end in that window.", C::YELLOW, ctx.color));
+    } else {
+        println!("{}", ctx.render_rows(&rows, top));
+    }
+    0
+}
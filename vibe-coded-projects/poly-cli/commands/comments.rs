@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use poly_core::{fetch_comments, fetch_event, paint, render_comment_line, Comment, OutputFormat, PolyError, C};
+
+use crate::cli::CommentsArgs;
+use crate::commands::{emit, emit_line, Context};
+
+// Pages a --follow poll reads back through to reach the last comment it showed.
+const CATCH_UP_PAGES: usize = 10;
+
+// The comments since the last poll, newest first. Pages back from the newest until one reaches a
+// comment already seen, so a burst of more than --limit between polls isn't dropped.
+async fn fetch_new(event_id: &str, limit: usize, seen: &HashSet<String>) -> Result<Vec<Comment>, PolyError> {
+    let mut comments = Vec::new();
+    for page in 0..CATCH_UP_PAGES {
+        let batch = fetch_comments(event_id, limit, page * limit).await?;
+        let caught_up =
+            batch.len() < limit || batch.iter().any(|c| c.id.as_ref().is_some_and(|id| seen.contains(id)));
+        comments.extend(batch);
+        if caught_up {
+            return Ok(comments);
+        }
+    }
+    eprintln!("More than {} new comments since the last poll; older ones were skipped", comments.len());
+    Ok(comments)
+}
+
+pub async fn run(args: &CommentsArgs, ctx: &Context) -> i32 {
+    if args.limit < 1 {
+        return ctx.invalid("--limit must be >= 1");
+    }
+    if args.interval < 2 {
+        return ctx.invalid("--interval must be >= 2");
+    }
+    if let Some(code) = ctx.reject_formats("comments", &[OutputFormat::Html]) {
+        return code;
+    }
+    if args.follow && matches!(ctx.format, OutputFormat::Csv | OutputFormat::Yaml) {
+        return ctx.invalid(&format!("--format {} can't be combined with --follow", ctx.format));
+    }
+
+    // Comments hang off the event's numeric id, not its slug.
+    let event = match fetch_event(&args.slug).await {
+        Ok(e) => e,
+        Err(e) => return ctx.fail("fetch event", &e),
+    };
+    let Some(event_id) = event.id.clone() else {
+        return ctx.fail("fetch event", &PolyError::Shape("event without an id"));
+    };
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut first = true;
+
+    loop {
+        let fetched = if first {
+            fetch_comments(&event_id, args.limit, 0).await
+        } else {
+            fetch_new(&event_id, args.limit, &seen).await
+        };
+        let comments = match fetched {
+            Ok(c) => c,
+            Err(e) if args.follow && !first => {
+                eprintln!("Failed to fetch comments: {e}");
+                tokio::time::sleep(Duration::from_secs(args.interval)).await;
+                continue;
+            }
+            Err(e) => return ctx.fail("fetch comments", &e),
+        };
+
+        if !ctx.table() && !args.follow {
+            return emit(ctx, &comments);
+        }
+        // Newest first from the API; oldest first on screen so the feed scrolls forward in time.
+        let fresh: Vec<&Comment> =
+            comments.iter().rev().filter(|c| c.id.as_ref().is_none_or(|id| seen.insert(id.clone()))).collect();
+        if ctx.format == OutputFormat::Ndjson {
+            let code = emit(ctx, &fresh);
+            if code != 0 {
+                return code;
+            }
+        } else {
+            if first && ctx.table() {
+                let title = format!("Comments on {}", event.title.as_deref().unwrap_or(&args.slug));
+                println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+                if fresh.is_empty() {
+                    let waiting = if args.follow { "No comments yet; waiting for new ones" } else { "No comments yet" };
+                    println!("{}", paint(waiting, C::DIM, ctx.color));
+                }
+            }
+            for comment in fresh {
+                if ctx.json() {
+                    let code = emit_line(ctx, comment);
+                    if code != 0 {
+                        return code;
+                    }
+                } else {
+                    println!("{}", render_comment_line(comment, ctx.color));
+                }
+            }
+        }
+
+        if !args.follow {
+            return 0;
+        }
+        first = false;
+        tokio::time::sleep(Duration::from_secs(args.interval)).await;
+    }
+}
@@ -0,0 +1,30 @@
+use poly_core::{attach_quotes, fetch_market_rows, paint, render_comparison, PolyError, C};
+
+use crate::cli::CompareArgs;
+use crate::commands::{emit_rows, Context};
+
+pub async fn run(args: &CompareArgs, ctx: &Context) -> i32 {
+    let mut rows = match fetch_market_rows(&args.slugs).await {
+        Ok((rows, missing)) if rows.is_empty() => {
+            return ctx.fail("fetch markets", &PolyError::NotFound(format!("markets {}", missing.join(", "))));
+        }
+        Ok((rows, missing)) => {
+            if !missing.is_empty() {
+                eprintln!("Not found, left out of the comparison: {}", missing.join(", "));
+            }
+            rows
+        }
+        Err(e) => return ctx.fail("fetch markets", &e),
+    };
+    attach_quotes(&mut rows).await;
+
+    let title = format!("Comparing {} markets", rows.len());
+    if !ctx.table() {
+        return emit_rows(ctx, &rows, &title);
+    }
+
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    println!();
+    println!("{}", render_comparison(&rows, ctx.color));
+    0
+}
@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+
+use poly_core::{match_markets, paint, render_venue_matches, search_rows, EventQuery, Matcher, OutputFormat, Venue, C};
+
+use crate::cli::CompareVenuesArgs;
+use crate::commands::{emit, Context, DEFAULT_FETCH_LIMIT, DEFAULT_TOP};
+
+pub async fn run(args: &CompareVenuesArgs, ctx: &Context) -> i32 {
+    let top = args.top.or(ctx.config.top).unwrap_or(DEFAULT_TOP);
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    if top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+    if !(0.0..=1.0).contains(&args.min_similarity) {
+        return ctx.invalid("--min-similarity must be between 0 and 1");
+    }
+    let mut venues = ctx.venues(&args.venues, &[Venue::Polymarket, Venue::Kalshi]);
+    let mut seen = HashSet::new();
+    venues.retain(|venue| seen.insert(*venue));
+    let [left, right] = venues[..] else {
+        return ctx.invalid("compare-venues matches exactly two venues");
+    };
+    // Each match nests a full row per venue, which CSV and HTML rows can't hold.
+    if let Some(code) = ctx.reject_formats("compare-venues", &[OutputFormat::Csv, OutputFormat::Html]) {
+        return code;
+    }
+
+    let query = EventQuery::new(fetch_limit);
+    let (left_rows, right_rows) = match tokio::try_join!(left.fetch_markets(&query), right.fetch_markets(&query)) {
+        Ok(rows) => rows,
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+    let matcher = Matcher::Substring(args.topic.to_lowercase());
+    let (left_rows, right_rows) = (search_rows(left_rows, &matcher), search_rows(right_rows, &matcher));
+    let mut matches = match_markets(&left_rows, &right_rows, args.min_similarity);
+    matches.truncate(top);
+
+    if !ctx.table() {
+        return emit(ctx, &matches);
+    }
+
+    let title = paint(
+        &format!("{left} vs {right}: \"{}\"", args.topic),
+        &(String::from(C::BOLD) + C::CYAN),
+        ctx.color,
+    );
+    let summary = paint(
+        &format!("{} {left} and {} {right} markets matched the topic", left_rows.len(), right_rows.len()),
+        C::DIM,
+        ctx.color,
+    );
+    println!("{title}  |  {summary}");
+    if matches.is_empty() {
+        let none = "No markets on one venue look like a market on the other.";
+        println!("{}", paint(none, C::YELLOW, ctx.color));
+    } else {
+        println!("{}", render_venue_matches(&matches, ctx.color));
+    }
+    0
+}
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use poly_core::{
+    align_histories, differences, fetch_price_history, format_price, paint, pearson, sparkline, Aligned,
+    OutputFormat, PolyError, PricePoint, C,
+};
+use serde::Serialize;
+
+use crate::cli::CorrelateArgs;
+use crate::commands::history::resolve_token;
+use crate::commands::{emit_document, Context};
+
+const SPARK_WIDTH: usize = 60;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report<'a> {
+    a: &'a str,
+    b: &'a str,
+    samples: usize,
+    correlation: Option<f64>,
+    change_correlation: Option<f64>,
+    series: &'a Aligned,
+}
+
+async fn history(
+    market: &str,
+    args: &CorrelateArgs,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<(String, Vec<PricePoint>), PolyError> {
+    let (token, title) = resolve_token(market, None).await?;
+    let points = fetch_price_history(&token, start.timestamp(), end.timestamp(), args.interval.num_minutes()).await?;
+    Ok((title, points))
+}
+
+fn strength(r: f64) -> &'static str {
+    match r.abs() {
+        x if x >= 0.7 => "strong",
+        x if x >= 0.4 => "moderate",
+        _ => "weak",
+    }
+}
+
+fn paint_r(r: Option<f64>, color: bool) -> String {
+    match r {
+        Some(r) => {
+            let style = if r >= 0.0 { C::GREEN } else { C::RED };
+            format!("{} ({})", paint(&format!("{r:+.2}"), style, color), strength(r))
+        }
+        None => paint("n/a", C::DIM, color),
+    }
+}
+
+pub async fn run(args: &CorrelateArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("correlate", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    let end = Utc::now();
+    let Some(start) = end.checked_sub_signed(args.range) else {
+        return ctx.invalid("--range reaches back past any representable date");
+    };
+    let (a, b) = match tokio::try_join!(history(&args.a, args, start, end), history(&args.b, args, start, end)) {
+        Ok(pair) => pair,
+        Err(e) => return ctx.fail("fetch price history", &e),
+    };
+    let aligned = align_histories(&a.1, &b.1);
+    let correlation = pearson(&aligned.a, &aligned.b);
+    let change_correlation = pearson(&differences(&aligned.a), &differences(&aligned.b));
+
+    let report = Report {
+        a: &a.0,
+        b: &b.0,
+        samples: aligned.t.len(),
+        correlation,
+        change_correlation,
+        series: &aligned,
+    };
+    if let Some(code) = emit_document(ctx, &report) {
+        return code;
+    }
+
+    println!("{}", paint("Price correlation", &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if aligned.t.len() < 2 {
+        println!("{}", paint("Not enough overlapping price history to compare.", C::YELLOW, ctx.color));
+        return 0;
+    }
+    for (label, title, series, style) in [("A", &a.0, &aligned.a, C::CYAN), ("B", &b.0, &aligned.b, C::YELLOW)] {
+        let first = series.first().copied();
+        let last = series.last().copied();
+        println!(
+            "{} {}  {} → {}  {}",
+            paint(label, C::BOLD, ctx.color),
+            paint(&sparkline(series, SPARK_WIDTH), style, ctx.color),
+            format_price(first),
+            format_price(last),
+            paint(title, C::WHITE, ctx.color),
+        );
+    }
+    let label = |text: &str| paint(text, C::DIM, ctx.color);
+    println!(
+        "\n{} {}  {} {}  {} {}",
+        label("Pearson (prices):"),
+        paint_r(correlation, ctx.color),
+        label("Pearson (changes):"),
+        paint_r(change_correlation, ctx.color),
+        label("Samples:"),
+        aligned.t.len(),
+    );
+    0
+}
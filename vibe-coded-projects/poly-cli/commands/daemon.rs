@@ -0,0 +1,163 @@
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use poly_core::{fetch_markets, format_span, EventQuery, SnapshotDb};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::DaemonArgs;
+use crate::commands::serve::{self, Cache};
+use crate::commands::{Context, Session, DEFAULT_FETCH_LIMIT};
+
+// What the daemon reports at /status, and what `poly status` reads back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaemonStatus {
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub every_secs: u64,
+    pub db: String,
+    pub snapshots: u64,
+    pub failures: u64,
+    pub last_snapshot_at: Option<DateTime<Utc>>,
+    // Markets in the last recorded snapshot.
+    pub last_markets: usize,
+    pub last_error: Option<String>,
+    pub next_snapshot_at: Option<DateTime<Utc>>,
+}
+
+type SharedStatus = Arc<Mutex<DaemonStatus>>;
+
+async fn status(State(status): State<SharedStatus>) -> Json<DaemonStatus> {
+    Json(status.lock().expect("status lock").clone())
+}
+
+// Prometheus text exposition of the same counters.
+async fn metrics(State(status): State<SharedStatus>) -> String {
+    let status = status.lock().expect("status lock").clone();
+    let uptime = (Utc::now() - status.started_at).num_seconds();
+    let last = status.last_snapshot_at.map_or(0, |at| at.timestamp());
+    let (failures, markets) = (status.failures as i64, status.last_markets as i64);
+    let metrics = [
+        ("poly_snapshots_total", "counter", "Snapshots recorded since the daemon started.", status.snapshots as i64),
+        ("poly_snapshot_failures_total", "counter", "Snapshots that failed to fetch or record.", failures),
+        ("poly_last_snapshot_timestamp_seconds", "gauge", "Unix time of the last recorded snapshot.", last),
+        ("poly_last_snapshot_markets", "gauge", "Markets in the last recorded snapshot.", markets),
+        ("poly_uptime_seconds", "gauge", "Seconds since the daemon started.", uptime),
+    ];
+    let mut out = String::new();
+    for (name, kind, help, value) in metrics {
+        let _ = write!(out, "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n");
+    }
+    out
+}
+
+// Records a snapshot every `--every` into the database and serves the latest one over HTTP, so
+// the API answers from what was just recorded instead of fetching again. It runs in the
+// foreground; leave detaching and restarts to systemd or similar.
+pub async fn run(args: &DaemonArgs, ctx: &Context) -> i32 {
+    let Ok(every) = args.every.to_std() else {
+        return ctx.invalid("--every must be positive");
+    };
+    let Some(ttl) = every.checked_mul(2) else {
+        return ctx.invalid("--every is too long");
+    };
+    let mut db = match SnapshotDb::open(&args.db) {
+        Ok(db) => db,
+        Err(e) => return ctx.fail("open snapshot database", &e),
+    };
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    let query = EventQuery::new(fetch_limit);
+    // Rows outlive one interval so a failed snapshot doesn't send every API request upstream.
+    let cache = Arc::new(Cache::new(query.clone(), ttl));
+    let shared = Arc::new(Mutex::new(DaemonStatus {
+        pid: std::process::id(),
+        started_at: Utc::now(),
+        every_secs: every.as_secs(),
+        db: args.db.display().to_string(),
+        snapshots: 0,
+        failures: 0,
+        last_snapshot_at: None,
+        last_markets: 0,
+        last_error: None,
+        next_snapshot_at: None,
+    }));
+
+    let app = serve::router(cache.clone()).merge(
+        Router::new()
+            .route("/status", get(status))
+            .route("/metrics", get(metrics))
+            .with_state(shared.clone()),
+    );
+    let addr = SocketAddr::new(args.host, args.port);
+    let listener = match tokio::net::TcpListener::bind(addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("Failed to bind {addr}: {e}");
+            return 1;
+        }
+    };
+    let mut server = tokio::spawn(async move { axum::serve(listener, app).await });
+    eprintln!(
+        "Recording a snapshot every {} into {}; serving on http://{addr} (endpoints: /top, /search?q=, \
+         /market/<slug>, /status, /metrics)",
+        format_span(args.every),
+        args.db.display()
+    );
+
+    let session = Session::start();
+    session.catch_interrupts();
+    loop {
+        let taken_at = Utc::now();
+        let recorded = match fetch_markets(&query).await {
+            Ok(rows) => match db.record(taken_at, &rows) {
+                Ok(_) => Ok(rows),
+                Err(e) => Err(format!("Failed to record snapshot: {e}")),
+            },
+            Err(e) => Err(format!("Failed to fetch data: {e}")),
+        };
+        {
+            let mut status = shared.lock().expect("status lock");
+            match &recorded {
+                Ok(rows) => {
+                    status.snapshots += 1;
+                    status.last_snapshot_at = Some(taken_at);
+                    status.last_markets = rows.len();
+                    status.last_error = None;
+                }
+                Err(e) => {
+                    status.failures += 1;
+                    status.last_error = Some(e.clone());
+                }
+            }
+            status.next_snapshot_at = Utc::now().checked_add_signed(args.every);
+        }
+        match recorded {
+            Ok(rows) => {
+                cache.store(rows).await;
+                session.refreshed();
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(every) => {}
+            _ = session.interrupted() => break,
+            served = &mut server => {
+                match served {
+                    Ok(Err(e)) => eprintln!("Server error: {e}"),
+                    Err(e) => eprintln!("Server error: {e}"),
+                    Ok(Ok(())) => eprintln!("Server stopped"),
+                }
+                return 1;
+            }
+        }
+    }
+
+    eprintln!("{}", session.summary());
+    0
+}
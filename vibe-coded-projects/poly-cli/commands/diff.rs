@@ -0,0 +1,75 @@
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use poly_core::{
+    diff_snapshots, paint, parse_snapshot, render_snapshot_diff, OutputFormat, SnapshotDb, SnapshotRow, C,
+};
+
+use crate::cli::DiffArgs;
+use crate::commands::{emit_document, Context};
+
+pub async fn run(args: &DiffArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("diff", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+    if args.top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+
+    let (before, after, span) = match (&args.db, args.snapshots.as_slice()) {
+        (Some(path), _) => {
+            let to = args.to.unwrap_or_else(Utc::now);
+            let Some(from) = args.from.filter(|from| *from < to) else {
+                return ctx.invalid("--from must be earlier than --to");
+            };
+            let db = match SnapshotDb::open(path) {
+                Ok(db) => db,
+                Err(e) => return ctx.fail("open snapshot database", &e),
+            };
+            let before = match db.snapshot_at(from) {
+                Ok(Some(found)) => found,
+                Ok(None) => return ctx.invalid(&format!("nothing was recorded in {} by {from}", path.display())),
+                Err(e) => return ctx.fail("read snapshot database", &e),
+            };
+            let after = match db.snapshot_at(to) {
+                Ok(Some(found)) => found,
+                Ok(None) => return ctx.invalid(&format!("nothing was recorded in {} by {to}", path.display())),
+                Err(e) => return ctx.fail("read snapshot database", &e),
+            };
+            let span = format!("{} → {}", stamp(before.0), stamp(after.0));
+            (before.1, after.1, span)
+        }
+        (None, [earlier, later]) => {
+            let before = match read(earlier) {
+                Ok(rows) => rows,
+                Err(e) => return ctx.invalid(&e),
+            };
+            let after = match read(later) {
+                Ok(rows) => rows,
+                Err(e) => return ctx.invalid(&e),
+            };
+            (before, after, format!("{} → {}", earlier.display(), later.display()))
+        }
+        (None, _) => return ctx.invalid("pass two snapshot files, or --db with --from"),
+    };
+
+    let diff = diff_snapshots(&before, &after, args.top);
+    if let Some(code) = emit_document(ctx, &diff) {
+        return code;
+    }
+
+    let title = format!("Top {} changes: {span}", args.top);
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    println!("{}", render_snapshot_diff(&diff, ctx.color));
+    0
+}
+
+fn read(path: &Path) -> Result<Vec<SnapshotRow>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    parse_snapshot(&text).map_err(|e| format!("{} isn't a saved snapshot: {e}", path.display()))
+}
+
+fn stamp(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M UTC").to_string()
+}
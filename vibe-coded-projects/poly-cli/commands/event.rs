@@ -0,0 +1,22 @@
+use poly_core::{fetch_event, render_event_detail, rows_from_events, OutputFormat};
+
+use crate::cli::EventArgs;
+use crate::commands::{emit_document, emit_rows, Context};
+
+pub async fn run(args: &EventArgs, ctx: &Context) -> i32 {
+    let event = match fetch_event(&args.slug).await {
+        Ok(e) => e,
+        Err(e) => return ctx.fail("fetch event", &e),
+    };
+
+    if let Some(code) = emit_document(ctx, &event) {
+        return code;
+    }
+    if OutputFormat::ROW_ONLY.contains(&ctx.format) {
+        let rows = rows_from_events(std::slice::from_ref(&event));
+        return emit_rows(ctx, &rows, event.display_title());
+    }
+
+    println!("{}", render_event_detail(&event, ctx.color));
+    0
+}
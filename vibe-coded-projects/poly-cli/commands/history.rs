@@ -0,0 +1,91 @@
+use chrono::Utc;
+use poly_core::{
+    fetch_market, fetch_price_history, format_percent, format_price, paint, render_price_chart, OutputFormat,
+    PolyError, C,
+};
+
+use crate::cli::HistoryArgs;
+use crate::commands::{emit, Context};
+
+const CHART_WIDTH: usize = 72;
+const CHART_HEIGHT: usize = 14;
+
+// CLOB token ids are long decimal numbers; anything else is treated as a market slug. Returns the
+// token and a title naming the market and outcome.
+pub async fn resolve_token(slug_or_token: &str, outcome: Option<&str>) -> Result<(String, String), PolyError> {
+    if slug_or_token.chars().all(|c| c.is_ascii_digit()) {
+        return Ok((slug_or_token.to_string(), format!("Token {slug_or_token}")));
+    }
+
+    let market = fetch_market(slug_or_token).await?;
+    let index = match outcome {
+        Some(wanted) => market
+            .outcomes
+            .iter()
+            .position(|o| o.eq_ignore_ascii_case(wanted))
+            .ok_or_else(|| PolyError::NotFound(format!("outcome '{wanted}' in market '{slug_or_token}'")))?,
+        None => 0,
+    };
+    let token = market
+        .clob_token_ids
+        .get(index)
+        .cloned()
+        .ok_or_else(|| PolyError::NotFound(format!("CLOB token for market '{slug_or_token}'")))?;
+    let outcome = market.outcomes.get(index).map(String::as_str).unwrap_or("?");
+    Ok((token, format!("{} — {outcome}", market.display_title())))
+}
+
+pub async fn run(args: &HistoryArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("history", &[OutputFormat::Html]) {
+        return code;
+    }
+
+    let (token, title) = match resolve_token(&args.market, args.outcome.as_deref()).await {
+        Ok(t) => t,
+        Err(e) => return ctx.fail("resolve market", &e),
+    };
+
+    let end = Utc::now();
+    let Some(start) = end.checked_sub_signed(args.range) else {
+        return ctx.invalid("--range reaches back past any representable date");
+    };
+    let fidelity = args.interval.num_minutes();
+    let points = match fetch_price_history(&token, start.timestamp(), end.timestamp(), fidelity).await {
+        Ok(p) => p,
+        Err(e) => return ctx.fail("fetch price history", &e),
+    };
+
+    if !ctx.table() {
+        return emit(ctx, &points);
+    }
+
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    println!("{}", render_price_chart(&points, CHART_WIDTH, CHART_HEIGHT, ctx.color));
+
+    if let (Some(first), Some(last)) = (points.first(), points.last()) {
+        let high = points.iter().map(|p| p.p).fold(f64::NEG_INFINITY, f64::max);
+        let low = points.iter().map(|p| p.p).fold(f64::INFINITY, f64::min);
+        let change = (last.p - first.p) * 100.0;
+        let change_color = if change >= 0.0 { C::GREEN } else { C::RED };
+        let change_text = format!("{change:+.1} pts ({})", format_percent(relative(first.p, last.p)));
+        let label = |text: &str| paint(text, C::DIM, ctx.color);
+        println!(
+            "\n{} {}  {} {}  {} {}  {} {}  {} {}",
+            label("Open:"),
+            format_price(Some(first.p)),
+            label("Close:"),
+            paint(&format_price(Some(last.p)), C::YELLOW, ctx.color),
+            label("Change:"),
+            paint(&change_text, change_color, ctx.color),
+            label("High:"),
+            format_price(Some(high)),
+            label("Low:"),
+            format_price(Some(low)),
+        );
+    }
+    0
+}
+
+fn relative(from: f64, to: f64) -> Option<f64> {
+    (from != 0.0).then(|| (to - from) / from * 100.0)
+}
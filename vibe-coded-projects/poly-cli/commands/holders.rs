@@ -0,0 +1,80 @@
+use poly_core::{
+    fetch_holders, fetch_market, fetch_open_interest, lookup_ens_names, paint, render_holders, JsonRpc, OutputFormat,
+    C, DEFAULT_ENS_RPC_URL,
+};
+use tracing::warn;
+
+use crate::cli::HoldersArgs;
+use crate::commands::{emit, Context};
+
+pub async fn run(args: &HoldersArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("holders", &[OutputFormat::Html]) {
+        return code;
+    }
+    if args.limit < 1 {
+        return ctx.invalid("--limit must be >= 1");
+    }
+
+    let market = match fetch_market(&args.slug).await {
+        Ok(m) => m,
+        Err(e) => return ctx.fail("fetch market", &e),
+    };
+    let Some(condition_id) = market.condition_id.as_deref() else {
+        return ctx.invalid(&format!("market '{}' has no condition id", args.slug));
+    };
+    let (holders, supply) = tokio::join!(fetch_holders(condition_id, args.limit), fetch_open_interest(condition_id));
+    let mut holders = match holders {
+        Ok(h) => h,
+        Err(e) => return ctx.fail("fetch holders", &e),
+    };
+    // Open interest stands in for the supply, so the percentages are approximate. Without it
+    // they're left blank rather than failing the whole view.
+    let supply = supply.unwrap_or_else(|e| {
+        warn!(error = %e, "no open interest; leaving % of supply blank");
+        None
+    });
+
+    if !args.no_ens {
+        let rpc = JsonRpc::new(args.ens_rpc_url.as_deref().unwrap_or(DEFAULT_ENS_RPC_URL));
+        let mut wallets: Vec<String> = holders.iter().filter_map(|h| h.proxy_wallet.clone()).collect();
+        wallets.sort();
+        wallets.dedup();
+        let names = lookup_ens_names(&rpc, &wallets).await;
+        for holder in &mut holders {
+            holder.ens = holder.proxy_wallet.as_ref().and_then(|w| names.get(&w.to_ascii_lowercase()).cloned());
+        }
+    }
+    for holder in &mut holders {
+        holder.supply_pct = supply.filter(|&s| s > 0.0).zip(holder.amount).map(|(s, amount)| amount / s * 100.0);
+    }
+
+    if !ctx.table() {
+        return emit(ctx, &holders);
+    }
+
+    let title = format!("Holders {}", market.display_title());
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if let Some(supply) = supply {
+        let outstanding = format!("~{supply:.0} shares of each outcome outstanding (from open interest)");
+        println!("{}", paint(&outstanding, C::DIM, ctx.color));
+    }
+    if holders.is_empty() {
+        println!("{}", paint("No holders", C::DIM, ctx.color));
+        return 0;
+    }
+    for (index, outcome) in market.outcomes.iter().enumerate() {
+        let token = market.clob_token_ids.get(index);
+        let side: Vec<_> = holders
+            .iter()
+            .filter(|h| h.outcome_index == Some(index) || (h.outcome_index.is_none() && h.asset.as_ref() == token))
+            .cloned()
+            .collect();
+        if side.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{}", paint(outcome, &(String::from(C::BOLD) + C::YELLOW), ctx.color));
+        println!("{}", render_holders(&side, ctx.color));
+    }
+    0
+}
@@ -0,0 +1,59 @@
+use poly_core::{estimate_impact, fetch_order_market, fetch_orderbook, format_price, paint, OutputFormat, Side, C};
+
+use crate::cli::ImpactArgs;
+use crate::commands::{emit_document, Context};
+
+pub async fn run(args: &ImpactArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("impact", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+    if !(args.size > 0.0 && args.size.is_finite()) {
+        return ctx.invalid("--size must be positive");
+    }
+
+    let token_id = args.token_id.trim();
+    let (book, market) = tokio::join!(fetch_orderbook(token_id), fetch_order_market(token_id));
+    let book = match book {
+        Ok(b) => b,
+        Err(e) => return ctx.fail("fetch orderbook", &e),
+    };
+    let market = match market {
+        Ok(m) => m,
+        Err(e) => return ctx.fail("fetch fee rate", &e),
+    };
+    let impact = estimate_impact(&book, args.side, args.size, market.fee_rate_bps);
+
+    if let Some(code) = emit_document(ctx, &impact) {
+        return code;
+    }
+
+    let title = format!("Market {} of {:.2} shares of {token_id}", impact.side, impact.size);
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if impact.filled <= 0.0 {
+        let side = if args.side == Side::Buy { "asks" } else { "bids" };
+        println!("{}", paint(&format!("No {side} in the book"), C::YELLOW, ctx.color));
+        return 0;
+    }
+    if impact.filled < impact.size {
+        let short = format!("Only {:.2} of {:.2} shares are available in the book", impact.filled, impact.size);
+        println!("{}", paint(&short, C::YELLOW, ctx.color));
+    }
+    let plural = if impact.levels == 1 { "" } else { "s" };
+    println!("Midpoint:      {}", format_price(impact.midpoint));
+    println!(
+        "Average fill:  {} over {} level{plural} (worst {})",
+        paint(&format_price(impact.avg_price), C::YELLOW, ctx.color),
+        impact.levels,
+        format_price(impact.worst_price)
+    );
+    if let (Some(slippage), Some(pct)) = (impact.slippage, impact.slippage_pct) {
+        let line = format!("{:+.2}¢ per share ({pct:+.2}% vs midpoint)", slippage * 100.0);
+        let color = if slippage > 0.0 { C::RED } else { C::GREEN };
+        println!("Slippage:      {}", paint(&line, color, ctx.color));
+    }
+    println!("Notional:      ${:.2}", impact.notional);
+    println!("Fees:          ${:.2} ({} bps base rate)", impact.fee, market.fee_rate_bps);
+    let net = if impact.side == Side::Buy { "Total cost:" } else { "Net proceeds:" };
+    println!("{net:<14} {}", paint(&format!("${:.2}", impact.net()), C::CYAN, ctx.color));
+    0
+}
@@ -0,0 +1,35 @@
+use poly_core::{fetch_leaderboard, paint, render_leaderboard, LeaderboardMetric, OutputFormat, C};
+
+use crate::cli::LeaderboardArgs;
+use crate::commands::{emit, Context};
+
+pub async fn run(args: &LeaderboardArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("leaderboard", &[OutputFormat::Html]) {
+        return code;
+    }
+    if args.limit < 1 {
+        return ctx.invalid("--limit must be >= 1");
+    }
+
+    let entries = match fetch_leaderboard(args.window, args.by, args.limit).await {
+        Ok(e) => e,
+        Err(e) => return ctx.fail("fetch leaderboard", &e),
+    };
+
+    if !ctx.table() {
+        return emit(ctx, &entries);
+    }
+
+    let ranking = match args.by {
+        LeaderboardMetric::Profit => "profit",
+        LeaderboardMetric::Volume => "volume",
+    };
+    let title = format!("Leaderboard by {ranking} ({})", args.window);
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if entries.is_empty() {
+        println!("{}", paint("No traders ranked for this window", C::DIM, ctx.color));
+        return 0;
+    }
+    println!("{}", render_leaderboard(&entries, args.by, ctx.color));
+    0
+}
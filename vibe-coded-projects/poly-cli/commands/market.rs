@@ -0,0 +1,22 @@
+use poly_core::{fetch_market, render_market_detail, OutputFormat};
+
+use crate::cli::MarketArgs;
+use crate::commands::{emit_document, Context};
+
+pub async fn run(args: &MarketArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("market", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    let market = match fetch_market(&args.slug).await {
+        Ok(m) => m,
+        Err(e) => return ctx.fail("fetch market", &e),
+    };
+
+    if let Some(code) = emit_document(ctx, &market) {
+        return code;
+    }
+
+    println!("{}", render_market_detail(&market, ctx.color));
+    0
+}
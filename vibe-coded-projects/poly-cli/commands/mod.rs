@@ -0,0 +1,557 @@
+use std::ffi::OsString;
+use std::future::Future;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use poly_core::{
+    fetch_market, format_span, render_html, render_table_with, select, supports_color, terminal_width, write_csv,
+    write_ndjson, Column, ErrorReport, JsonRpc, OutputFormat, PolyError, Query, Row, TableLayout, Venue,
+    DEFAULT_RPC_URL,
+};
+use ratatui::crossterm::cursor::MoveTo;
+use ratatui::crossterm::queue;
+use ratatui::crossterm::style::Print;
+use ratatui::crossterm::terminal::{Clear, ClearType};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{mpsc, Notify};
+
+use crate::cli::GlobalArgs;
+use crate::config::Config;
+
+pub mod arb;
+pub mod auth;
+pub mod balance;
+pub mod closing;
+pub mod comments;
+pub mod compare;
+pub mod compare_venues;
+pub mod correlate;
+pub mod daemon;
+pub mod diff;
+pub mod event;
+pub mod history;
+pub mod holders;
+pub mod impact;
+pub mod leaderboard;
+pub mod market;
+pub mod movers;
+pub mod new;
+pub mod order;
+pub mod orderbook;
+pub mod orders;
+pub mod portfolio;
+pub mod quote;
+pub mod resolved;
+pub mod search;
+pub mod serve;
+pub mod size;
+pub mod sports;
+pub mod status;
+pub mod tags;
+pub mod top;
+pub mod trades;
+pub mod verify;
+pub mod watch;
+pub mod watchlist;
+pub mod xarb;
+
+pub const DEFAULT_TOP: usize = 20;
+pub const DEFAULT_FETCH_LIMIT: usize = 150;
+// The smaller pool `--fields minimal` fetches: still a few times --top's default, so filters and
+// per-event limits have something to choose from.
+pub const MINIMAL_FETCH_LIMIT: usize = 50;
+// Seconds between refreshes in watch mode.
+pub const DEFAULT_INTERVAL: u64 = 30;
+
+// Shared state handed to every subcommand after the config file has been loaded.
+pub struct Context {
+    pub format: OutputFormat,
+    pub color: bool,
+    // Market-row columns from --columns or the config; None keeps each view's own layout.
+    pub columns: Option<Vec<Column>>,
+    // --select and --where, applied to JSON and NDJSON output.
+    pub query: Query,
+    pub config: Config,
+}
+
+impl Context {
+    pub fn new(global: &GlobalArgs, config: Config) -> Self {
+        Context {
+            format: if global.json {
+                OutputFormat::Json
+            } else {
+                global.format.unwrap_or_default()
+            },
+            color: supports_color(global.no_color || config.color == Some(false)),
+            columns: columns(&global.columns, &config),
+            query: Query { select: global.select.clone(), conditions: global.filter.clone() },
+            config,
+        }
+    }
+
+    pub fn json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    pub fn table(&self) -> bool {
+        self.format == OutputFormat::Table
+    }
+
+    // Table layout for market rows: the chosen columns, fitted to the terminal when there is one.
+    pub fn layout(&self) -> TableLayout {
+        TableLayout { columns: self.columns.clone(), width: terminal_width() }
+    }
+
+    pub fn render_rows(&self, rows: &[Row], top: usize) -> String {
+        render_table_with(rows, top, &self.layout(), self.color)
+    }
+
+    // For subcommands whose output isn't a list of market rows, only table and JSON apply.
+    pub fn reject_formats(&self, command: &str, unsupported: &[OutputFormat]) -> Option<i32> {
+        if unsupported.contains(&self.format) {
+            return Some(self.invalid(&format!("--format {} is not supported by `{command}`", self.format)));
+        }
+        None
+    }
+
+    // Reports an error and returns its exit code. Normally that's a sentence on stderr; with
+    // JSON output it's an `{"error": {...}}` object on stdout, so pipelines always get JSON.
+    fn report(&self, report: ErrorReport) -> i32 {
+        if self.json() {
+            print_json(&json!({ "error": report }));
+        } else {
+            eprintln!("{}", report.message);
+        }
+        report.exit_code
+    }
+
+    // A failed API call or local store operation, e.g. `ctx.fail("fetch data", &e)`.
+    pub fn fail(&self, action: &str, err: &PolyError) -> i32 {
+        self.report(ErrorReport { message: format!("Failed to {action}: {err}"), ..ErrorReport::from(err) })
+    }
+
+    // A failure a watch rides out. Under --json it's an `{"error": ...}` line on stderr, so scripts
+    // can parse it without it landing in the output.
+    pub fn warn(&self, action: &str, err: &PolyError) {
+        let report = ErrorReport { message: format!("Failed to {action}: {err}"), ..ErrorReport::from(err) };
+        if self.json() {
+            eprintln!("{}", json!({ "error": report }));
+        } else {
+            eprintln!("{}", report.message);
+        }
+    }
+
+    // Flags or config values that don't make sense; exits 2.
+    pub fn invalid(&self, message: &str) -> i32 {
+        self.report(ErrorReport::invalid_argument(message))
+    }
+
+    // The whole command outlasted --max-runtime; exits 10.
+    pub fn timed_out(&self, limit: Duration) -> i32 {
+        let message = format!("Gave up after {}s (--max-runtime)", limit.as_secs_f64());
+        self.report(ErrorReport::timeout(message))
+    }
+
+    // A Polygon node at --rpc-url, else the config's `rpc-url`, else a public endpoint.
+    pub fn rpc(&self, flag: Option<&str>) -> JsonRpc {
+        JsonRpc::new(flag.or(self.config.rpc_url.as_deref()).unwrap_or(DEFAULT_RPC_URL))
+    }
+
+    // Venues from --venue, else the config's `venues`, else `default`.
+    pub fn venues(&self, flag: &[Venue], default: &[Venue]) -> Vec<Venue> {
+        [flag, self.config.venues.as_deref().unwrap_or_default(), default]
+            .into_iter()
+            .find(|venues| !venues.is_empty())
+            .unwrap_or_default()
+            .to_vec()
+    }
+}
+
+// The first mention of each column wins, so `--columns title,rank,title` is `title,rank`.
+fn columns(flag: &[Column], config: &Config) -> Option<Vec<Column>> {
+    let chosen = Some(flag).filter(|c| !c.is_empty()).or(config.columns.as_deref())?;
+    let mut columns = Vec::new();
+    for column in chosen {
+        if !columns.contains(column) {
+            columns.push(*column);
+        }
+    }
+    Some(columns).filter(|c| !c.is_empty())
+}
+
+// Exit codes: 0 ok, 1 output failure, 2 invalid arguments or config, 3 network/request failure,
+// 4 non-success HTTP status, 5 JSON decode failure, 6 unexpected API response shape,
+// 7 requested event/market not found, 8 local database failure, 9 failed to write a --record file,
+// 10 --max-runtime ran out, 11 missing or unusable private key or API credentials,
+// 12 `verify` found gamma and the chain disagreeing. The codes 3 to 9 and 11 come from
+// `PolyError::exit_code`.
+
+// Accepts either a 0x condition id or a market slug, resolving the latter via gamma.
+pub async fn resolve_condition_id(market: &str) -> Result<String, PolyError> {
+    if market.starts_with("0x") {
+        return Ok(market.to_string());
+    }
+    fetch_market(market)
+        .await?
+        .condition_id
+        .ok_or_else(|| PolyError::NotFound(format!("condition id for market '{market}'")))
+}
+
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> i32 {
+    match serde_json::to_string_pretty(value) {
+        Ok(s) => {
+            println!("{s}");
+            0
+        }
+        Err(e) => {
+            eprintln!("Failed to serialize JSON: {e}");
+            1
+        }
+    }
+}
+
+pub fn print_yaml<T: Serialize + ?Sized>(value: &T) -> i32 {
+    match serde_yaml::to_string(value) {
+        Ok(s) => {
+            print!("{s}");
+            0
+        }
+        Err(e) => {
+            eprintln!("Failed to serialize YAML: {e}");
+            1
+        }
+    }
+}
+
+// The machine-readable output `emit` prints, for writing somewhere other than stdout. Table and
+// HTML come out empty; see `encode_rows` for HTML.
+pub fn encode<T: Serialize>(ctx: &Context, items: &[T]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    match ctx.format {
+        OutputFormat::Json => {
+            let values = query(ctx, items).map_err(|e| format!("Failed to serialize JSON: {e}"))?;
+            serde_json::to_writer_pretty(&mut out, &values).map_err(|e| format!("Failed to serialize JSON: {e}"))?;
+            out.push(b'\n');
+        }
+        OutputFormat::Yaml => {
+            serde_yaml::to_writer(&mut out, items).map_err(|e| format!("Failed to serialize YAML: {e}"))?;
+        }
+        OutputFormat::Csv => write_csv(items, &mut out).map_err(|e| format!("Failed to write CSV: {e}"))?,
+        OutputFormat::Ndjson => {
+            let values = query(ctx, items).map_err(|e| format!("Failed to serialize NDJSON: {e}"))?;
+            let timestamp = Utc::now().to_rfc3339();
+            write_ndjson(&values, &timestamp, &mut out).map_err(|e| format!("Failed to write NDJSON: {e}"))?;
+        }
+        OutputFormat::Table | OutputFormat::Html => {}
+    }
+    Ok(out)
+}
+
+// Items as JSON values after --where and --select.
+fn query<T: Serialize>(ctx: &Context, items: &[T]) -> serde_json::Result<Vec<Value>> {
+    let values = items.iter().map(serde_json::to_value).collect::<serde_json::Result<Vec<_>>>()?;
+    Ok(ctx.query.apply(values))
+}
+
+// Market rows in the current --format, honouring --columns, HTML included.
+pub fn encode_rows(ctx: &Context, rows: &[Row], title: &str) -> Result<Vec<u8>, String> {
+    match ctx.columns.as_deref() {
+        columns if ctx.format == OutputFormat::Html => {
+            Ok(render_html(rows, columns.unwrap_or(Column::DEFAULT), title).into_bytes())
+        }
+        Some(columns) => encode(ctx, &select(rows, columns)),
+        None => encode(ctx, rows),
+    }
+}
+
+fn print_encoded(encoded: Result<Vec<u8>, String>) -> i32 {
+    let written = encoded.and_then(|bytes| {
+        let mut out = io::stdout().lock();
+        out.write_all(&bytes).and_then(|()| out.flush()).map_err(|e| format!("Failed to write output: {e}"))
+    });
+    match written {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{e}");
+            1
+        }
+    }
+}
+
+// Writes machine-readable output for the current --format; table output is left to the caller.
+// HTML is only meaningful for market rows, see `emit_rows`.
+pub fn emit<T: Serialize>(ctx: &Context, items: &[T]) -> i32 {
+    print_encoded(encode(ctx, items))
+}
+
+// Single structured documents (an event, a market, an orderbook) in JSON or YAML; JSON goes
+// through --where and --select. Returns None when the caller should render its table view instead.
+pub fn emit_document<T: Serialize + ?Sized>(ctx: &Context, value: &T) -> Option<i32> {
+    match ctx.format {
+        OutputFormat::Json if ctx.query.is_empty() => Some(print_json(value)),
+        OutputFormat::Json => Some(match serde_json::to_value(value) {
+            Ok(document) => print_json(&ctx.query.apply_document(document)),
+            Err(e) => {
+                eprintln!("Failed to serialize JSON: {e}");
+                1
+            }
+        }),
+        OutputFormat::Yaml => Some(print_yaml(value)),
+        _ => None,
+    }
+}
+
+// One item of a --follow feed under --json, as a compact line after --where and --select; an
+// item a condition rules out prints nothing.
+pub fn emit_line<T: Serialize>(ctx: &Context, item: &T) -> i32 {
+    match query(ctx, std::slice::from_ref(item)) {
+        Ok(values) => {
+            for value in values {
+                println!("{value}");
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("Failed to serialize JSON: {e}");
+            1
+        }
+    }
+}
+
+pub fn emit_rows(ctx: &Context, rows: &[Row], title: &str) -> i32 {
+    print_encoded(encode_rows(ctx, rows, title))
+}
+
+// Replaces the file at `path` in one step: the content goes to a temporary file beside it that
+// is then renamed over it, so readers such as a web server never see a half-written file.
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "output path has no file name"))?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(name);
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp = path.with_file_name(temp_name);
+    // The replacement keeps the mode of the file it replaces, not the umask default.
+    let permissions = fs::metadata(path).ok().map(|meta| meta.permissions());
+
+    let written = File::create(&temp).and_then(|mut file| {
+        file.write_all(content)?;
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.sync_all()
+    });
+    match written.and_then(|()| fs::rename(&temp, path)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp);
+            Err(e)
+        }
+    }
+}
+
+// A running watch session: how long it has gone and how many refreshes it has shown, for the
+// summary printed when it ends.
+pub struct Session {
+    started: Instant,
+    refreshes: AtomicUsize,
+    interrupt: Arc<Notify>,
+}
+
+impl Session {
+    pub fn start() -> Self {
+        Session { started: Instant::now(), refreshes: AtomicUsize::new(0), interrupt: Arc::new(Notify::new()) }
+    }
+
+    // Takes over Ctrl-C: the first press resolves `interrupted` so the loop can finish its current
+    // frame and stop cleanly; a second press exits at once, for when that loop is stuck on a
+    // request. Only for loops that wait in `interrupted`, or Ctrl-C would do nothing.
+    pub fn catch_interrupts(&self) {
+        self.catch_interrupts_with(|| async {});
+    }
+
+    // `catch_interrupts` for loops that leave something behind that mustn't outlive the process,
+    // such as resting orders: a second press runs `cleanup` before exiting.
+    pub fn catch_interrupts_with<F, Fut>(&self, cleanup: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let interrupt = self.interrupt.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                interrupt.notify_one();
+            }
+            if tokio::signal::ctrl_c().await.is_ok() {
+                cleanup().await;
+                std::process::exit(130);
+            }
+        });
+    }
+
+    // Resolves on Ctrl-C, including one pressed while nobody was waiting.
+    pub async fn interrupted(&self) {
+        self.interrupt.notified().await;
+    }
+
+    pub fn refreshed(&self) {
+        self.refreshes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn summary(&self) -> String {
+        let refreshes = self.refreshes.load(Ordering::Relaxed);
+        let elapsed = chrono::Duration::from_std(self.started.elapsed()).unwrap_or_default();
+        let plural = if refreshes == 1 { "" } else { "es" };
+        format!("Stopped after {refreshes} refresh{plural} in {}", format_span(elapsed))
+    }
+}
+
+// Commands for a watch loop running without the TUI, typed on stdin one per line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Quit,
+    Refresh,
+    Pause,
+}
+
+// Reads `q`, `r` and `p` lines from stdin in the background. None when stdin isn't a terminal,
+// so cron jobs and pipelines never wait on it. Raw mode would catch single key presses, but it
+// also garbles the newlines of whatever the loop is printing.
+pub fn watch_keys() -> Option<mpsc::UnboundedReceiver<Key>> {
+    if !atty::is(atty::Stream::Stdin) {
+        return None;
+    }
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let key = match line.trim().to_ascii_lowercase().as_str() {
+                "q" => Key::Quit,
+                "r" => Key::Refresh,
+                "p" => Key::Pause,
+                _ => continue,
+            };
+            if tx.send(key).is_err() {
+                return;
+            }
+        }
+    });
+    Some(rx)
+}
+
+// Paces the watch loop without the TUI: the refresh interval, Ctrl-C, and keys typed on stdin.
+pub struct Pacer {
+    interval: Duration,
+    pub keys: Option<mpsc::UnboundedReceiver<Key>>,
+    paused: bool,
+}
+
+enum Wake {
+    Timer,
+    Interrupt,
+    Key(Option<Key>),
+}
+
+impl Pacer {
+    pub fn new(interval: Duration) -> Self {
+        Pacer { interval, keys: None, paused: false }
+    }
+
+    // Waits until the next refresh is due; false when the session should end. While paused the
+    // timer stands still, and resuming restarts it.
+    pub async fn wait(&mut self, session: &Session) -> bool {
+        let sleep = tokio::time::sleep(self.interval);
+        tokio::pin!(sleep);
+        loop {
+            let wake = tokio::select! {
+                _ = &mut sleep, if !self.paused => Wake::Timer,
+                _ = session.interrupted() => Wake::Interrupt,
+                key = next_key(&mut self.keys) => Wake::Key(key),
+            };
+            match wake {
+                Wake::Timer | Wake::Key(Some(Key::Refresh)) => return true,
+                Wake::Interrupt | Wake::Key(Some(Key::Quit)) => return false,
+                Wake::Key(Some(Key::Pause)) if self.paused => {
+                    self.paused = false;
+                    sleep.as_mut().reset(tokio::time::Instant::now() + self.interval);
+                    eprintln!("Resumed; refreshing every {}s", self.interval.as_secs());
+                }
+                Wake::Key(Some(Key::Pause)) => {
+                    self.paused = true;
+                    eprintln!("Paused; p resumes, r refreshes once, q quits");
+                }
+                // Stdin was closed, so only the timer and Ctrl-C are left.
+                Wake::Key(None) => self.keys = None,
+            }
+        }
+    }
+}
+
+async fn next_key(keys: &mut Option<mpsc::UnboundedReceiver<Key>>) -> Option<Key> {
+    match keys {
+        Some(keys) => keys.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+// The bell goes to stderr so it never lands inside machine-readable stdout.
+pub fn ring_bell() {
+    eprint!("\x07");
+    let _ = io::stderr().flush();
+}
+
+// Repaints a full-screen text frame in place. The first frame clears the terminal; later frames
+// only rewrite the lines that changed, so refreshes never flash a blank screen. The default
+// screen, and a live one whose stdout isn't a terminal, just prints each frame, so one-off output
+// and redirected output never carry cursor escapes.
+#[derive(Default)]
+pub struct Screen {
+    lines: Option<Vec<String>>,
+    in_place: bool,
+}
+
+impl Screen {
+    // For watch loops.
+    pub fn live() -> Self {
+        Screen { lines: None, in_place: atty::is(atty::Stream::Stdout) }
+    }
+
+    pub fn paint(&mut self, frame: &str) -> io::Result<()> {
+        if !self.in_place {
+            let mut out = io::stdout().lock();
+            writeln!(out, "{frame}")?;
+            return out.flush();
+        }
+        let lines: Vec<String> = frame.lines().map(str::to_string).collect();
+        let mut out = io::stdout().lock();
+
+        let previous = match self.lines.take() {
+            Some(previous) => previous,
+            None => {
+                queue!(out, Clear(ClearType::All))?;
+                Vec::new()
+            }
+        };
+        for (idx, line) in lines.iter().enumerate() {
+            if previous.get(idx) != Some(line) {
+                queue!(out, MoveTo(0, idx as u16), Print(line), Clear(ClearType::UntilNewLine))?;
+            }
+        }
+        queue!(out, MoveTo(0, lines.len() as u16))?;
+        if previous.len() > lines.len() {
+            queue!(out, Clear(ClearType::FromCursorDown))?;
+        }
+        out.flush()?;
+
+        self.lines = Some(lines);
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use poly_core::{fetch_markets, paint, sort_rows, Row, SortKey, SortSpec, C};
+
+use crate::cli::MoversArgs;
+use crate::commands::{emit_rows, Context, DEFAULT_FETCH_LIMIT};
+
+// Markets shown per section when --top isn't given.
+const DEFAULT_PER_SECTION: usize = 10;
+
+pub async fn run(args: &MoversArgs, ctx: &Context) -> i32 {
+    let top = args.top.unwrap_or(DEFAULT_PER_SECTION);
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    if top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+
+    let rows = match fetch_markets(&args.filter.event_query(fetch_limit)).await {
+        Ok(r) => args.filter.row_filter().apply(r),
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+    let (mut gainers, mut losers): (Vec<Row>, Vec<Row>) = rows
+        .into_iter()
+        .filter(|row| row.change_24h_pct.is_some_and(|change| change != 0.0))
+        .partition(|row| row.change_24h_pct.is_some_and(|change| change > 0.0));
+    sort_rows(&mut gainers, &[SortSpec { key: SortKey::Change, descending: true }]);
+    sort_rows(&mut losers, &[SortSpec { key: SortKey::Change, descending: false }]);
+    gainers.truncate(top);
+    losers.truncate(top);
+
+    let title = "Polymarket movers by 24h change";
+    if !ctx.table() {
+        let rows: Vec<Row> = gainers.into_iter().chain(losers).collect();
+        return emit_rows(ctx, &rows, title);
+    }
+
+    println!("{}", paint(title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    for (heading, rows, color) in [("Gainers", &gainers, C::GREEN), ("Losers", &losers, C::RED)] {
+        println!();
+        println!("{}", paint(heading, &(String::from(color) + C::BOLD), ctx.color));
+        if rows.is_empty() {
+            println!("{}", paint("No markets moved this way in the last 24h.", C::DIM, ctx.color));
+        } else {
+            println!("{}", ctx.render_rows(rows, top));
+        }
+    }
+    0
+}
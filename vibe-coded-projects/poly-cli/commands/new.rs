@@ -0,0 +1,46 @@
+use chrono::Utc;
+use poly_core::{fetch_new_markets, paint, render_new_markets, EventQuery, OutputFormat, C};
+
+use crate::cli::NewArgs;
+use crate::commands::{emit, Context, DEFAULT_FETCH_LIMIT, DEFAULT_TOP};
+
+pub async fn run(args: &NewArgs, ctx: &Context) -> i32 {
+    let top = args.top.or(ctx.config.top).unwrap_or(DEFAULT_TOP);
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    if top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+    if let Some(code) = ctx.reject_formats("new", &[OutputFormat::Html]) {
+        return code;
+    }
+
+    let Some(since) = Utc::now().checked_sub_signed(args.since) else {
+        return ctx.invalid("--since reaches back past any representable date");
+    };
+    let query = EventQuery { tags: args.tags.clone(), ..EventQuery::new(fetch_limit) };
+    let markets = match fetch_new_markets(&query, since, top).await {
+        Ok(m) => m,
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+
+    if !ctx.table() {
+        return emit(ctx, &markets);
+    }
+
+    let title = paint("Polymarket new markets", &(String::from(C::BOLD) + C::CYAN), ctx.color);
+    let summary = paint(
+        &format!("created since {}", since.format("%Y-%m-%d %H:%M UTC")),
+        C::DIM,
+        ctx.color,
+    );
+    println!("{title}  |  {summary}");
+    if markets.is_empty() {
+        println!("{}", paint("No markets were created in that window.", C::YELLOW, ctx.color));
+    } else {
+        println!("{}", render_new_markets(&markets, ctx.color));
+    }
+    0
+}
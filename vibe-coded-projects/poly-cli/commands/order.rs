@@ -0,0 +1,60 @@
+use poly_core::{fetch_order_market, post_order, sign_order, OrderRequest, OutputFormat};
+
+use crate::cli::OrderArgs;
+use crate::commands::{emit_document, Context};
+use crate::credentials;
+
+pub async fn run(args: &OrderArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("order", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    let wallet = match credentials::wallet() {
+        Ok(w) => w,
+        Err(e) => return ctx.fail("load private key", &e),
+    };
+    let request = OrderRequest {
+        token_id: args.token_id.trim().to_string(),
+        side: args.side,
+        price: args.price,
+        size: args.size,
+        funder: args.funder.clone().or_else(|| ctx.config.funder.clone()),
+        signature_type: args.signature_type.or(ctx.config.signature_type).unwrap_or_default(),
+    };
+    let market = match fetch_order_market(&request.token_id).await {
+        Ok(m) => m,
+        Err(e) => return ctx.fail("fetch market rules", &e),
+    };
+    if let Err(message) = market.check(&request) {
+        return ctx.invalid(&message);
+    }
+    let order = match sign_order(&wallet, &request, &market) {
+        Ok(o) => o,
+        Err(e) => return ctx.fail("sign order", &e),
+    };
+    let summary = format!("{} {} @ {} of {}", request.side, request.size, request.price, request.token_id);
+
+    if args.dry_run {
+        if let Some(code) = emit_document(ctx, &order) {
+            return code;
+        }
+        println!("Signed {summary} as {} (not submitted)", order.signer);
+        println!("Signature: {}", order.signature);
+        return 0;
+    }
+
+    let auth = match credentials::clob_auth(&wallet).await {
+        Ok(a) => a,
+        Err(e) => return ctx.fail("get CLOB API credentials", &e),
+    };
+    let placed = match post_order(&auth, &order).await {
+        Ok(p) => p,
+        Err(e) => return ctx.fail("place order", &e),
+    };
+
+    if let Some(code) = emit_document(ctx, &placed) {
+        return code;
+    }
+    println!("Placed {summary}: order {} ({})", placed.order_id, placed.status);
+    0
+}
@@ -0,0 +1,32 @@
+use poly_core::{fetch_orderbook, paint, render_orderbook, OutputFormat, C};
+
+use crate::cli::OrderbookArgs;
+use crate::commands::{emit_document, Context};
+
+pub async fn run(args: &OrderbookArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("orderbook", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    if args.depth < 1 {
+        return ctx.invalid("--depth must be >= 1");
+    }
+
+    let book = match fetch_orderbook(&args.token_id).await {
+        Ok(b) => b,
+        Err(e) => return ctx.fail("fetch orderbook", &e),
+    };
+
+    if let Some(code) = emit_document(ctx, &book) {
+        return code;
+    }
+
+    let title = paint(
+        &format!("Orderbook {}", args.token_id),
+        &(String::from(C::BOLD) + C::CYAN),
+        ctx.color,
+    );
+    println!("{title}");
+    println!("{}", render_orderbook(&book, args.depth, ctx.color));
+    0
+}
@@ -0,0 +1,85 @@
+use poly_core::{
+    attach_order_titles, cancel_all_orders, cancel_order, fetch_open_orders, paint, render_open_orders, ClobAuth,
+    OutputFormat, PolyError, C,
+};
+
+use crate::cli::{OrdersArgs, OrdersCommand};
+use crate::commands::{emit, emit_document, Context};
+use crate::credentials;
+
+pub async fn run(args: &OrdersArgs, ctx: &Context) -> i32 {
+    // Refuse an unsupported --format before asking for the wallet key.
+    let rejected = match &args.command {
+        OrdersCommand::List { .. } => ctx.reject_formats("orders list", &[OutputFormat::Html]),
+        OrdersCommand::Cancel { .. } => ctx.reject_formats("orders cancel", OutputFormat::ROW_ONLY),
+    };
+    if let Some(code) = rejected {
+        return code;
+    }
+
+    let auth = match auth().await {
+        Ok(a) => a,
+        Err(e) => return ctx.fail("get CLOB API credentials", &e),
+    };
+    match &args.command {
+        OrdersCommand::List { market } => list(&auth, market.as_deref(), ctx).await,
+        // clap makes `id` and `--all` exclusive, so no id means --all.
+        OrdersCommand::Cancel { id, .. } => cancel(&auth, id.as_deref(), ctx).await,
+    }
+}
+
+async fn auth() -> Result<ClobAuth, PolyError> {
+    let wallet = credentials::wallet()?;
+    credentials::clob_auth(&wallet).await
+}
+
+async fn list(auth: &ClobAuth, market: Option<&str>, ctx: &Context) -> i32 {
+    let mut orders = match fetch_open_orders(auth, market).await {
+        Ok(o) => o,
+        Err(e) => return ctx.fail("fetch open orders", &e),
+    };
+    attach_order_titles(&mut orders).await;
+    orders.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    if !ctx.table() {
+        return emit(ctx, &orders);
+    }
+
+    let title = format!("Open orders {}", auth.address);
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if orders.is_empty() {
+        println!("{}", paint("No open orders", C::DIM, ctx.color));
+        return 0;
+    }
+    println!("{}", render_open_orders(&orders, ctx.color));
+    0
+}
+
+// One order by id, or every open order when `id` is None.
+async fn cancel(auth: &ClobAuth, id: Option<&str>, ctx: &Context) -> i32 {
+    let cancelled = match id {
+        Some(id) => cancel_order(auth, id.trim()).await,
+        None => cancel_all_orders(auth).await,
+    };
+    let cancelled = match cancelled {
+        Ok(c) => c,
+        Err(e) => return ctx.fail("cancel orders", &e),
+    };
+    // A named order that wasn't cancelled is a failure; with --all, the ones left are listed.
+    if let Some(reason) = id.and_then(|id| cancelled.not_canceled.get(id.trim())) {
+        return ctx.fail("cancel order", &PolyError::Rejected(reason.clone()));
+    }
+
+    if let Some(code) = emit_document(ctx, &cancelled) {
+        return code;
+    }
+    let count = cancelled.canceled.len();
+    println!("Cancelled {count} order{}", if count == 1 { "" } else { "s" });
+    for order_id in &cancelled.canceled {
+        println!("  {}", paint(order_id, C::DIM, ctx.color));
+    }
+    for (order_id, reason) in &cancelled.not_canceled {
+        println!("{}", paint(&format!("Not cancelled {order_id}: {reason}"), C::YELLOW, ctx.color));
+    }
+    0
+}
@@ -0,0 +1,74 @@
+use poly_core::{
+    compute_pnl, fetch_positions, fetch_user_trades, paint, render_pnl, render_positions, OutputFormat, Position,
+    C,
+};
+
+use crate::cli::PortfolioArgs;
+use crate::commands::{emit, Context};
+
+// Cap on how much trade history `--pnl` replays; very active wallets get a partial cost basis.
+const MAX_TRADES: usize = 5000;
+
+pub fn is_address(text: &str) -> bool {
+    text.len() == 42 && text.starts_with("0x") && text[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub async fn run(args: &PortfolioArgs, ctx: &Context) -> i32 {
+    if !is_address(&args.address) {
+        let address = &args.address;
+        return ctx.invalid(&format!("'{address}' is not a wallet address (expected 0x followed by 40 hex digits)"));
+    }
+    if let Some(code) = ctx.reject_formats("portfolio", &[OutputFormat::Html]) {
+        return code;
+    }
+
+    let positions = match fetch_positions(&args.address).await {
+        Ok(p) => p,
+        Err(e) => return ctx.fail("fetch positions", &e),
+    };
+
+    if args.pnl {
+        return run_pnl(args, ctx, &positions).await;
+    }
+    if !ctx.table() {
+        return emit(ctx, &positions);
+    }
+
+    let title = format!("Portfolio {}", args.address);
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if positions.is_empty() {
+        println!("{}", paint("No open positions", C::DIM, ctx.color));
+        return 0;
+    }
+    println!("{}", render_positions(&positions, ctx.color));
+    0
+}
+
+async fn run_pnl(args: &PortfolioArgs, ctx: &Context, positions: &[Position]) -> i32 {
+    let trades = match fetch_user_trades(&args.address, MAX_TRADES).await {
+        Ok(t) => t,
+        Err(e) => return ctx.fail("fetch trade history", &e),
+    };
+    let pnl = compute_pnl(&trades, positions, args.since.map(|t| t.timestamp()));
+
+    if !ctx.table() {
+        return emit(ctx, &pnl);
+    }
+
+    let window = match args.since {
+        Some(since) => format!("realized since {}", since.format("%Y-%m-%d %H:%M UTC")),
+        None => "all time".to_string(),
+    };
+    let title = format!("PnL {} ({window})", args.address);
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if trades.len() >= MAX_TRADES {
+        let note = format!("Only the latest {MAX_TRADES} trades were replayed; older cost basis is missing");
+        println!("{}", paint(&note, C::YELLOW, ctx.color));
+    }
+    if pnl.is_empty() {
+        println!("{}", paint("No trades or positions", C::DIM, ctx.color));
+        return 0;
+    }
+    println!("{}", render_pnl(&pnl, ctx.color));
+    0
+}
@@ -0,0 +1,227 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use chrono::Utc;
+use poly_core::{
+    cancel_order, display_options, fetch_order_market, fetch_orderbook, format_price, paint, post_order, sign_order,
+    ClobAuth, OrderMarket, OrderRequest, OutputFormat, PolyError, Quote, Side, SignatureType, Wallet, C,
+};
+use serde::Serialize;
+
+use crate::cli::QuoteArgs;
+use crate::commands::{emit, Context, Session};
+use crate::credentials;
+
+// One refresh: the book's top and the quote derived from it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QuoteLine {
+    best_bid: Option<f64>,
+    best_ask: Option<f64>,
+    #[serde(flatten)]
+    quote: Quote,
+    size: f64,
+    // Whether this refresh placed new orders (only with --execute).
+    placed: bool,
+}
+
+// What --execute needs to trade, and the two orders currently resting. The ids are shared with
+// the Ctrl-C handler, which cancels them if a second press cuts the loop short.
+struct Trader {
+    wallet: Wallet,
+    auth: ClobAuth,
+    funder: Option<String>,
+    signature_type: SignatureType,
+    resting: Arc<Mutex<Vec<String>>>,
+}
+
+async fn cancel_resting(auth: &ClobAuth, resting: &Mutex<Vec<String>>) -> Result<(), PolyError> {
+    loop {
+        let Some(order_id) = resting.lock().unwrap_or_else(PoisonError::into_inner).pop() else {
+            return Ok(());
+        };
+        if let Err(e) = cancel_order(auth, &order_id).await {
+            resting.lock().unwrap_or_else(PoisonError::into_inner).push(order_id);
+            return Err(e);
+        }
+    }
+}
+
+impl Trader {
+    // Cancels the resting pair, then places `quote`. Both sides or neither are left resting.
+    async fn requote(
+        &mut self,
+        token_id: &str,
+        quote: Quote,
+        size: f64,
+        market: &OrderMarket,
+    ) -> Result<(), PolyError> {
+        self.cancel().await?;
+        for (side, price) in [(Side::Buy, quote.bid), (Side::Sell, quote.ask)] {
+            let request = OrderRequest {
+                token_id: token_id.to_string(),
+                side,
+                price,
+                size,
+                funder: self.funder.clone(),
+                signature_type: self.signature_type,
+            };
+            let placed = match sign_order(&self.wallet, &request, market) {
+                Ok(order) => post_order(&self.auth, &order).await,
+                Err(e) => Err(e),
+            };
+            match placed {
+                Ok(placed) => self.resting.lock().unwrap_or_else(PoisonError::into_inner).push(placed.order_id),
+                Err(e) => {
+                    self.cancel().await?;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn cancel(&mut self) -> Result<(), PolyError> {
+        cancel_resting(&self.auth, &self.resting).await
+    }
+}
+
+// Requotes every --interval until Ctrl-C. With --execute the orders are replaced only when the
+// quote moves, and cancelled on the way out.
+pub async fn run(args: &QuoteArgs, ctx: &Context) -> i32 {
+    let unsupported = [OutputFormat::Json, OutputFormat::Yaml, OutputFormat::Csv, OutputFormat::Html];
+    if let Some(code) = ctx.reject_formats("quote", &unsupported) {
+        return code;
+    }
+    if args.interval < 1 {
+        return ctx.invalid("--interval must be >= 1");
+    }
+
+    let token_id = args.token_id.trim();
+    let market = match fetch_order_market(token_id).await {
+        Ok(m) => m,
+        Err(e) => return ctx.fail("fetch market rules", &e),
+    };
+    if args.spread < market.tick_size {
+        return ctx.invalid(&format!("--spread must be at least the tick size, {}", market.tick_size));
+    }
+    let sizing = OrderRequest {
+        token_id: token_id.to_string(),
+        side: Side::Buy,
+        price: market.tick_size,
+        size: args.size,
+        funder: args.funder.clone().or_else(|| ctx.config.funder.clone()),
+        signature_type: args.signature_type.or(ctx.config.signature_type).unwrap_or_default(),
+    };
+    if let Err(message) = market.check(&sizing) {
+        return ctx.invalid(&message);
+    }
+
+    let mut trader = None;
+    if args.execute {
+        let wallet = match credentials::wallet() {
+            Ok(w) => w,
+            Err(e) => return ctx.fail("load private key", &e),
+        };
+        let auth = match credentials::clob_auth(&wallet).await {
+            Ok(a) => a,
+            Err(e) => return ctx.fail("get CLOB API credentials", &e),
+        };
+        trader = Some(Trader {
+            wallet,
+            auth,
+            funder: sizing.funder.clone(),
+            signature_type: sizing.signature_type,
+            resting: Arc::new(Mutex::new(Vec::new())),
+        });
+    }
+
+    let session = Session::start();
+    match &trader {
+        Some(trader) => {
+            let (auth, resting) = (trader.auth.clone(), trader.resting.clone());
+            session.catch_interrupts_with(move || async move {
+                if let Err(e) = cancel_resting(&auth, &resting).await {
+                    eprintln!("Failed to cancel the resting quotes: {e}");
+                    eprintln!("Check `poly orders list` for orders still open");
+                }
+            });
+        }
+        None => session.catch_interrupts(),
+    }
+    let mut code = 0;
+    let mut current: Option<Quote> = None;
+    loop {
+        match fetch_orderbook(token_id).await {
+            Ok(book) => match book.midpoint().and_then(|mid| Quote::around(mid, args.spread, market.tick_size)) {
+                Some(quote) => {
+                    let moved = current.is_none_or(|q| q.bid != quote.bid || q.ask != quote.ask);
+                    let mut placed = false;
+                    if let (Some(trader), true) = (trader.as_mut(), moved) {
+                        match trader.requote(token_id, quote, args.size, &market).await {
+                            Ok(()) => {
+                                current = Some(quote);
+                                placed = true;
+                            }
+                            Err(e) => {
+                                current = None;
+                                eprintln!("Failed to place quotes: {e}");
+                            }
+                        }
+                    }
+                    let line = QuoteLine {
+                        best_bid: book.best_bid(),
+                        best_ask: book.best_ask(),
+                        quote,
+                        size: args.size,
+                        placed,
+                    };
+                    if ctx.table() {
+                        print_line(&line, trader.is_some(), ctx.color);
+                    } else {
+                        code = emit(ctx, &[line]);
+                        if code != 0 {
+                            break;
+                        }
+                    }
+                }
+                None => eprintln!("No quote: the book is one-sided or too close to 0 or 1"),
+            },
+            Err(e) => eprintln!("Failed to fetch orderbook: {e}"),
+        }
+        session.refreshed();
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(args.interval)) => {}
+            _ = session.interrupted() => break,
+        }
+    }
+
+    if let Some(trader) = trader.as_mut() {
+        if let Err(e) = trader.cancel().await {
+            eprintln!("Failed to cancel the resting quotes: {e}");
+            eprintln!("Check `poly orders list` for orders still open");
+        }
+    }
+    eprintln!("{}", session.summary());
+    code
+}
+
+fn print_line(line: &QuoteLine, executing: bool, color: bool) {
+    let time = display_options().zone.clock(Utc::now());
+    let action = match (executing, line.placed) {
+        (false, _) => String::new(),
+        (true, true) => paint("  placed", C::GREEN, color),
+        (true, false) => paint("  kept", C::DIM, color),
+    };
+    println!(
+        "{}  book {} / {}  mid {}  quote {} / {} x {:.2}{action}",
+        paint(&time, C::DIM, color),
+        format_price(line.best_bid),
+        format_price(line.best_ask),
+        paint(&format_price(Some(line.quote.mid)), C::YELLOW, color),
+        paint(&format_price(Some(line.quote.bid)), C::GREEN, color),
+        paint(&format_price(Some(line.quote.ask)), C::RED, color),
+        line.size,
+    );
+}
@@ -0,0 +1,46 @@
+use chrono::Utc;
+use poly_core::{fetch_resolutions, paint, render_resolutions, EventQuery, OutputFormat, C};
+
+use crate::cli::ResolvedArgs;
+use crate::commands::{emit, Context, DEFAULT_FETCH_LIMIT, DEFAULT_TOP};
+
+pub async fn run(args: &ResolvedArgs, ctx: &Context) -> i32 {
+    let top = args.top.or(ctx.config.top).unwrap_or(DEFAULT_TOP);
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    if top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+    if let Some(code) = ctx.reject_formats("resolved", &[OutputFormat::Html]) {
+        return code;
+    }
+
+    let Some(since) = Utc::now().checked_sub_signed(args.since) else {
+        return ctx.invalid("--since reaches back past any representable date");
+    };
+    let query = EventQuery { tags: args.tags.clone(), ..EventQuery::new(fetch_limit) };
+    let resolved = match fetch_resolutions(&query, since, top).await {
+        Ok(r) => r,
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+
+    if !ctx.table() {
+        return emit(ctx, &resolved);
+    }
+
+    let title = paint("Polymarket resolved markets", &(String::from(C::BOLD) + C::CYAN), ctx.color);
+    let summary = paint(
+        &format!("closed since {}", since.format("%Y-%m-%d %H:%M UTC")),
+        C::DIM,
+        ctx.color,
+    );
+    println!("{title}  |  {summary}");
+    if resolved.is_empty() {
+        println!("{}", paint("No markets resolved in that window.", C::YELLOW, ctx.color));
+    } else {
+        println!("{}", render_resolutions(&resolved, ctx.color));
+    }
+    0
+}
@@ -0,0 +1,51 @@
+use poly_core::{fetch_markets, paint, search_rows, Matcher, C};
+
+use crate::cli::SearchArgs;
+use crate::commands::{emit_rows, Context, DEFAULT_FETCH_LIMIT, DEFAULT_TOP};
+
+pub async fn run(args: &SearchArgs, ctx: &Context) -> i32 {
+    let top = args.top.or(ctx.config.top).unwrap_or(DEFAULT_TOP);
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    if top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+
+    let matcher = match Matcher::new(&args.pattern, args.regex) {
+        Ok(m) => m,
+        Err(e) => return ctx.invalid(&format!("Invalid --regex pattern: {e}")),
+    };
+
+    let mut rows = match fetch_markets(&args.filter.event_query(fetch_limit)).await {
+        Ok(r) => search_rows(args.filter.row_filter().apply(r), &matcher),
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+
+    args.sort.apply(&mut rows);
+
+    if !ctx.table() {
+        let title = format!("Polymarket search: \"{}\"", args.pattern);
+        return emit_rows(ctx, &rows[..rows.len().min(top)], &title);
+    }
+
+    let title = paint(
+        &format!("Polymarket search: \"{}\"", args.pattern),
+        &(String::from(C::BOLD) + C::CYAN),
+        ctx.color,
+    );
+    let summary = paint(
+        &format!("{} matching markets (showing up to {top})", rows.len()),
+        C::DIM,
+        ctx.color,
+    );
+    println!("{title}  |  {summary}");
+    if rows.is_empty() {
+        println!("{}", paint("No markets matched.", C::YELLOW, ctx.color));
+    } else {
+        println!("{}", ctx.render_rows(&rows, top));
+    }
+
+    0
+}
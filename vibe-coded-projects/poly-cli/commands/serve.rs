@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use poly_core::{fetch_market, fetch_markets, search_rows, sort_rows, EventQuery, Market, Matcher, PolyError, Row, SortKey, SortSpec};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::Mutex;
+
+use crate::cli::ServeArgs;
+use crate::commands::{Context, DEFAULT_FETCH_LIMIT, DEFAULT_TOP};
+
+// Market lookups kept at once; clients asking for many slugs push out the oldest.
+const MAX_CACHED_MARKETS: usize = 1_000;
+
+// Rows and market lookups are shared by every request and refreshed at most once per TTL,
+// so LAN clients never multiply traffic to Polymarket.
+pub struct Cache {
+    query: EventQuery,
+    ttl: Duration,
+    rows: Mutex<Option<(Instant, Arc<Vec<Row>>)>>,
+    markets: Mutex<HashMap<String, (Instant, Arc<Market>)>>,
+}
+
+impl Cache {
+    pub fn new(query: EventQuery, ttl: Duration) -> Self {
+        Cache { query, ttl, rows: Mutex::new(None), markets: Mutex::new(HashMap::new()) }
+    }
+
+    // Hands the cache rows fetched elsewhere, as the daemon does with each snapshot.
+    pub async fn store(&self, rows: Vec<Row>) {
+        *self.rows.lock().await = Some((Instant::now(), Arc::new(rows)));
+    }
+
+    async fn rows(&self) -> Result<Arc<Vec<Row>>, PolyError> {
+        let mut slot = self.rows.lock().await;
+        if let Some((fetched, rows)) = slot.as_ref() {
+            if fetched.elapsed() < self.ttl {
+                return Ok(rows.clone());
+            }
+        }
+        let rows = Arc::new(fetch_markets(&self.query).await?);
+        *slot = Some((Instant::now(), rows.clone()));
+        Ok(rows)
+    }
+
+    // Fetched without holding the lock, so one slow lookup doesn't stall every other slug.
+    async fn market(&self, slug: &str) -> Result<Arc<Market>, PolyError> {
+        if let Some((fetched, market)) = self.markets.lock().await.get(slug) {
+            if fetched.elapsed() < self.ttl {
+                return Ok(market.clone());
+            }
+        }
+        let market = Arc::new(fetch_market(slug).await?);
+        let mut markets = self.markets.lock().await;
+        markets.retain(|_, (fetched, _)| fetched.elapsed() < self.ttl);
+        if markets.len() >= MAX_CACHED_MARKETS {
+            let oldest = markets.iter().min_by_key(|(_, (fetched, _))| *fetched).map(|(slug, _)| slug.clone());
+            markets.remove(&oldest.unwrap_or_default());
+        }
+        markets.insert(slug.to_string(), (Instant::now(), market.clone()));
+        Ok(market)
+    }
+}
+
+type AppState = Arc<Cache>;
+
+struct ApiError(StatusCode, String);
+
+impl From<PolyError> for ApiError {
+    fn from(err: PolyError) -> Self {
+        let status = match err {
+            PolyError::NotFound(_) => StatusCode::NOT_FOUND,
+            _ => StatusCode::BAD_GATEWAY,
+        };
+        ApiError(status, err.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+struct TopParams {
+    n: Option<usize>,
+    sort: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+    #[serde(default)]
+    regex: bool,
+    n: Option<usize>,
+}
+
+fn parse_sort(raw: Option<&str>) -> Result<Vec<SortSpec>, ApiError> {
+    let raw = raw.unwrap_or("volume");
+    raw.split(',')
+        .map(|key| key.parse::<SortKey>().map(SortSpec::new))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ApiError(StatusCode::BAD_REQUEST, e))
+}
+
+async fn top(State(cache): State<AppState>, Query(params): Query<TopParams>) -> Result<Json<Vec<Row>>, ApiError> {
+    let specs = parse_sort(params.sort.as_deref())?;
+    let mut rows = cache.rows().await?.as_ref().clone();
+    sort_rows(&mut rows, &specs);
+    rows.truncate(params.n.unwrap_or(DEFAULT_TOP));
+    Ok(Json(rows))
+}
+
+async fn search(
+    State(cache): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<Row>>, ApiError> {
+    let matcher = Matcher::new(&params.q, params.regex)
+        .map_err(|e| ApiError(StatusCode::BAD_REQUEST, format!("invalid regex: {e}")))?;
+    let mut rows = search_rows(cache.rows().await?.as_ref().clone(), &matcher);
+    sort_rows(&mut rows, &[SortSpec::new(SortKey::Volume)]);
+    rows.truncate(params.n.unwrap_or(DEFAULT_TOP));
+    Ok(Json(rows))
+}
+
+async fn market(State(cache): State<AppState>, Path(slug): Path<String>) -> Result<Json<Market>, ApiError> {
+    let market = cache.market(&slug).await?;
+    Ok(Json(market.as_ref().clone()))
+}
+
+// The /top, /search and /market endpoints, also served by the daemon.
+pub fn router(cache: Arc<Cache>) -> Router {
+    Router::new()
+        .route("/top", get(top))
+        .route("/search", get(search))
+        .route("/market/{slug}", get(market))
+        .with_state(cache)
+}
+
+pub async fn run(args: &ServeArgs, ctx: &Context) -> i32 {
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    let cache = Arc::new(Cache::new(EventQuery::new(fetch_limit), Duration::from_secs(args.cache_ttl)));
+    let app = router(cache);
+
+    let addr = SocketAddr::new(args.host, args.port);
+    let listener = match tokio::net::TcpListener::bind(addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("Failed to bind {addr}: {e}");
+            return 1;
+        }
+    };
+
+    eprintln!("Serving on http://{addr} (endpoints: /top, /search?q=, /market/<slug>)");
+    if let Err(e) = axum::serve(listener, app).await {
+        eprintln!("Server error: {e}");
+        return 1;
+    }
+    0
+}
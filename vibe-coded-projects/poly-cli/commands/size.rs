@@ -0,0 +1,107 @@
+use poly_core::{fetch_market, format_price, paint, Kelly, OutputFormat, PolyError, Stake, C};
+use serde::Serialize;
+
+use crate::cli::SizeArgs;
+use crate::commands::{emit_document, Context};
+
+const MULTIPLIERS: [(&str, f64); 3] = [("Full", 1.0), ("Half", 0.5), ("Quarter", 0.25)];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Sizing {
+    market: Option<String>,
+    // The side with the edge, by name.
+    side: String,
+    bankroll: f64,
+    #[serde(flatten)]
+    kelly: Kelly,
+    stakes: Vec<Stake>,
+}
+
+// The price of one outcome of a market, with a title for it and the name of the other side.
+async fn market_price(slug: &str, outcome: Option<&str>) -> Result<(f64, String, String, String), PolyError> {
+    let market = fetch_market(slug).await?;
+    let index = match outcome {
+        Some(wanted) => market
+            .outcomes
+            .iter()
+            .position(|o| o.eq_ignore_ascii_case(wanted))
+            .ok_or_else(|| PolyError::NotFound(format!("outcome '{wanted}' in market '{slug}'")))?,
+        None => 0,
+    };
+    let price = market
+        .outcome_prices
+        .get(index)
+        .copied()
+        .ok_or_else(|| PolyError::NotFound(format!("a price for market '{slug}'")))?;
+    let name = market.outcomes.get(index).cloned().unwrap_or_else(|| "Yes".to_string());
+    // In a two-outcome market the complement has a name; otherwise it's just "not" this one.
+    let other = match market.outcomes.as_slice() {
+        [_, second] if index == 0 => second.clone(),
+        [first, _] => first.clone(),
+        _ => format!("Not {name}"),
+    };
+    Ok((price, market.display_title().to_string(), name, other))
+}
+
+pub async fn run(args: &SizeArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("size", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+    if !(args.bankroll > 0.0 && args.bankroll.is_finite()) {
+        return ctx.invalid("--bankroll must be positive");
+    }
+
+    let (price, title, name, other) = match (&args.market, args.price) {
+        (Some(slug), _) => match market_price(slug, args.outcome.as_deref()).await {
+            Ok(found) => found,
+            Err(e) => return ctx.fail("fetch market price", &e),
+        },
+        (None, Some(price)) => (price, String::new(), "Yes".to_string(), "No".to_string()),
+        (None, None) => return ctx.invalid("pass --price or --market"),
+    };
+    // A resolved market trades at 0 or 1.
+    if price <= 0.0 || price >= 1.0 {
+        return ctx.invalid(&format!("{title} is priced at {price}, which leaves nothing to size"));
+    }
+
+    let kelly = Kelly::new(args.prob, price);
+    let sizing = Sizing {
+        market: args.market.as_ref().map(|_| title.clone()),
+        side: if kelly.buy_outcome { name.clone() } else { other },
+        bankroll: args.bankroll,
+        kelly,
+        stakes: MULTIPLIERS.iter().map(|&(_, m)| kelly.stake(args.bankroll, m)).collect(),
+    };
+    if let Some(code) = emit_document(ctx, &sizing) {
+        return code;
+    }
+
+    let heading = if title.is_empty() {
+        format!("Kelly sizing: {name} at {}", format_price(Some(price)))
+    } else {
+        format!("Kelly sizing: {title} — {name} at {}", format_price(Some(price)))
+    };
+    println!("{}", paint(&heading, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    println!("Your estimate: {:.1}%   Bankroll: ${:.2}", args.prob * 100.0, args.bankroll);
+    if kelly.fraction <= 0.0 {
+        println!("{}", paint("No edge at this price: Kelly says don't bet", C::YELLOW, ctx.color));
+        return 0;
+    }
+    let cost = format_price(Some(kelly.cost()));
+    let edge = format!("Edge: buy {} at {cost}, {:+.1}% expected return", sizing.side, kelly.edge * 100.0);
+    println!("{}", paint(&edge, C::GREEN, ctx.color));
+    println!();
+    println!("{:<8} {:>10} {:>12} {:>12} {:>16}", "Kelly", "Bankroll", "Stake", "Shares", "Expected profit");
+    for ((label, _), stake) in MULTIPLIERS.iter().zip(&sizing.stakes) {
+        println!(
+            "{:<8} {:>9.1}% {:>12} {:>12.2} {:>16}",
+            label,
+            stake.fraction * 100.0,
+            format!("${:.2}", stake.stake),
+            stake.shares,
+            format!("${:.2}", stake.expected_profit),
+        );
+    }
+    0
+}
@@ -0,0 +1,52 @@
+use poly_core::{fetch_leagues, fetch_matchups, paint, render_matchups, OutputFormat, C};
+
+use crate::cli::SportsArgs;
+use crate::commands::{emit, Context};
+
+pub async fn run(args: &SportsArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("sports", &[OutputFormat::Html]) {
+        return code;
+    }
+    if args.limit < 1 {
+        return ctx.invalid("--limit must be >= 1");
+    }
+
+    let mut leagues = match fetch_leagues().await {
+        Ok(l) => l,
+        Err(e) => return ctx.fail("fetch leagues", &e),
+    };
+    leagues.sort_by(|a, b| a.sport.cmp(&b.sport));
+
+    let Some(wanted) = &args.league else {
+        if !ctx.table() {
+            return emit(ctx, &leagues);
+        }
+        println!("{}", paint("Leagues", &(String::from(C::BOLD) + C::CYAN), ctx.color));
+        for league in &leagues {
+            println!("{}", league.sport);
+        }
+        return 0;
+    };
+    let Some(league) = leagues.iter().find(|l| l.sport.eq_ignore_ascii_case(wanted)) else {
+        let known: Vec<&str> = leagues.iter().map(|l| l.sport.as_str()).collect();
+        return ctx.invalid(&format!("unknown league '{wanted}' (expected one of: {})", known.join(", ")));
+    };
+
+    let matchups = match fetch_matchups(league, args.limit).await {
+        Ok(m) => m,
+        Err(e) => return ctx.fail("fetch games", &e),
+    };
+
+    if !ctx.table() {
+        return emit(ctx, &matchups);
+    }
+
+    let title = format!("{} games", league.sport.to_uppercase());
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if matchups.is_empty() {
+        println!("{}", paint("No open games listed for this league", C::DIM, ctx.color));
+        return 0;
+    }
+    println!("{}", render_matchups(&matchups, ctx.color));
+    0
+}
@@ -0,0 +1,44 @@
+use chrono::Utc;
+use poly_core::{format_age, format_span, HttpFetcher, OutputFormat, ReqwestFetcher};
+
+use crate::cli::StatusArgs;
+use crate::commands::daemon::DaemonStatus;
+use crate::commands::{emit_document, Context};
+
+pub async fn run(args: &StatusArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("status", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    let url = format!("{}/status", args.url.trim_end_matches('/'));
+    let fetched = ReqwestFetcher::default().get_json(&url, &[]).await;
+    let status = match fetched.and_then(|payload| Ok(serde_json::from_value::<DaemonStatus>(payload)?)) {
+        Ok(s) => s,
+        Err(e) => return ctx.fail(&format!("reach the daemon at {}", args.url), &e),
+    };
+
+    if let Some(code) = emit_document(ctx, &status) {
+        return code;
+    }
+
+    let now = Utc::now();
+    println!("Daemon pid {}, up {}", status.pid, format_span(now - status.started_at));
+    println!(
+        "Snapshots: {} recorded into {} every {}, {} failed",
+        status.snapshots,
+        status.db,
+        format_span(chrono::Duration::seconds(status.every_secs as i64)),
+        status.failures
+    );
+    match status.last_snapshot_at {
+        Some(at) => println!("Last: {} ago, {} markets", format_age(now - at), status.last_markets),
+        None => println!("Last: none yet"),
+    }
+    if let Some(at) = status.next_snapshot_at {
+        println!("Next: in {}", format_span(at - now));
+    }
+    if let Some(error) = &status.last_error {
+        println!("Last error: {error}");
+    }
+    0
+}
@@ -0,0 +1,42 @@
+use poly_core::{fetch_events, paint, render_tags, summarize_tags, EventQuery, OutputFormat, C};
+
+use crate::cli::TagsArgs;
+use crate::commands::{emit, Context};
+
+pub async fn run(args: &TagsArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("tags", &[OutputFormat::Html]) {
+        return code;
+    }
+    if args.top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if args.fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+
+    // Gamma's tag list has no counts, so tags are tallied from the open events themselves.
+    let events = match fetch_events(&EventQuery::new(args.fetch_limit)).await {
+        Ok(e) => e,
+        Err(e) => return ctx.fail("fetch events", &e),
+    };
+    let mut tags = summarize_tags(&events);
+    let total = tags.len();
+    tags.truncate(args.top);
+
+    if !ctx.table() {
+        return emit(ctx, &tags);
+    }
+
+    let title = format!("Tags across the top {} open events", events.len());
+    println!("{}", paint(&title, &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    if tags.is_empty() {
+        println!("{}", paint("No tagged events found", C::DIM, ctx.color));
+        return 0;
+    }
+    println!("{}", render_tags(&tags, ctx.color));
+    if total > tags.len() {
+        let more = format!("{} more tags; raise --top to see them", total - tags.len());
+        println!("{}", paint(&more, C::DIM, ctx.color));
+    }
+    0
+}
@@ -0,0 +1,585 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use poly_core::{
+    append_csv_log, attach_momentum, attach_quotes, attach_spikes, attach_trends, cache, fetch_market_rows,
+    fetch_venue_markets, format_age, paint, read_csv_log_volumes, render_table_with, terminal_width, Alert,
+    AlertEngine, AlertRule, Column, EventQuery, HistoryCache, MarketChannel, PolyError, Row, RowFilter, SnapshotDb,
+    TableLayout, Venue, VolumeBaseline, C,
+};
+use tokio::sync::{mpsc, Notify};
+use tracing::warn;
+
+use crate::cli::{SortArgs, TopArgs};
+use crate::commands::{
+    emit_rows, encode_rows, ring_bell, watch_keys, watchlist, write_atomic, Context, Pacer, Screen, Session,
+    DEFAULT_FETCH_LIMIT, DEFAULT_INTERVAL, DEFAULT_TOP, MINIMAL_FETCH_LIMIT,
+};
+use crate::config::Fields;
+use crate::notify::{self, NotifyTarget};
+use crate::tui::{self, Update};
+
+// How far back the sparkline column looks.
+const TREND_HOURS: i64 = 24;
+// Hourly price history barely changes between refreshes, so it's reused for this long.
+const HISTORY_TTL: Duration = Duration::from_secs(300);
+// The spike baseline is a trailing week ending a day ago, so rebuilding it hourly is plenty.
+const BASELINE_TTL: Duration = Duration::from_secs(3600);
+
+type BuiltBaseline = (Instant, Arc<VolumeBaseline>);
+
+// TopArgs merged with the config file: CLI flags win, then config values, then built-in defaults.
+#[derive(Debug, Clone)]
+struct Settings {
+    top: usize,
+    fetch_limit: usize,
+    watch: bool,
+    interval: u64,
+    deltas: bool,
+    alerts: Vec<AlertRule>,
+    beep: bool,
+    notify: Vec<NotifyTarget>,
+    stream: bool,
+    db: Option<PathBuf>,
+    output: Option<PathBuf>,
+    log_csv: Option<PathBuf>,
+    venues: Vec<Venue>,
+    // Explicit market slugs to show instead of the volume-ranked feed.
+    watchlist: Option<Vec<String>>,
+    sparklines: bool,
+    momentum: bool,
+    spreads: bool,
+    // Volume multiple that flags a spike against the --db or --log-csv history.
+    spike: Option<f64>,
+    // The last spike baseline built and when, reused across refreshes until BASELINE_TTL.
+    baseline: Arc<Mutex<Option<BuiltBaseline>>>,
+    columns: Option<Vec<Column>>,
+    // Shared by the sparkline and momentum columns across refreshes.
+    history: Arc<HistoryCache>,
+    query: EventQuery,
+    filter: RowFilter,
+    sort: SortArgs,
+}
+
+impl Settings {
+    fn resolve(args: &TopArgs, ctx: &Context) -> Result<Self, String> {
+        let config = &ctx.config;
+        let top = args.top.or(config.top).unwrap_or(DEFAULT_TOP);
+        let fields = args.fields.or(config.fields).unwrap_or_default();
+        let fetch_limit = args.fetch_limit.or(config.fetch_limit).unwrap_or(match fields {
+            Fields::Minimal => MINIMAL_FETCH_LIMIT,
+            Fields::Full => DEFAULT_FETCH_LIMIT,
+        });
+        let watchlist = if args.watchlist { Some(watchlist::load()?) } else { None };
+        if watchlist.as_ref().is_some_and(Vec::is_empty) {
+            return Err("watchlist is empty; add markets with `poly watchlist add <slug>`".to_string());
+        }
+        let settings = Settings {
+            top,
+            fetch_limit,
+            watch: args.watch,
+            interval: args.interval.or(config.interval).unwrap_or(DEFAULT_INTERVAL),
+            deltas: args.deltas,
+            alerts: args.alerts.clone(),
+            beep: args.beep,
+            notify: args
+                .notify
+                .iter()
+                .cloned()
+                .chain(args.webhook.clone().map(NotifyTarget::Webhook))
+                .collect(),
+            stream: args.stream,
+            db: args.db.clone(),
+            output: args.output.clone(),
+            log_csv: args.log_csv.clone(),
+            venues: ctx.venues(&args.venues, &[Venue::Polymarket]),
+            watchlist,
+            sparklines: args.sparklines,
+            momentum: args.momentum,
+            spreads: args.spreads,
+            spike: args.spike,
+            baseline: Arc::default(),
+            columns: ctx.columns.clone(),
+            history: Arc::new(HistoryCache::new(HISTORY_TTL)),
+            query: args.filter.event_query(fetch_limit.max(top)),
+            filter: args.filter.row_filter(),
+            sort: args.sort.clone(),
+        };
+
+        if settings.top < 1 {
+            return Err("--top must be >= 1".to_string());
+        }
+        if settings.fetch_limit < 1 {
+            return Err("--fetch-limit must be >= 1".to_string());
+        }
+        if settings.interval < 2 {
+            return Err("--interval must be >= 2".to_string());
+        }
+        if settings.spike.is_some() && settings.db.is_none() && settings.log_csv.is_none() {
+            return Err("--spike needs snapshot history from --db or --log-csv".to_string());
+        }
+        if fields == Fields::Minimal && (settings.sparklines || settings.momentum || settings.spreads) {
+            return Err("--fields minimal can't be combined with --sparklines, --momentum or --spreads".to_string());
+        }
+
+        Ok(settings)
+    }
+
+    fn heading(&self) -> String {
+        match self.watchlist {
+            Some(_) => "Polymarket Watchlist".to_string(),
+            None => format!("Polymarket Top {}", self.top),
+        }
+    }
+
+    fn title(&self) -> String {
+        format!("{} by {}", self.heading(), self.sort.label())
+    }
+
+    // The rows, and a warning naming any watchlist slugs gamma no longer knows.
+    async fn fetch(&self) -> Result<(Vec<Row>, Option<String>), PolyError> {
+        match &self.watchlist {
+            Some(slugs) => {
+                let (rows, missing) = fetch_market_rows(slugs).await?;
+                let warning = (!missing.is_empty())
+                    .then(|| format!("Not found, skipped from the watchlist: {}", missing.join(", ")));
+                Ok((rows, warning))
+            }
+            None => Ok((fetch_venue_markets(&self.venues, &self.query).await?, None)),
+        }
+    }
+
+    // Appends a freshly fetched snapshot to the --log-csv file, if there is one.
+    fn log(&self, rows: &[Row]) -> Result<(), String> {
+        let Some(path) = &self.log_csv else {
+            return Ok(());
+        };
+        append_csv_log(path, rows, &Utc::now().to_rfc3339())
+            .map_err(|e| format!("Failed to append to {}: {e}", path.display()))
+    }
+
+    // Filters, sorts, and cuts freshly fetched rows down to what gets displayed.
+    fn prepare(&self, rows: Vec<Row>) -> Vec<Row> {
+        let mut rows = self.filter.apply(rows);
+        self.sort.apply(&mut rows);
+        rows.truncate(self.top);
+        rows
+    }
+
+    // The trailing volume baseline from whichever history store is configured, the database
+    // first. A store with under a day of history yet gives an empty baseline.
+    fn baseline(&self) -> Result<VolumeBaseline, String> {
+        let (since, until) = VolumeBaseline::window(Utc::now());
+        let samples = match (&self.db, &self.log_csv) {
+            (Some(path), _) => SnapshotDb::open(path)
+                .and_then(|db| db.volume_samples(since, until))
+                .map_err(|e| format!("Failed to read volume history from {}: {e}", path.display()))?,
+            // The log is only created on the first refresh.
+            (None, Some(path)) if !path.exists() => Vec::new(),
+            (None, Some(path)) => read_csv_log_volumes(path, since, until)
+                .map_err(|e| format!("Failed to read volume history from {}: {e}", path.display()))?,
+            (None, None) => Vec::new(),
+        };
+        Ok(VolumeBaseline::from_samples(samples))
+    }
+
+    // The baseline from the last hour, rebuilt from the history store once it's older. A failed
+    // read isn't kept, so the next refresh tries again.
+    fn cached_baseline(&self) -> Result<Arc<VolumeBaseline>, String> {
+        let mut cached = self.baseline.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((_, baseline)) = cached.as_ref().filter(|(built, _)| built.elapsed() < BASELINE_TTL) {
+            return Ok(Arc::clone(baseline));
+        }
+        let baseline = Arc::new(self.baseline()?);
+        *cached = Some((Instant::now(), Arc::clone(&baseline)));
+        Ok(baseline)
+    }
+
+    // Watchlisted markets are followed whatever their state, so they also alert on resolving.
+    fn alert_engine(&self) -> AlertEngine {
+        let engine = AlertEngine::new(self.alerts.clone());
+        match self.watchlist {
+            Some(_) => engine.track_resolutions(),
+            None => engine,
+        }
+    }
+
+    // Per-row extras that cost a request each, so they're only fetched for displayed rows.
+    async fn decorate(&self, rows: &mut [Row]) {
+        if let Some(threshold) = self.spike {
+            match self.cached_baseline() {
+                Ok(baseline) => attach_spikes(rows, &baseline, threshold),
+                // Printing would tear the TUI, and the other extras skip quietly too.
+                Err(e) => warn!("{e}"),
+            }
+        }
+        if self.sparklines {
+            attach_trends(&self.history, rows, TREND_HOURS).await;
+        }
+        if self.momentum {
+            attach_momentum(&self.history, rows).await;
+        }
+        if self.spreads {
+            attach_quotes(rows).await;
+        }
+    }
+}
+
+pub async fn run(args: &TopArgs, ctx: &Context) -> i32 {
+    let args = match Settings::resolve(args, ctx) {
+        Ok(s) => s,
+        Err(e) => return ctx.invalid(&e),
+    };
+    let color = ctx.color;
+    // The stream repaints a price table in place; there's no row format it could write instead.
+    if args.stream && !ctx.table() {
+        return ctx.invalid(&format!("--stream only works with the table format, not --format {}", ctx.format));
+    }
+
+    let mut db = match args.db.as_deref().map(SnapshotDb::open).transpose() {
+        Ok(db) => db,
+        Err(e) => return ctx.fail("open snapshot database", &e),
+    };
+
+    if args.stream && ctx.table() {
+        return run_stream(&args, ctx, color).await;
+    }
+    if args.watch && ctx.table() && args.output.is_none() {
+        return run_tui(args, db).await;
+    }
+
+    let session = Session::start();
+    let mut pacer = Pacer::new(Duration::from_secs(args.interval));
+    if args.watch {
+        session.catch_interrupts();
+        pacer.keys = watch_keys();
+        if pacer.keys.is_some() {
+            eprintln!("Type q, r or p and press Enter to quit, refresh now, or pause");
+        }
+    }
+    let mut engine = args.alert_engine();
+    loop {
+        let rows = match args.fetch().await {
+            Ok((r, warning)) => {
+                if let Some(warning) = warning {
+                    eprintln!("{warning}");
+                }
+                r
+            }
+            Err(e) if args.watch => {
+                ctx.warn("fetch data", &e);
+                if !pacer.wait(&session).await {
+                    break;
+                }
+                continue;
+            }
+            Err(e) => return ctx.fail("fetch data", &e),
+        };
+
+        if let Some(db) = db.as_mut() {
+            if let Err(e) = db.record(Utc::now(), &rows) {
+                if !args.watch {
+                    return ctx.fail("record snapshot", &e);
+                }
+                ctx.warn("record snapshot", &e);
+            }
+        }
+        if let Err(e) = args.log(&rows) {
+            eprintln!("{e}");
+            if !args.watch {
+                return 1;
+            }
+        }
+
+        let alerts = engine.evaluate(&args.filter.apply(rows.clone()));
+        let mut rows = args.prepare(rows);
+        args.decorate(&mut rows).await;
+
+        if let Some(path) = &args.output {
+            // Files get plain text at fixed widths, whatever the terminal looks like.
+            let output = if ctx.table() {
+                Ok(dashboard(&rows, &args, false, None, None, &alerts).into_bytes())
+            } else {
+                encode_rows(ctx, &rows, &args.title())
+            };
+            let written = output.and_then(|bytes| {
+                write_atomic(path, &bytes).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+            });
+            // Like a failed snapshot, a failed write skips this refresh rather than ending the watch.
+            if let Err(e) = written {
+                eprintln!("{e}");
+                if !args.watch {
+                    return 1;
+                }
+            }
+            for alert in &alerts {
+                eprintln!("ALERT {alert}");
+            }
+        } else if !ctx.table() {
+            let code = emit_rows(ctx, &rows, &args.title());
+            if code != 0 {
+                return code;
+            }
+            for alert in &alerts {
+                eprintln!("ALERT {alert}");
+            }
+        } else {
+            // Fitted to the terminal on every frame, so resizing mid-watch takes effect.
+            let frame = dashboard(&rows, &args, color, terminal_width(), None, &alerts);
+            if let Err(e) = Screen::default().paint(&frame) {
+                eprintln!("Failed to write output: {e}");
+                return 1;
+            }
+        }
+        if args.beep && !alerts.is_empty() {
+            ring_bell();
+        }
+        let delivery = notify::deliver_reported(&args.notify, Some(&rows), &alerts);
+        session.refreshed();
+
+        // A file is rewritten each refresh, so any format can keep going.
+        if !args.watch || (args.output.is_none() && !ctx.format.is_continuous()) {
+            let _ = delivery.await;
+            return 0;
+        }
+        if !pacer.wait(&session).await {
+            break;
+        }
+    }
+
+    eprintln!("{}", session.summary());
+    0
+}
+
+fn dashboard(
+    rows: &[Row],
+    args: &Settings,
+    color: bool,
+    width: Option<usize>,
+    status: Option<&str>,
+    alerts: &[Alert],
+) -> String {
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S UTC");
+    let title = paint(&args.title(), &(String::from(C::BOLD) + C::CYAN), color);
+    let updated = match cache::served_since() {
+        Some(at) => {
+            let at: DateTime<Utc> = at.into();
+            let age = format_age(Utc::now() - at);
+            let stale = format!("OFFLINE: cached {} ({age} ago)", at.format("%Y-%m-%d %H:%M UTC"));
+            paint(&stale, &(String::from(C::BOLD) + C::YELLOW), color)
+        }
+        None => paint(&format!("Updated: {now}"), C::DIM, color),
+    };
+
+    let header = match status {
+        Some(status) => format!("{title}  |  {updated}  |  {}", paint(status, C::GREEN, color)),
+        None => format!("{title}  |  {updated}"),
+    };
+    let layout = TableLayout { columns: args.columns.clone(), width };
+    let mut frame = format!("{header}\n{}\n", render_table_with(rows, args.top, &layout, color));
+    for alert in alerts {
+        frame.push_str(&paint(&format!("\n⚠ ALERT {alert}"), &(String::from(C::BOLD) + C::RED), color));
+    }
+    let sources: Vec<&str> = args.venues.iter().map(|venue| venue.label()).collect();
+    frame.push_str(&paint(&format!("\nSource: {}", sources.join(", ")), C::DIM, color));
+    frame
+}
+
+// Background refresh loop for the TUI: fetches on every interval or when the UI asks for an
+// immediate refresh, and stops once the UI side hangs up.
+async fn feed_tui(
+    args: Settings,
+    mut db: Option<SnapshotDb>,
+    tx: mpsc::Sender<Update>,
+    refresh: Arc<Notify>,
+    session: Arc<Session>,
+) {
+    let mut engine = args.alert_engine();
+    loop {
+        let mut updates = Vec::new();
+        let mut deliver = None;
+        match args.fetch().await {
+            Ok((rows, missing)) => {
+                updates.extend(missing.map(Update::Error));
+                let warning = db.as_mut().and_then(|db| db.record(Utc::now(), &rows).err());
+                let log_failure = args.log(&rows).err();
+                let mut rows = args.filter.apply(rows);
+                let alerts = engine.evaluate(&rows);
+                let mut shown = args.prepare(rows.clone());
+                args.decorate(&mut shown).await;
+                // The TUI re-sorts locally, so extras fetched for the displayed rows are copied
+                // back onto the full row set by token.
+                for row in rows.iter_mut() {
+                    let decorated = shown.iter().find(|s| s.token_id.is_some() && s.token_id == row.token_id);
+                    if let Some(decorated) = decorated {
+                        row.trend = decorated.trend.clone();
+                        row.momentum = decorated.momentum;
+                        row.spike = decorated.spike;
+                        row.best_bid = decorated.best_bid;
+                        row.best_ask = decorated.best_ask;
+                        row.spread = decorated.spread;
+                        row.midpoint = decorated.midpoint;
+                    }
+                }
+                session.refreshed();
+                deliver = Some((shown, alerts.clone()));
+                updates.push(Update::Rows(rows));
+                if !alerts.is_empty() {
+                    updates.push(Update::Alerts(alerts));
+                }
+                if let Some(e) = warning {
+                    updates.push(Update::Error(format!("Failed to record snapshot: {e}")));
+                }
+                updates.extend(log_failure.map(Update::Error));
+            }
+            Err(e) => updates.push(Update::Error(format!("Failed to fetch data: {e}"))),
+        }
+
+        for update in updates {
+            if tx.send(update).await.is_err() {
+                return;
+            }
+        }
+
+        // Notifications go out after the table, on their own task so slow targets never hold
+        // up the redraw or the next refresh.
+        if let Some((snapshot, alerts)) = deliver {
+            let delivery = notify::deliver(&args.notify, Some(&snapshot), &alerts);
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                for failure in delivery.await.unwrap_or_default() {
+                    if tx.send(Update::Error(failure)).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(args.interval)) => {}
+            _ = refresh.notified() => {}
+        }
+    }
+}
+
+async fn run_tui(args: Settings, db: Option<SnapshotDb>) -> i32 {
+    let (tx, rx) = mpsc::channel(4);
+    let refresh = Arc::new(Notify::new());
+    let options = tui::Options {
+        heading: args.heading(),
+        top: args.top,
+        sort: args.sort.specs(),
+        deltas: args.deltas,
+        beep: args.beep,
+    };
+    // The TUI reads Ctrl-C as a key press, so it needs no signal handling of its own.
+    let session = Arc::new(Session::start());
+    let feeder = tokio::spawn(feed_tui(args, db, tx, refresh.clone(), session.clone()));
+
+    let result = tui::run(options, rx, refresh).await;
+    feeder.abort();
+
+    match result {
+        Ok(()) => {
+            eprintln!("{}", session.summary());
+            0
+        }
+        Err(e) => {
+            eprintln!("Terminal error: {e}");
+            1
+        }
+    }
+}
+
+// Redraws are coalesced so a burst of WebSocket frames doesn't repaint the screen per message.
+const STREAM_REDRAW_EVERY: Duration = Duration::from_millis(250);
+// How many of the latest alerts stay listed under the live table.
+const STREAM_RECENT_ALERTS: usize = 5;
+
+async fn run_stream(args: &Settings, ctx: &Context, color: bool) -> i32 {
+    let mut rows = match args.fetch().await {
+        Ok((r, warning)) => {
+            if let Some(warning) = warning {
+                eprintln!("{warning}");
+            }
+            args.prepare(r)
+        }
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+    args.decorate(&mut rows).await;
+
+    let asset_ids: Vec<String> = rows.iter().filter_map(|r| r.token_id.clone()).collect();
+    if asset_ids.is_empty() {
+        eprintln!("None of the displayed markets have CLOB token ids to stream");
+        return 1;
+    }
+
+    let mut channel = match MarketChannel::connect(&asset_ids).await {
+        Ok(c) => c,
+        Err(e) => return ctx.fail("open price stream", &e),
+    };
+
+    let mut engine = args.alert_engine();
+    let mut recent = engine.evaluate(&rows);
+    let mut screen = Screen::live();
+    if let Err(e) = screen.paint(&dashboard(&rows, args, color, terminal_width(), Some("LIVE"), &recent)) {
+        eprintln!("Failed to write output: {e}");
+        return 1;
+    }
+    if args.beep && !recent.is_empty() {
+        ring_bell();
+    }
+    notify::deliver_reported(&args.notify, Some(&rows), &recent);
+    let session = Session::start();
+    session.catch_interrupts();
+    session.refreshed();
+    let mut last_draw = Instant::now();
+    let mut dirty = false;
+
+    loop {
+        let next = tokio::select! {
+            next = tokio::time::timeout(STREAM_REDRAW_EVERY, channel.next_updates()) => next,
+            _ = session.interrupted() => {
+                eprintln!("{}", session.summary());
+                return 0;
+            }
+        };
+        match next {
+            Ok(Some(Ok(updates))) => {
+                for update in updates {
+                    for row in rows.iter_mut().filter(|r| r.token_id.as_deref() == Some(update.asset_id.as_str())) {
+                        row.price = Some(update.price);
+                        dirty = true;
+                    }
+                }
+            }
+            Ok(Some(Err(e))) => return ctx.fail("read price stream", &e),
+            Ok(None) => {
+                eprintln!("{}", session.summary());
+                return ctx.fail("read price stream", &PolyError::StreamClosed);
+            }
+            Err(_) => {}
+        }
+
+        if dirty && last_draw.elapsed() >= STREAM_REDRAW_EVERY {
+            let fired = engine.evaluate(&rows);
+            if args.beep && !fired.is_empty() {
+                ring_bell();
+            }
+            notify::deliver_reported(&args.notify, None, &fired);
+            recent.extend(fired);
+            let overflow = recent.len().saturating_sub(STREAM_RECENT_ALERTS);
+            recent.drain(..overflow);
+
+            if let Err(e) = screen.paint(&dashboard(&rows, args, color, terminal_width(), Some("LIVE"), &recent)) {
+                eprintln!("Failed to write output: {e}");
+                return 1;
+            }
+            session.refreshed();
+            last_draw = Instant::now();
+            dirty = false;
+        }
+    }
+}
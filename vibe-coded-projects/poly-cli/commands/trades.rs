@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use poly_core::{fetch_trades, paint, render_trade_line, OutputFormat, Trade, C};
+
+use crate::cli::TradesArgs;
+use crate::commands::{emit, emit_line, resolve_condition_id, Context};
+
+type TradeKey = (Option<String>, Option<String>);
+
+fn owned_key(trade: &Trade) -> TradeKey {
+    let (hash, asset) = trade.key();
+    (hash.map(str::to_string), asset.map(str::to_string))
+}
+
+pub async fn run(args: &TradesArgs, ctx: &Context) -> i32 {
+    if args.limit < 1 {
+        return ctx.invalid("--limit must be >= 1");
+    }
+    if args.interval < 2 {
+        return ctx.invalid("--interval must be >= 2");
+    }
+    if let Some(code) = ctx.reject_formats("trades", &[OutputFormat::Html]) {
+        return code;
+    }
+    if args.follow && matches!(ctx.format, OutputFormat::Csv | OutputFormat::Yaml) {
+        return ctx.invalid(&format!("--format {} can't be combined with --follow", ctx.format));
+    }
+
+    let condition_id = match resolve_condition_id(&args.market).await {
+        Ok(id) => id,
+        Err(e) => return ctx.fail("resolve market", &e),
+    };
+
+    let mut seen: HashSet<TradeKey> = HashSet::new();
+    let mut first = true;
+
+    loop {
+        let trades = match fetch_trades(&condition_id, args.limit).await {
+            Ok(t) => t,
+            Err(e) if args.follow && !first => {
+                eprintln!("Failed to fetch trades: {e}");
+                tokio::time::sleep(Duration::from_secs(args.interval)).await;
+                continue;
+            }
+            Err(e) => return ctx.fail("fetch trades", &e),
+        };
+
+        if !ctx.table() && !args.follow {
+            return emit(ctx, &trades);
+        }
+        if ctx.format == OutputFormat::Ndjson {
+            let fresh: Vec<&Trade> = trades.iter().rev().filter(|t| seen.insert(owned_key(t))).collect();
+            let code = emit(ctx, &fresh);
+            if code != 0 {
+                return code;
+            }
+            first = false;
+            tokio::time::sleep(Duration::from_secs(args.interval)).await;
+            continue;
+        }
+
+        if first && !ctx.json() {
+            let header = format!(
+                "{:<8}  {:<4}  {:<10}  {:>7}  {:>12}  {}",
+                "Time", "Side", "Outcome", "Price", "Size", "Notional"
+            );
+            println!("{}", paint(&header, &(String::from(C::BLUE) + C::BOLD), ctx.color));
+        }
+
+        // The API returns newest first; print oldest first so the tape scrolls forward in time.
+        for trade in trades.iter().rev() {
+            if !seen.insert(owned_key(trade)) {
+                continue;
+            }
+            if ctx.json() {
+                let code = emit_line(ctx, trade);
+                if code != 0 {
+                    return code;
+                }
+            } else {
+                println!("{}", render_trade_line(trade, ctx.color));
+            }
+        }
+
+        if !args.follow {
+            return 0;
+        }
+        first = false;
+        tokio::time::sleep(Duration::from_secs(args.interval)).await;
+    }
+}
@@ -0,0 +1,60 @@
+use poly_core::{paint, verify_market, OutputFormat, Verdict, C};
+
+use crate::cli::VerifyArgs;
+use crate::commands::{emit_document, Context};
+
+// Exit code when gamma and the chain disagree on the winner, so a cron check can alert on it.
+const MISMATCH: i32 = 12;
+
+pub async fn run(args: &VerifyArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("verify", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    let rpc = ctx.rpc(args.rpc_url.as_deref());
+    let check = match verify_market(&rpc, &args.slug).await {
+        Ok(v) => v,
+        Err(e) => return ctx.fail("verify resolution", &e),
+    };
+
+    let verdict_code = if check.verdict == Verdict::Mismatch { MISMATCH } else { 0 };
+    if let Some(code) = emit_document(ctx, &check) {
+        return if code == 0 { verdict_code } else { code };
+    }
+
+    println!("{}", paint(&format!("Verify {}", check.title), &(String::from(C::BOLD) + C::CYAN), ctx.color));
+    println!("Condition:  {}", paint(&check.condition_id, C::DIM, ctx.color));
+    let uma = check.uma_status.as_deref().map(|s| format!(" (UMA: {s})")).unwrap_or_default();
+    let gamma = match (&check.gamma_winner, check.gamma_closed) {
+        (Some(winner), _) => format!("closed, winner {winner}{uma}"),
+        (None, true) => format!("closed, no winner shown{uma}"),
+        (None, false) => format!("open{uma}"),
+    };
+    println!("Gamma:      {gamma}");
+    let chain = match &check.payouts {
+        Some(payouts) => {
+            let split: Vec<String> = payouts
+                .iter()
+                .enumerate()
+                .map(|(idx, p)| {
+                    let name = check.outcomes.get(idx).map_or_else(|| format!("outcome {idx}"), String::clone);
+                    format!("{name} {p}")
+                })
+                .collect();
+            let winner = check.chain_winner.as_deref().map_or("split payout".to_string(), |w| format!("winner {w}"));
+            format!("payouts {}, {winner}", split.join(" / "))
+        }
+        None => "not reported yet".to_string(),
+    };
+    println!("On chain:   {chain}");
+
+    let (verdict, color) = match check.verdict {
+        Verdict::Match => ("match", C::GREEN),
+        Verdict::Mismatch => ("MISMATCH: gamma and the chain disagree on the winner", C::RED),
+        Verdict::PendingOnChain => ("pending: gamma shows a winner the oracle hasn't reported on chain", C::YELLOW),
+        Verdict::PendingGamma => ("pending: settled on chain but gamma shows no winner", C::YELLOW),
+        Verdict::Unresolved => ("unresolved", C::DIM),
+    };
+    println!("Verdict:    {}", paint(verdict, &(String::from(color) + C::BOLD), ctx.color));
+    verdict_code
+}
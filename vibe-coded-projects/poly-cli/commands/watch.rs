@@ -0,0 +1,222 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use chrono::Utc;
+use poly_core::{
+    attach_quotes, fetch_market_rows, fetch_price_history, fetch_trades, format_end, format_money, format_percent,
+    format_price, paint, render_trade_line, sparkline, Alert, AlertEngine, PolyError, Row, Trade, C,
+};
+use tracing::warn;
+
+use crate::cli::WatchArgs;
+use crate::config::Fields;
+use crate::commands::{
+    emit_rows, resolve_condition_id, ring_bell, watch_keys, Context, Pacer, Screen, Session, DEFAULT_INTERVAL,
+};
+use crate::notify::{self, NotifyTarget};
+
+// The sparkline starts from this much price history, sampled this often, and then grows by one
+// point per refresh.
+const SEED_HOURS: i64 = 24;
+const SEED_FIDELITY_MINUTES: i64 = 15;
+// Points kept for the sparkline; the oldest scroll off as refreshes add new ones.
+const MAX_POINTS: usize = 240;
+const SPARK_WIDTH: usize = 60;
+// Alerts kept on screen under the header.
+const RECENT_ALERTS: usize = 5;
+// Recent trades fetched per refresh under --fields minimal.
+const MINIMAL_TRADES: usize = 3;
+
+// The market's row, with its bid/ask from the order book when `quotes` is set.
+async fn fetch_row(slug: &str, quotes: bool) -> Result<Row, PolyError> {
+    let (mut rows, _) = fetch_market_rows(&[slug.to_string()]).await?;
+    if quotes {
+        attach_quotes(&mut rows).await;
+    }
+    rows.pop().ok_or_else(|| PolyError::NotFound(format!("market '{slug}'")))
+}
+
+async fn seed_prices(row: &Row) -> VecDeque<f64> {
+    let Some(token) = &row.token_id else {
+        return VecDeque::new();
+    };
+    let end = Utc::now().timestamp();
+    match fetch_price_history(token, end - SEED_HOURS * 3600, end, SEED_FIDELITY_MINUTES).await {
+        Ok(points) => points.into_iter().map(|point| point.p).collect(),
+        Err(e) => {
+            warn!(%token, error = %e, "no price history to seed the sparkline");
+            VecDeque::new()
+        }
+    }
+}
+
+fn frame(row: &Row, trades: &[Trade], prices: &VecDeque<f64>, alerts: &[Alert], color: bool) -> String {
+    let label = |text: &str| paint(text, &(String::from(C::BLUE) + C::BOLD), color);
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S UTC");
+    let mut lines = vec![format!(
+        "{}  |  {}",
+        paint(&row.title, &(String::from(C::BOLD) + C::CYAN), color),
+        paint(&format!("Updated: {now}"), C::DIM, color)
+    )];
+    if !row.event.is_empty() && row.event != row.title {
+        lines.push(paint(&row.event, C::DIM, color));
+    }
+    if let Some(outcome) = &row.resolution {
+        lines.push(paint(&format!("RESOLVED: {outcome}"), &(String::from(C::GREEN) + C::BOLD), color));
+    }
+    for alert in alerts {
+        lines.push(paint(&format!("⚠ ALERT {alert}"), &(String::from(C::BOLD) + C::RED), color));
+    }
+    lines.push(String::new());
+
+    let change = match row.change_24h_pct {
+        Some(v) if v > 0.0 => paint(&format_percent(Some(v)), C::GREEN, color),
+        Some(v) if v < 0.0 => paint(&format_percent(Some(v)), C::RED, color),
+        other => paint(&format_percent(other), C::DIM, color),
+    };
+    lines.push(format!(
+        "{} {} ({change} 24h)   {} {} / {}   {} {}",
+        label("Price"),
+        paint(&format_price(row.price), &(String::from(C::YELLOW) + C::BOLD), color),
+        label("Bid / Ask"),
+        format_price(row.best_bid),
+        format_price(row.best_ask),
+        label("Spread"),
+        format_price(row.spread),
+    ));
+    let liquidity = row.liquidity.map_or_else(|| "n/a".to_string(), format_money);
+    lines.push(format!(
+        "{} {}   {} {}   {} {}   {} {}",
+        label("24h Volume"),
+        paint(&format_money(row.volume_24h), C::CYAN, color),
+        label("Volume"),
+        format_money(row.volume),
+        label("Liquidity"),
+        liquidity,
+        label("Ends"),
+        format_end(row.end_date.as_deref()),
+    ));
+
+    lines.push(String::new());
+    let prices: Vec<f64> = prices.iter().copied().collect();
+    if prices.is_empty() {
+        lines.push(paint("No price history yet.", C::DIM, color));
+    } else {
+        let low = prices.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let rising = prices.last() >= prices.first();
+        lines.push(format!(
+            "{}  {}",
+            paint(&sparkline(&prices, SPARK_WIDTH), if rising { C::GREEN } else { C::RED }, color),
+            paint(&format!("low {}  high {}", format_price(Some(low)), format_price(Some(high))), C::DIM, color),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(label("Recent trades"));
+    if trades.is_empty() {
+        lines.push(paint("No trades yet.", C::DIM, color));
+    } else {
+        let header =
+            format!("{:<8}  {:<4}  {:<10}  {:>7}  {:>12}  {}", "Time", "Side", "Outcome", "Price", "Size", "Notional");
+        lines.push(paint(&header, C::DIM, color));
+        lines.extend(trades.iter().map(|trade| render_trade_line(trade, color)));
+    }
+
+    if let Some(url) = row.url() {
+        lines.push(String::new());
+        lines.push(paint(&url, C::DIM, color));
+    }
+    lines.join("\n")
+}
+
+pub async fn run(slug: &str, args: &WatchArgs, ctx: &Context) -> i32 {
+    let interval = args.top.interval.or(ctx.config.interval).unwrap_or(DEFAULT_INTERVAL);
+    if interval < 2 {
+        return ctx.invalid("--interval must be >= 2");
+    }
+    let minimal = args.top.fields.or(ctx.config.fields) == Some(Fields::Minimal);
+    let trade_limit = if minimal { args.trades.min(MINIMAL_TRADES) } else { args.trades };
+
+    let condition_id = match resolve_condition_id(slug).await {
+        Ok(id) => id,
+        Err(e) => return ctx.fail("resolve market", &e),
+    };
+    let targets: Vec<NotifyTarget> =
+        args.top.notify.iter().cloned().chain(args.top.webhook.clone().map(NotifyTarget::Webhook)).collect();
+    let mut engine = AlertEngine::new(args.top.alerts.clone()).track_resolutions();
+    let mut recent: Vec<Alert> = Vec::new();
+    let mut prices = VecDeque::new();
+    let mut first = true;
+    let mut screen = Screen::live();
+
+    let session = Session::start();
+    let mut pacer = Pacer::new(Duration::from_secs(interval));
+    if ctx.table() || ctx.format.is_continuous() {
+        session.catch_interrupts();
+    }
+    // Typed keys would be echoed over the repainted frame, so only the ndjson stream takes them.
+    if !ctx.table() {
+        pacer.keys = watch_keys();
+    }
+    loop {
+        let fetched = tokio::try_join!(fetch_row(slug, !minimal), async {
+            match trade_limit {
+                0 => Ok(Vec::new()),
+                limit => fetch_trades(&condition_id, limit).await,
+            }
+        });
+        let (row, trades) = match fetched {
+            Ok(fetched) => fetched,
+            Err(e) if !first => {
+                ctx.warn("fetch market", &e);
+                if !pacer.wait(&session).await {
+                    break;
+                }
+                continue;
+            }
+            Err(e) => return ctx.fail("fetch market", &e),
+        };
+
+        let alerts = engine.evaluate(std::slice::from_ref(&row));
+        if !ctx.table() {
+            let code = emit_rows(ctx, std::slice::from_ref(&row), &row.title);
+            if code != 0 || !ctx.format.is_continuous() {
+                return code;
+            }
+            for alert in &alerts {
+                eprintln!("ALERT {alert}");
+            }
+        } else {
+            recent.extend(alerts.iter().cloned());
+            let overflow = recent.len().saturating_sub(RECENT_ALERTS);
+            recent.drain(..overflow);
+            if first {
+                prices = seed_prices(&row).await;
+            }
+            if let Some(price) = row.price {
+                prices.push_back(price);
+                while prices.len() > MAX_POINTS {
+                    prices.pop_front();
+                }
+            }
+            if let Err(e) = screen.paint(&frame(&row, &trades, &prices, &recent, ctx.color)) {
+                eprintln!("Failed to write output: {e}");
+                return 1;
+            }
+        }
+        if args.top.beep && !alerts.is_empty() {
+            ring_bell();
+        }
+        notify::deliver_reported(&targets, None, &alerts);
+        session.refreshed();
+        first = false;
+
+        if !pacer.wait(&session).await {
+            break;
+        }
+    }
+
+    eprintln!("{}", session.summary());
+    0
+}
@@ -0,0 +1,143 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use poly_core::{fetch_market, OutputFormat};
+
+use crate::cli::{WatchlistArgs, WatchlistCommand};
+use crate::commands::{emit_document, Context};
+use crate::config;
+
+pub fn path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("watchlist.txt"))
+}
+
+// One slug per line; blank lines and `#` comments are ignored. A missing file is an empty list.
+pub fn load() -> Result<Vec<String>, String> {
+    let Some(path) = path() else {
+        return Ok(Vec::new());
+    };
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("failed to read watchlist {}: {e}", path.display())),
+    }
+}
+
+fn save(slugs: &[String]) -> Result<(), String> {
+    let path = path().ok_or("could not determine a config directory for the watchlist")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    }
+    let mut text = slugs.join("\n");
+    text.push('\n');
+    fs::write(&path, text).map_err(|e| format!("failed to write watchlist {}: {e}", path.display()))
+}
+
+// Machine-readable formats get the updated list back; the table format already narrated each change.
+fn saved(ctx: &Context, list: &[String]) -> i32 {
+    if let Err(e) = save(list) {
+        eprintln!("{e}");
+        return 1;
+    }
+    emit_document(ctx, list).unwrap_or(0)
+}
+
+async fn add(slugs: &[String], ctx: &Context) -> i32 {
+    let mut list = match load() {
+        Ok(list) => list,
+        Err(e) => return ctx.invalid(&e),
+    };
+
+    // A slug that fails its lookup is left out, but the others are still added and saved.
+    let mut failed = Vec::new();
+    for slug in slugs {
+        if list.contains(slug) {
+            println!("{slug} is already on the watchlist");
+            continue;
+        }
+        // Resolving the slug up front catches typos before they break the dashboard.
+        let market = match fetch_market(slug).await {
+            Ok(m) => m,
+            Err(e) => {
+                failed.push((slug, e));
+                continue;
+            }
+        };
+        list.push(slug.clone());
+        if ctx.table() {
+            println!("Added {slug} ({})", market.display_title());
+        }
+    }
+
+    let Some((first, error)) = failed.first() else {
+        return saved(ctx, &list);
+    };
+    if let Err(e) = save(&list) {
+        eprintln!("{e}");
+        return 1;
+    }
+    let names: Vec<String> = failed.iter().map(|(slug, _)| format!("'{slug}'")).collect();
+    let action = match failed.len() {
+        1 => format!("look up market '{first}'"),
+        _ => format!("look up markets {}", names.join(", ")),
+    };
+    ctx.fail(&action, error)
+}
+
+fn remove(slugs: &[String], ctx: &Context) -> i32 {
+    let mut list = match load() {
+        Ok(list) => list,
+        Err(e) => return ctx.invalid(&e),
+    };
+
+    for slug in slugs {
+        let before = list.len();
+        list.retain(|s| s != slug);
+        if ctx.table() {
+            if list.len() < before {
+                println!("Removed {slug}");
+            } else {
+                println!("{slug} is not on the watchlist");
+            }
+        }
+    }
+
+    saved(ctx, &list)
+}
+
+fn list(ctx: &Context) -> i32 {
+    let list = match load() {
+        Ok(list) => list,
+        Err(e) => return ctx.invalid(&e),
+    };
+
+    if let Some(code) = emit_document(ctx, &list) {
+        return code;
+    }
+
+    if list.is_empty() {
+        println!("Watchlist is empty; add markets with `poly watchlist add <slug>`");
+    }
+    for slug in &list {
+        println!("{slug}");
+    }
+    0
+}
+
+pub async fn run(args: &WatchlistArgs, ctx: &Context) -> i32 {
+    if let Some(code) = ctx.reject_formats("watchlist", OutputFormat::ROW_ONLY) {
+        return code;
+    }
+
+    match &args.command {
+        WatchlistCommand::Add { slugs } => add(slugs, ctx).await,
+        WatchlistCommand::Remove { slugs } => remove(slugs, ctx),
+        WatchlistCommand::List => list(ctx),
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use futures_util::future::try_join_all;
+use poly_core::{find_price_gaps, paint, render_xarb, EventQuery, OutputFormat, Venue, C};
+
+use crate::cli::XarbArgs;
+use crate::commands::{emit, Context, DEFAULT_FETCH_LIMIT, DEFAULT_TOP};
+
+pub async fn run(args: &XarbArgs, ctx: &Context) -> i32 {
+    let top = args.top.or(ctx.config.top).unwrap_or(DEFAULT_TOP);
+    let fetch_limit = args.fetch_limit.or(ctx.config.fetch_limit).unwrap_or(DEFAULT_FETCH_LIMIT);
+    if top < 1 {
+        return ctx.invalid("--top must be >= 1");
+    }
+    if fetch_limit < 1 {
+        return ctx.invalid("--fetch-limit must be >= 1");
+    }
+    if !(0.0..1.0).contains(&args.threshold) {
+        return ctx.invalid("--threshold must be between 0 and 1");
+    }
+    if !(0.0..=1.0).contains(&args.min_similarity) {
+        return ctx.invalid("--min-similarity must be between 0 and 1");
+    }
+    let mut venues = ctx.venues(&args.venues, &[Venue::Polymarket, Venue::Kalshi]);
+    let mut seen = HashSet::new();
+    venues.retain(|venue| seen.insert(*venue));
+    if venues.len() < 2 {
+        return ctx.invalid("xarb needs at least two venues to compare");
+    }
+    // Each gap nests a full row per venue, which CSV and HTML rows can't hold.
+    if let Some(code) = ctx.reject_formats("xarb", &[OutputFormat::Csv, OutputFormat::Html]) {
+        return code;
+    }
+
+    let query = EventQuery::new(fetch_limit);
+    let sets = match try_join_all(venues.iter().map(|venue| venue.fetch_markets(&query))).await {
+        Ok(sets) => sets,
+        Err(e) => return ctx.fail("fetch data", &e),
+    };
+    let scanned: usize = sets.iter().map(Vec::len).sum();
+    let mut gaps = find_price_gaps(&sets, args.min_similarity, args.threshold);
+    gaps.truncate(top);
+
+    if !ctx.table() {
+        return emit(ctx, &gaps);
+    }
+
+    let names: Vec<&str> = venues.iter().map(|venue| venue.label()).collect();
+    let title = paint(
+        &format!("Cross-venue arbitrage: {}", names.join(" vs ")),
+        &(String::from(C::BOLD) + C::CYAN),
+        ctx.color,
+    );
+    let summary = paint(
+        &format!("{scanned} markets scanned, gaps of {:.0}+ points", args.threshold * 100.0),
+        C::DIM,
+        ctx.color,
+    );
+    println!("{title}  |  {summary}");
+    if gaps.is_empty() {
+        let none = "No matched markets are priced apart by more than the threshold.";
+        println!("{}", paint(none, C::YELLOW, ctx.color));
+    } else {
+        println!("{}", render_xarb(&gaps, ctx.color));
+    }
+    0
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use poly_core::{Column, SignatureType, SortKey, Venue};
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    pub top: Option<usize>,
+    pub fetch_limit: Option<usize>,
+    // minimal or full, as --fields.
+    pub fields: Option<Fields>,
+    pub interval: Option<u64>,
+    pub color: Option<bool>,
+    pub venues: Option<Vec<Venue>>,
+    pub columns: Option<Vec<Column>>,
+    // local, UTC, or an IANA name.
+    pub tz: Option<String>,
+    pub relative_end: Option<bool>,
+    // Seconds a cached API response stays usable; 0 turns the cache off.
+    pub cache_ttl: Option<u64>,
+    pub retries: Option<u32>,
+    // Seconds, fractions allowed.
+    pub retry_backoff: Option<f64>,
+    // Requests per second; 0 means unlimited.
+    pub rate_limit: Option<f64>,
+    pub proxy: Option<String>,
+    // Polygon JSON-RPC endpoint for on-chain lookups, as --rpc-url.
+    pub rpc_url: Option<String>,
+    // Defaults for `poly order --funder` and `--signature-type`.
+    pub funder: Option<String>,
+    pub signature_type: Option<SignatureType>,
+    // Named setups picked with --profile, as `[profiles.<name>]` tables.
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+// How much `top` and `watch` download per refresh, from --fields or the config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fields {
+    Minimal,
+    #[default]
+    Full,
+}
+
+impl FromStr for Fields {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "minimal" => Ok(Fields::Minimal),
+            "full" => Ok(Fields::Full),
+            other => Err(format!("unknown fields '{other}' (expected minimal or full)")),
+        }
+    }
+}
+
+// A named dashboard setup. Its display keys replace the top-level ones of the same names, and
+// the filter and sort keys stand in for the flags of the same names when those aren't given.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Profile {
+    pub top: Option<usize>,
+    pub fetch_limit: Option<usize>,
+    pub interval: Option<u64>,
+    pub venues: Option<Vec<Venue>>,
+    pub columns: Option<Vec<Column>>,
+    pub tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+    pub min_volume: Option<f64>,
+    pub min_volume_24h: Option<f64>,
+    pub min_liquidity: Option<f64>,
+    pub limit_per_event: Option<usize>,
+    pub price_between: Option<[f64; 2]>,
+    pub sort: Vec<SortKey>,
+    pub asc: bool,
+    pub desc: bool,
+}
+
+impl Profile {
+    // The bounds the matching flags' parsers and checks hold the command line to.
+    fn check(&self) -> Result<(), String> {
+        if self.asc && self.desc {
+            return Err("sets both asc and desc".to_string());
+        }
+        if self.top == Some(0) {
+            return Err("has top = 0 (expected at least 1)".to_string());
+        }
+        if self.fetch_limit == Some(0) {
+            return Err("has fetch-limit = 0 (expected at least 1)".to_string());
+        }
+        if self.limit_per_event == Some(0) {
+            return Err("has limit-per-event = 0 (expected at least 1)".to_string());
+        }
+        if self.price_between.is_some_and(|band| band.iter().any(|p| !(0.0..=1.0).contains(p))) {
+            return Err("has a price-between outside 0 to 1".to_string());
+        }
+        if self.interval.is_some_and(|secs| secs < 2) {
+            return Err("has an interval under 2 seconds".to_string());
+        }
+        let minimums = [
+            ("min-volume", self.min_volume),
+            ("min-volume-24h", self.min_volume_24h),
+            ("min-liquidity", self.min_liquidity),
+        ];
+        for (key, value) in minimums {
+            if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+                return Err(format!("has a negative or non-numeric {key}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    // Takes the named profile out of the config and lays its display keys over the top-level
+    // ones; the rest is left for the command line to pick up.
+    pub fn take_profile(&mut self, name: &str) -> Result<Profile, String> {
+        let Some(profile) = self.profiles.remove(name) else {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            return Err(match known.as_slice() {
+                [] => format!("unknown profile '{name}': the config file defines no [profiles]"),
+                _ => format!("unknown profile '{name}' (expected one of: {})", known.join(", ")),
+            });
+        };
+        profile.check().map_err(|e| format!("profile '{name}' {e}"))?;
+        self.top = profile.top.or(self.top);
+        self.fetch_limit = profile.fetch_limit.or(self.fetch_limit);
+        self.interval = profile.interval.or(self.interval);
+        self.venues = profile.venues.clone().or(self.venues.take());
+        self.columns = profile.columns.clone().or(self.columns.take());
+        Ok(profile)
+    }
+}
+
+// Where poly-cli keeps its config and other local state.
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("poly-cli"));
+    }
+    dirs::home_dir().map(|home| home.join(".config").join("poly-cli"))
+}
+
+// Where saved API responses live, separate from config so they can be wiped freely.
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CACHE_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("poly-cli"));
+    }
+    dirs::home_dir().map(|home| home.join(".cache").join("poly-cli"))
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+// An explicit path must exist; the default path is optional and silently skipped when absent.
+pub fn load(explicit: Option<&Path>) -> Result<Config, String> {
+    let path = match explicit {
+        Some(p) => p.to_path_buf(),
+        None => match default_path() {
+            Some(p) if p.exists() => p,
+            _ => return Ok(Config::default()),
+        },
+    };
+
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read config {}: {e}", path.display()))?;
+    toml::from_str(&text).map_err(|e| format!("invalid config {}: {e}", path.display()))
+}
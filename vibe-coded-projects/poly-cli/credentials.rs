@@ -0,0 +1,86 @@
+use std::env;
+
+use poly_core::{derive_api_key, ApiCreds, ClobAuth, PolyError, Wallet};
+
+// Secrets live in the OS keyring under this service name.
+const KEYRING_SERVICE: &str = "poly-cli";
+const PRIVATE_KEY_ENTRY: &str = "private-key";
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+// API credentials belong to one address, so a changed key never picks up stale ones.
+fn creds_entry(address: &str) -> String {
+    format!("api-creds:{}", address.to_ascii_lowercase())
+}
+
+fn keyring_get(entry: &str) -> Result<Option<String>, PolyError> {
+    let read = keyring::Entry::new(KEYRING_SERVICE, entry).and_then(|entry| entry.get_password());
+    match read {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(PolyError::Auth(format!("failed to read the OS keyring: {e}"))),
+    }
+}
+
+fn keyring_set(entry: &str, secret: &str) -> Result<(), PolyError> {
+    keyring::Entry::new(KEYRING_SERVICE, entry)
+        .and_then(|entry| entry.set_password(secret))
+        .map_err(|e| PolyError::Auth(format!("failed to write the OS keyring: {e}")))
+}
+
+// POLY_PRIVATE_KEY, which takes precedence over the keyring.
+pub fn private_key_from_env() -> Option<String> {
+    env_var("POLY_PRIVATE_KEY")
+}
+
+pub fn stored_private_key() -> Result<Option<String>, PolyError> {
+    keyring_get(PRIVATE_KEY_ENTRY)
+}
+
+pub fn store_private_key(key: &str) -> Result<(), PolyError> {
+    keyring_set(PRIVATE_KEY_ENTRY, key.trim())
+}
+
+fn stored_creds(address: &str) -> Result<Option<ApiCreds>, PolyError> {
+    let Some(json) = keyring_get(&creds_entry(address))? else {
+        return Ok(None);
+    };
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|_| PolyError::Auth("stored API credentials are corrupt; rerun `poly auth create-key`".to_string()))
+}
+
+pub fn store_creds(address: &str, creds: &ApiCreds) -> Result<(), PolyError> {
+    keyring_set(&creds_entry(address), &serde_json::to_string(creds)?)
+}
+
+// The signing key from POLY_PRIVATE_KEY, else the OS keyring.
+pub fn wallet() -> Result<Wallet, PolyError> {
+    let key = match private_key_from_env() {
+        Some(key) => key,
+        None => stored_private_key()?.ok_or_else(|| {
+            PolyError::Auth("no private key: set POLY_PRIVATE_KEY or run `poly auth create-key`".to_string())
+        })?,
+    };
+    Wallet::from_private_key(&key)
+}
+
+// API credentials from POLY_API_KEY, POLY_API_SECRET and POLY_API_PASSPHRASE, else the ones
+// `poly auth create-key` stored, else derived from the wallet, which costs one extra request.
+pub async fn clob_auth(wallet: &Wallet) -> Result<ClobAuth, PolyError> {
+    let from_env = match (env_var("POLY_API_KEY"), env_var("POLY_API_SECRET"), env_var("POLY_API_PASSPHRASE")) {
+        (Some(api_key), Some(secret), Some(passphrase)) => Some(ApiCreds { api_key, secret, passphrase }),
+        _ => None,
+    };
+    let stored = match from_env {
+        Some(creds) => Some(creds),
+        None => stored_creds(&wallet.address())?,
+    };
+    let creds = match stored {
+        Some(creds) => creds,
+        None => derive_api_key(wallet, 0).await?,
+    };
+    Ok(ClobAuth::new(wallet.address(), creds))
+}
@@ -0,0 +1,291 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::Utc;
+use notify_rust::Notification;
+use poly_core::html::escape_html;
+use poly_core::{format_percent, format_price, post_webhook, Alert, Row, Trigger, WebhookEvent};
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+
+// Where fired alerts are delivered: `--notify` targets plus the `--webhook` URL.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotifyTarget {
+    Desktop,
+    Discord(String),
+    Slack(String),
+    Telegram { token: String, chat_id: String },
+    Webhook(String),
+}
+
+impl FromStr for NotifyTarget {
+    type Err = String;
+
+    // `desktop`, or `<service>:<webhook url>`; the URL keeps its case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (kind, url) = s.split_once(':').unwrap_or((s, ""));
+        let url = url.trim().to_string();
+        match (kind.to_ascii_lowercase().as_str(), url.is_empty()) {
+            ("desktop", true) => Ok(NotifyTarget::Desktop),
+            ("discord", false) => Ok(NotifyTarget::Discord(url)),
+            ("slack", false) => Ok(NotifyTarget::Slack(url)),
+            // Bot tokens contain a colon themselves, so the chat id is whatever follows the last one.
+            ("telegram", false) => match url.rsplit_once(':') {
+                Some((token, chat_id)) if !token.is_empty() && !chat_id.is_empty() => {
+                    Ok(NotifyTarget::Telegram { token: token.to_string(), chat_id: chat_id.to_string() })
+                }
+                _ => Err("telegram target must look like telegram:<bot_token>:<chat_id>".to_string()),
+            },
+            ("discord" | "slack", true) => {
+                Err(format!("notify target '{kind}' needs a webhook URL, e.g. {kind}:https://..."))
+            }
+            _ => Err(format!(
+                "unknown notify target '{s}' (expected desktop, discord:<webhook>, slack:<webhook>, \
+                 telegram:<bot_token>:<chat_id>)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for NotifyTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyTarget::Desktop => f.write_str("desktop"),
+            NotifyTarget::Discord(_) => f.write_str("discord"),
+            NotifyTarget::Slack(_) => f.write_str("slack"),
+            NotifyTarget::Telegram { .. } => f.write_str("telegram"),
+            NotifyTarget::Webhook(_) => f.write_str("webhook"),
+        }
+    }
+}
+
+// One line on what happened, with the rule or outcome passed through `quote` for the target's markup.
+fn describe(alert: &Alert, quote: impl Fn(&str) -> String) -> String {
+    match &alert.trigger {
+        Trigger::Rule { rule, .. } => format!("Alert {} fired at {}", quote(&rule.to_string()), alert.reading()),
+        Trigger::Resolved { resolved } => format!("Resolved {}", quote(resolved)),
+    }
+}
+
+fn show_desktop(alert: &Alert) -> Result<(), String> {
+    let summary = match alert.trigger {
+        Trigger::Rule { .. } => format!("Polymarket alert: {}", alert.label()),
+        Trigger::Resolved { .. } => "Polymarket market resolved".to_string(),
+    };
+    Notification::new()
+        .appname("poly")
+        .summary(&summary)
+        .body(&format!("{}\n{}", alert.row.title, alert.reading()))
+        .show()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn discord_embed(alert: &Alert) -> Value {
+    let row = &alert.row;
+    let color = match alert.trigger {
+        Trigger::Resolved { .. } => 0x3498DB,
+        Trigger::Rule { .. } if row.change_24h_pct.unwrap_or(0.0) >= 0.0 => 0x2ECC71,
+        Trigger::Rule { .. } => 0xE74C3C,
+    };
+    json!({
+        "username": "poly",
+        "embeds": [{
+            "title": row.title,
+            "url": row.url(),
+            "description": describe(alert, |text| format!("`{text}`")),
+            "color": color,
+            "fields": [
+                { "name": "Price", "value": format_price(row.price), "inline": true },
+                { "name": "24h Change", "value": format_percent(row.change_24h_pct), "inline": true },
+                { "name": "Event", "value": row.event, "inline": false },
+            ],
+        }],
+    })
+}
+
+// Slack reads &, < and > as control characters in mrkdwn, so they go in as entities.
+fn escape_mrkdwn(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn slack_blocks(alert: &Alert) -> Value {
+    let row = &alert.row;
+    let title = match row.url() {
+        Some(link) => format!("<{link}|{}>", escape_mrkdwn(&row.title)),
+        None => escape_mrkdwn(&row.title),
+    };
+    json!({
+        "text": escape_mrkdwn(&format!("Polymarket alert: {alert}")),
+        "blocks": [
+            {
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("*{title}*\n{}", describe(alert, |text| format!("`{}`", escape_mrkdwn(text)))),
+                },
+            },
+            {
+                "type": "section",
+                "fields": [
+                    { "type": "mrkdwn", "text": format!("*Price*\n{}", format_price(row.price)) },
+                    { "type": "mrkdwn", "text": format!("*24h Change*\n{}", format_percent(row.change_24h_pct)) },
+                ],
+            },
+        ],
+    })
+}
+
+fn telegram_message(chat_id: &str, alert: &Alert) -> Value {
+    let row = &alert.row;
+    let title = match row.url() {
+        Some(link) => format!("<a href=\"{link}\">{}</a>", escape_html(&row.title)),
+        None => format!("<b>{}</b>", escape_html(&row.title)),
+    };
+    let text = format!(
+        "{title}\n{}\nPrice {} · 24h {}",
+        describe(alert, |text| format!("<code>{}</code>", escape_html(text))),
+        format_price(row.price),
+        format_percent(row.change_24h_pct),
+    );
+    json!({
+        "chat_id": chat_id,
+        "text": text,
+        "parse_mode": "HTML",
+        "disable_web_page_preview": true,
+    })
+}
+
+impl NotifyTarget {
+    // Every target except the desktop boils down to POSTing JSON somewhere; this is the URL and
+    // the body it expects for one alert.
+    fn alert_post(&self, alert: &Alert) -> Option<(String, Value)> {
+        match self {
+            NotifyTarget::Desktop => None,
+            NotifyTarget::Discord(url) => Some((url.clone(), discord_embed(alert))),
+            NotifyTarget::Slack(url) => Some((url.clone(), slack_blocks(alert))),
+            NotifyTarget::Telegram { token, chat_id } => Some((
+                format!("https://api.telegram.org/bot{token}/sendMessage"),
+                telegram_message(chat_id, alert),
+            )),
+            NotifyTarget::Webhook(url) => {
+                Some((url.clone(), json!(WebhookEvent::Alert { timestamp: Utc::now(), alert })))
+            }
+        }
+    }
+}
+
+// Delivers alerts to every target. Failures are returned rather than printed because the TUI
+// owns the terminal and shows them in its status bar instead.
+pub async fn send(targets: &[NotifyTarget], alerts: &[Alert]) -> Vec<String> {
+    let mut failures = Vec::new();
+    for target in targets {
+        for alert in alerts {
+            let result = match target.alert_post(alert) {
+                Some((url, body)) => post_webhook(&url, &body).await.map_err(|e| e.to_string()),
+                None => {
+                    // The desktop notification APIs block on a bus round trip.
+                    let alert = alert.clone();
+                    tokio::task::spawn_blocking(move || show_desktop(&alert))
+                        .await
+                        .unwrap_or_else(|e| Err(e.to_string()))
+                }
+            };
+            if let Err(e) = result {
+                failures.push(format!("{target} notification failed: {e}"));
+            }
+        }
+    }
+    failures
+}
+
+// `snapshot` of `rows` (when given) and then `send`, on a task of their own, so a slow target
+// and its retries never hold up the refresh loop. The handle yields the failures; a one-shot
+// command must await it before exiting or the posts are dropped.
+pub fn deliver(targets: &[NotifyTarget], rows: Option<&[Row]>, alerts: &[Alert]) -> JoinHandle<Vec<String>> {
+    let snapshots = targets.iter().any(|t| matches!(t, NotifyTarget::Webhook(_)));
+    let rows = rows.filter(|_| snapshots).map(<[Row]>::to_vec);
+    let (targets, alerts) = (targets.to_vec(), alerts.to_vec());
+    tokio::spawn(async move {
+        let mut failures = match &rows {
+            Some(rows) => snapshot(&targets, rows).await,
+            None => Vec::new(),
+        };
+        failures.extend(send(&targets, &alerts).await);
+        failures
+    })
+}
+
+// `deliver`, printing the failures to stderr when they come in.
+pub fn deliver_reported(targets: &[NotifyTarget], rows: Option<&[Row]>, alerts: &[Alert]) -> JoinHandle<()> {
+    let delivery = deliver(targets, rows, alerts);
+    tokio::spawn(async move {
+        for failure in delivery.await.unwrap_or_default() {
+            eprintln!("{failure}");
+        }
+    })
+}
+
+// Pushes the full refreshed table to targets that take snapshots (only webhooks do).
+pub async fn snapshot(targets: &[NotifyTarget], rows: &[Row]) -> Vec<String> {
+    let mut failures = Vec::new();
+    for target in targets {
+        if let NotifyTarget::Webhook(url) = target {
+            let event = WebhookEvent::Snapshot { timestamp: Utc::now(), rows };
+            if let Err(e) = post_webhook(url, &event).await {
+                failures.push(format!("{target} snapshot failed: {e}"));
+            }
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use poly_core::Venue;
+
+    use super::*;
+
+    fn resolved_alert() -> Alert {
+        let row = Row {
+            event: "Q&A".to_string(),
+            event_slug: None,
+            title: "Will <b> & <i> ship?".to_string(),
+            slug: None,
+            token_id: None,
+            price: Some(1.0),
+            volume: 0.0,
+            volume_24h: 0.0,
+            change_24h_pct: None,
+            liquidity: None,
+            end_date: None,
+            resolution: None,
+            best_bid: None,
+            best_ask: None,
+            spread: None,
+            midpoint: None,
+            momentum: None,
+            spike: None,
+            venue: Venue::Polymarket,
+            outcome_prices: Vec::new(),
+            trend: Vec::new(),
+        };
+        Alert { trigger: Trigger::Resolved { resolved: "<Yes & No>".to_string() }, row }
+    }
+
+    #[test]
+    fn discord_text_is_left_as_written() {
+        let embed = &discord_embed(&resolved_alert())["embeds"][0];
+        assert_eq!(embed["title"], "Will <b> & <i> ship?");
+        assert_eq!(embed["description"], "Resolved `<Yes & No>`");
+    }
+
+    #[test]
+    fn slack_mrkdwn_escapes_control_characters() {
+        let blocks = slack_blocks(&resolved_alert());
+        let text = blocks["blocks"][0]["text"]["text"].as_str().unwrap();
+        assert_eq!(text, "*Will &lt;b&gt; &amp; &lt;i&gt; ship?*\nResolved `&lt;Yes &amp; No&gt;`");
+        assert!(!blocks["text"].as_str().unwrap().contains('<'));
+    }
+}
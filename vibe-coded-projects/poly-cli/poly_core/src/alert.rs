@@ -0,0 +1,267 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Serialize, Serializer};
+
+use crate::format::{format_money, format_percent, format_price};
+use crate::model::Row;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertField {
+    Price,
+    Volume,
+    Volume24h,
+    Change24h,
+    Liquidity,
+}
+
+impl AlertField {
+    pub fn value(self, row: &Row) -> Option<f64> {
+        match self {
+            AlertField::Price => row.price,
+            AlertField::Volume => Some(row.volume),
+            AlertField::Volume24h => Some(row.volume_24h),
+            AlertField::Change24h => row.change_24h_pct,
+            AlertField::Liquidity => row.liquidity,
+        }
+    }
+
+    pub fn format(self, value: f64) -> String {
+        match self {
+            AlertField::Price => format_price(Some(value)),
+            AlertField::Change24h => format_percent(Some(value)),
+            AlertField::Volume | AlertField::Volume24h | AlertField::Liquidity => format_money(value),
+        }
+    }
+}
+
+impl FromStr for AlertField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "price" => Ok(AlertField::Price),
+            "volume" => Ok(AlertField::Volume),
+            "volume24h" => Ok(AlertField::Volume24h),
+            "change" | "change24h" => Ok(AlertField::Change24h),
+            "liquidity" => Ok(AlertField::Liquidity),
+            other => Err(format!(
+                "unknown alert field '{other}' (expected price, volume, volume24h, change24h, liquidity)"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl CompareOp {
+    // Longest operators first so ">=" isn't read as ">" followed by "=5".
+    const ALL: [(&'static str, CompareOp); 6] = [
+        (">=", CompareOp::Ge),
+        ("<=", CompareOp::Le),
+        ("==", CompareOp::Eq),
+        ("!=", CompareOp::Ne),
+        (">", CompareOp::Gt),
+        ("<", CompareOp::Lt),
+    ];
+
+    pub fn apply(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            CompareOp::Gt => lhs > rhs,
+            CompareOp::Ge => lhs >= rhs,
+            CompareOp::Lt => lhs < rhs,
+            CompareOp::Le => lhs <= rhs,
+            CompareOp::Eq => lhs == rhs,
+            CompareOp::Ne => lhs != rhs,
+        }
+    }
+
+    // Splits `<lhs> <op> <rhs>` at its first operator.
+    pub(crate) fn split(s: &str) -> Option<(&str, CompareOp, &str)> {
+        let (at, symbol, op) = CompareOp::ALL
+            .iter()
+            .filter_map(|&(symbol, op)| s.find(symbol).map(|at| (at, symbol, op)))
+            .min_by_key(|&(at, symbol, _)| (at, std::cmp::Reverse(symbol.len())))?;
+        Some((&s[..at], op, &s[at + symbol.len()..]))
+    }
+}
+
+// Thresholds accept `_` separators, a trailing `%`, and k/m/b suffixes, e.g. `1.5m` or `250_000`.
+pub(crate) fn parse_threshold(text: &str) -> Result<f64, String> {
+    let cleaned = text.trim().trim_end_matches('%').replace('_', "");
+    let (number, scale) = match cleaned.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => (&cleaned[..cleaned.len() - 1], 1_000.0),
+        Some('m') => (&cleaned[..cleaned.len() - 1], 1_000_000.0),
+        Some('b') => (&cleaned[..cleaned.len() - 1], 1_000_000_000.0),
+        _ => (cleaned.as_str(), 1.0),
+    };
+    number
+        .parse::<f64>()
+        .map(|n| n * scale)
+        .map_err(|_| format!("invalid threshold '{}'", text.trim()))
+}
+
+// One `--alert` expression: `<field> <op> <number>`, e.g. `change24h > 5`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    pub field: AlertField,
+    pub op: CompareOp,
+    pub threshold: f64,
+    source: String,
+}
+
+impl AlertRule {
+    // The row's value for this rule's field when it satisfies the rule.
+    pub fn check(&self, row: &Row) -> Option<f64> {
+        self.field.value(row).filter(|&value| self.op.apply(value, self.threshold))
+    }
+}
+
+impl FromStr for AlertRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (field, op, threshold) =
+            CompareOp::split(s).ok_or_else(|| format!("alert '{s}' needs a comparison (>, >=, <, <=, ==, !=)"))?;
+
+        Ok(AlertRule {
+            field: field.parse()?,
+            op,
+            threshold: parse_threshold(threshold)?,
+            source: s.split_whitespace().collect::<Vec<_>>().join(" "),
+        })
+    }
+}
+
+impl fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for AlertRule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+// What set an alert off. Serialized flat into the alert, so rule alerts keep their `rule` and
+// `value` fields and resolutions carry a `resolved` outcome instead.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum Trigger {
+    Rule { rule: AlertRule, value: f64 },
+    // The market settled on this outcome since the previous refresh.
+    Resolved { resolved: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    #[serde(flatten)]
+    pub trigger: Trigger,
+    pub row: Row,
+}
+
+impl Alert {
+    // The rule that fired, or `resolved`.
+    pub fn label(&self) -> String {
+        match &self.trigger {
+            Trigger::Rule { rule, .. } => rule.to_string(),
+            Trigger::Resolved { .. } => "resolved".to_string(),
+        }
+    }
+
+    // The value the rule matched at, or the winning outcome.
+    pub fn reading(&self) -> String {
+        match &self.trigger {
+            Trigger::Rule { rule, value } => match rule.field {
+                AlertField::Volume | AlertField::Volume24h | AlertField::Liquidity => {
+                    self.row.venue.format_money(*value)
+                }
+                field => field.format(*value),
+            },
+            Trigger::Resolved { resolved } => resolved.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {} ({})", self.label(), self.row.title, self.reading())
+    }
+}
+
+fn market_key(row: &Row) -> &String {
+    row.token_id.as_ref().or(row.slug.as_ref()).unwrap_or(&row.title)
+}
+
+// Evaluates rules against each refresh and only reports a market when it newly crosses a rule,
+// so a condition that stays true doesn't re-fire on every refresh.
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    active: HashSet<(usize, String)>,
+    // Markets last seen still open, when resolutions are tracked.
+    open: Option<HashSet<String>>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        AlertEngine { rules, active: HashSet::new(), open: None }
+    }
+
+    // Also alert when a market that was open on the previous refresh comes back resolved. A
+    // market that's already resolved the first time it's seen stays quiet.
+    pub fn track_resolutions(mut self) -> Self {
+        self.open = Some(HashSet::new());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.open.is_none()
+    }
+
+    pub fn evaluate(&mut self, rows: &[Row]) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let mut active = HashSet::new();
+
+        for (idx, rule) in self.rules.iter().enumerate() {
+            for row in rows {
+                let Some(value) = rule.check(row) else {
+                    continue;
+                };
+                let key = (idx, market_key(row).clone());
+                if !self.active.contains(&key) {
+                    alerts.push(Alert { trigger: Trigger::Rule { rule: rule.clone(), value }, row: row.clone() });
+                }
+                active.insert(key);
+            }
+        }
+        self.active = active;
+
+        if let Some(open) = &mut self.open {
+            for row in rows {
+                match &row.resolution {
+                    Some(outcome) if open.remove(market_key(row)) => alerts.push(Alert {
+                        trigger: Trigger::Resolved { resolved: outcome.clone() },
+                        row: row.clone(),
+                    }),
+                    Some(_) => {}
+                    None => {
+                        open.insert(market_key(row).clone());
+                    }
+                }
+            }
+        }
+
+        alerts
+    }
+}
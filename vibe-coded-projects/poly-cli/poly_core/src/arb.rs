@@ -0,0 +1,56 @@
+use serde::Serialize;
+
+use crate::model::Row;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArbSide {
+    // Outcomes sum below $1: buy one of each and collect $1 at resolution.
+    Buy,
+    // Outcomes sum above $1: sell one of each and pay out $1 at resolution.
+    Sell,
+}
+
+// A live market whose outcome prices don't sum to $1 by more than the fee allowance.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mispricing {
+    pub event: String,
+    pub title: String,
+    pub slug: Option<String>,
+    pub outcome_prices: Vec<f64>,
+    pub sum: f64,
+    // What's left of the gap to $1 after fees, per complete set of outcomes.
+    pub edge: f64,
+    pub side: ArbSide,
+    #[serde(rename = "volume24h")]
+    pub volume_24h: f64,
+    pub liquidity: Option<f64>,
+}
+
+// Markets whose outcome prices sum further from 1.0 than `fee`, widest edge first. Gamma's
+// outcome prices are marks rather than executable quotes, so treat these as leads to check on
+// the book, not guaranteed fills.
+pub fn find_mispricings(rows: &[Row], fee: f64) -> Vec<Mispricing> {
+    let mut found: Vec<Mispricing> = rows
+        .iter()
+        .filter(|row| row.resolution.is_none() && row.outcome_prices.len() >= 2)
+        .filter_map(|row| {
+            let sum: f64 = row.outcome_prices.iter().sum();
+            let edge = (sum - 1.0).abs() - fee;
+            (edge > 0.0).then(|| Mispricing {
+                event: row.event.clone(),
+                title: row.title.clone(),
+                slug: row.slug.clone(),
+                outcome_prices: row.outcome_prices.clone(),
+                sum,
+                edge,
+                side: if sum < 1.0 { ArbSide::Buy } else { ArbSide::Sell },
+                volume_24h: row.volume_24h,
+                liquidity: row.liquidity,
+            })
+        })
+        .collect();
+    found.sort_by(|a, b| b.edge.total_cmp(&a.edge));
+    found
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::URL_SAFE;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::clob::CLOB_URL;
+use crate::error::PolyError;
+use crate::http::send_json;
+use crate::wallet::{domain_separator, hash_struct, word_address, word_string, word_uint, Wallet};
+
+// The fixed statement a wallet signs to prove it controls its address.
+const CLOB_AUTH_MESSAGE: &str = "This message attests that I control the given wallet";
+
+// CLOB API credentials, as /auth/api-key hands them out.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCreds {
+    pub api_key: String,
+    pub secret: String,
+    pub passphrase: String,
+}
+
+// Only the key id is safe to show.
+impl fmt::Debug for ApiCreds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiCreds").field("api_key", &self.api_key).finish_non_exhaustive()
+    }
+}
+
+fn timestamp() -> String {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()).to_string()
+}
+
+// Level 1 auth: a fresh EIP-712 signature from the wallet itself. Only the key endpoints take it.
+fn l1_headers(wallet: &Wallet, nonce: u64) -> Result<Vec<(&'static str, String)>, PolyError> {
+    let timestamp = timestamp();
+    let domain = domain_separator("ClobAuthDomain", "1", None);
+    let hash = hash_struct(
+        "ClobAuth(address address,string timestamp,uint256 nonce,string message)",
+        &[
+            word_address(&wallet.address_bytes()),
+            word_string(&timestamp),
+            word_uint(nonce.into()),
+            word_string(CLOB_AUTH_MESSAGE),
+        ],
+    );
+    Ok(vec![
+        ("POLY_ADDRESS", wallet.address()),
+        ("POLY_SIGNATURE", wallet.sign_typed_data(&domain, &hash)?),
+        ("POLY_TIMESTAMP", timestamp),
+        ("POLY_NONCE", nonce.to_string()),
+    ])
+}
+
+fn creds_from(payload: Value) -> Result<ApiCreds, PolyError> {
+    serde_json::from_value(payload).map_err(|_| PolyError::Shape("api key response without apiKey/secret/passphrase"))
+}
+
+// Issues a new set of API credentials for the wallet. Fails if `nonce` was already used, in
+// which case `derive_api_key` recovers the existing set.
+pub async fn create_api_key(wallet: &Wallet, nonce: u64) -> Result<ApiCreds, PolyError> {
+    let headers = l1_headers(wallet, nonce)?;
+    creds_from(send_json(Method::POST, &format!("{CLOB_URL}/auth/api-key"), &[], &headers, None).await?)
+}
+
+// The credentials created earlier for the wallet and `nonce`.
+pub async fn derive_api_key(wallet: &Wallet, nonce: u64) -> Result<ApiCreds, PolyError> {
+    let headers = l1_headers(wallet, nonce)?;
+    creds_from(send_json(Method::GET, &format!("{CLOB_URL}/auth/derive-api-key"), &[], &headers, None).await?)
+}
+
+// Level 2 auth: API credentials for a wallet address, which HMAC-sign every trading request.
+#[derive(Debug, Clone)]
+pub struct ClobAuth {
+    pub address: String,
+    pub creds: ApiCreds,
+}
+
+impl ClobAuth {
+    pub fn new(address: String, creds: ApiCreds) -> Self {
+        ClobAuth { address, creds }
+    }
+
+    fn headers(
+        &self,
+        method: &Method,
+        path: &str,
+        body: Option<&str>,
+    ) -> Result<Vec<(&'static str, String)>, PolyError> {
+        let timestamp = timestamp();
+        let secret = URL_SAFE
+            .decode(&self.creds.secret)
+            .map_err(|_| PolyError::Auth("API secret is not base64".to_string()))?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(&secret)
+            .map_err(|_| PolyError::Auth("API secret is unusable".to_string()))?;
+        mac.update(format!("{timestamp}{method}{path}{}", body.unwrap_or_default()).as_bytes());
+        Ok(vec![
+            ("POLY_ADDRESS", self.address.clone()),
+            ("POLY_SIGNATURE", URL_SAFE.encode(mac.finalize().into_bytes())),
+            ("POLY_TIMESTAMP", timestamp),
+            ("POLY_API_KEY", self.creds.api_key.clone()),
+            ("POLY_PASSPHRASE", self.creds.passphrase.clone()),
+        ])
+    }
+
+    // A signed request to a CLOB path such as `/order`. The signature covers the path without
+    // its query string.
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<&Value>,
+    ) -> Result<Value, PolyError> {
+        let body = body.map(serde_json::to_string).transpose()?;
+        let headers = self.headers(&method, path, body.as_deref())?;
+        send_json(method, &format!("{CLOB_URL}{path}"), query, &headers, body).await
+    }
+}
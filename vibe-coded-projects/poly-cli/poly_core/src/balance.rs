@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+use crate::error::PolyError;
+use crate::rpc::{calldata, scaled, word, JsonRpc};
+use crate::trading::{CTF_EXCHANGE, NEG_RISK_CTF_EXCHANGE};
+use crate::wallet::{checksum, parse_address, word_address};
+
+// Bridged USDC (USDC.e), the collateral every Polymarket market settles in.
+pub const USDC: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+// Gnosis Conditional Tokens, the ERC-1155 contract holding outcome shares.
+pub const CONDITIONAL_TOKENS: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045";
+const NEG_RISK_ADAPTER: &str = "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296";
+
+const USDC_DECIMALS: i32 = 6;
+const POL_DECIMALS: i32 = 18;
+
+// The contracts trading needs approved: each must be able to move USDC to buy and outcome
+// shares to sell.
+const SPENDERS: [(&str, &str); 3] = [
+    ("CTF Exchange", CTF_EXCHANGE),
+    ("Neg Risk CTF Exchange", NEG_RISK_CTF_EXCHANGE),
+    ("Neg Risk Adapter", NEG_RISK_ADAPTER),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Approval {
+    pub spender: &'static str,
+    pub contract: String,
+    // USDC the contract may spend; None when the approval is unlimited.
+    pub usdc_allowance: Option<f64>,
+    // Whether it may move the wallet's outcome shares.
+    pub shares_approved: bool,
+}
+
+impl Approval {
+    pub fn usdc_approved(&self) -> bool {
+        self.usdc_allowance.is_none_or(|allowance| allowance > 0.0)
+    }
+}
+
+// What a wallet holds for trading and what it has approved.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Funding {
+    pub address: String,
+    pub usdc: f64,
+    // Gas for approvals and transfers; trading itself is gasless.
+    pub pol: f64,
+    pub approvals: Vec<Approval>,
+}
+
+impl Funding {
+    // Ready to buy and sell through every exchange.
+    pub fn ready(&self) -> bool {
+        self.approvals.iter().all(|a| a.usdc_approved() && a.shares_approved)
+    }
+}
+
+pub async fn fetch_funding(rpc: &JsonRpc, address: &str) -> Result<Funding, PolyError> {
+    let owner = parse_address(address)?;
+    let balance = rpc.call(USDC, &calldata("balanceOf(address)", &[word_address(&owner)])).await?;
+    let usdc = scaled(&word(&balance, 0)?, USDC_DECIMALS).ok_or(PolyError::Shape("USDC balance out of range"))?;
+    let pol = scaled(&rpc.native_balance(address).await?, POL_DECIMALS).unwrap_or(f64::MAX);
+
+    let mut approvals = Vec::with_capacity(SPENDERS.len());
+    for (name, contract) in SPENDERS {
+        let pair = [word_address(&owner), word_address(&parse_address(contract)?)];
+        let allowance = rpc.call(USDC, &calldata("allowance(address,address)", &pair)).await?;
+        let approved = rpc.call(CONDITIONAL_TOKENS, &calldata("isApprovedForAll(address,address)", &pair)).await?;
+        approvals.push(Approval {
+            spender: name,
+            contract: contract.to_string(),
+            usdc_allowance: scaled(&word(&allowance, 0)?, USDC_DECIMALS),
+            shares_approved: word(&approved, 0)?[31] == 1,
+        });
+    }
+
+    Ok(Funding { address: checksum(&owner), usdc, pol, approvals })
+}
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once, OnceLock, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::tape::replay_key;
+
+// One cached GET response, stored as `<hash of request>.json`; the file's mtime is when it was fetched.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    url: String,
+    payload: Value,
+}
+
+enum Freshness {
+    // Entries from earlier runs younger than `ttl`.
+    Recent { ttl: Duration, started: SystemTime },
+    // Any entry, however old; nothing goes to the network.
+    Offline { oldest_served: Mutex<Option<SystemTime>> },
+}
+
+struct Cache {
+    dir: PathBuf,
+    freshness: Freshness,
+}
+
+// Set at most once, before the first request, by `enable` or `offline`.
+static CACHE: OnceLock<Cache> = OnceLock::new();
+// The directory is pruned once per process, on its first write.
+static PRUNED: Once = Once::new();
+
+// Past this age even the last response to a request goes, though `--offline` could still use it.
+const OFFLINE_RETENTION: Duration = Duration::from_secs(7 * 24 * 3600);
+
+// Answers GET requests from responses saved in `dir` by earlier runs, as long as they're younger
+// than `ttl`. Responses fetched by this process are saved but never served back to it, so watch
+// mode and the server keep seeing fresh data after their first refresh.
+pub fn enable(dir: &Path, ttl: Duration) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    install(Cache { dir: dir.to_path_buf(), freshness: Freshness::Recent { ttl, started: SystemTime::now() } })
+}
+
+// Answers every GET request from `dir` whatever its age, failing requests that were never cached
+// instead of going to the network.
+pub fn offline(dir: &Path) -> io::Result<()> {
+    if !dir.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "nothing has been cached yet"));
+    }
+    install(Cache { dir: dir.to_path_buf(), freshness: Freshness::Offline { oldest_served: Mutex::new(None) } })
+}
+
+fn install(cache: Cache) -> io::Result<()> {
+    CACHE
+        .set(cache)
+        .map_err(|_| io::Error::new(io::ErrorKind::AlreadyExists, "the response cache is already set up"))
+}
+
+pub fn is_offline() -> bool {
+    matches!(CACHE.get(), Some(Cache { freshness: Freshness::Offline { .. }, .. }))
+}
+
+// In offline mode, when the oldest response served so far was fetched.
+pub fn served_since() -> Option<SystemTime> {
+    match CACHE.get() {
+        Some(Cache { freshness: Freshness::Offline { oldest_served }, .. }) => {
+            *oldest_served.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+        _ => None,
+    }
+}
+
+// Keyed on the full URL, clock parameters included, so two price-history windows never share an
+// entry. FNV-1a, which unlike the std hasher is stable across builds, so cache files outlive upgrades.
+fn file_name(url: &Url) -> String {
+    let hash = url
+        .as_str()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    format!("{hash:016x}.json")
+}
+
+fn read_entry(path: &Path) -> Option<(SystemTime, Entry)> {
+    let fetched = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    let entry = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    Some((fetched, entry))
+}
+
+// Offline, a request whose clock parameters moved on since it was cached still gets the latest
+// response to the same request otherwise, the way a replayed tape matches it.
+fn lookup_replay(dir: &Path, url: &Url) -> Option<(SystemTime, Entry)> {
+    let key = replay_key(url);
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|item| read_entry(&item.ok()?.path()))
+        .filter(|(_, entry)| Url::parse(&entry.url).is_ok_and(|cached| replay_key(&cached) == key))
+        .max_by_key(|(fetched, _)| *fetched)
+}
+
+// A cached payload for the request, or None on a miss. Unreadable entries count as misses.
+pub(crate) fn lookup(url: &Url) -> Option<Value> {
+    let cache = CACHE.get()?;
+    // Different requests can share a file name if their hashes collide.
+    let exact = read_entry(&cache.dir.join(file_name(url))).filter(|(_, entry)| entry.url == url.as_str());
+    match &cache.freshness {
+        Freshness::Recent { ttl, started } => {
+            let (fetched, entry) = exact?;
+            if fetched >= *started || fetched.elapsed().ok()? >= *ttl {
+                return None;
+            }
+            Some(entry.payload)
+        }
+        Freshness::Offline { oldest_served } => {
+            let (fetched, entry) = exact.or_else(|| lookup_replay(&cache.dir, url))?;
+            let mut oldest = oldest_served.lock().unwrap_or_else(PoisonError::into_inner);
+            *oldest = Some(oldest.map_or(fetched, |oldest| oldest.min(fetched)));
+            Some(entry.payload)
+        }
+    }
+}
+
+fn older_than(at: SystemTime, age: Duration) -> bool {
+    at.elapsed().is_ok_and(|elapsed| elapsed >= age)
+}
+
+// Drops entries older than `ttl`, except the newest response to each request (clock parameters
+// aside), which `--offline` falls back on until it's a week old.
+fn prune(dir: &Path, ttl: Duration) {
+    let Ok(items) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut stale = Vec::new();
+    let mut newest: HashMap<String, (SystemTime, PathBuf)> = HashMap::new();
+    for path in items.filter_map(|item| Some(item.ok()?.path())) {
+        let Some((fetched, entry)) = read_entry(&path) else {
+            // Unreadable files never serve anything.
+            if fs::metadata(&path).and_then(|meta| meta.modified()).is_ok_and(|at| older_than(at, ttl)) {
+                stale.push(path);
+            }
+            continue;
+        };
+        if older_than(fetched, OFFLINE_RETENTION) {
+            stale.push(path);
+            continue;
+        }
+        let key = Url::parse(&entry.url).map_or(entry.url, |url| replay_key(&url));
+        let older = match newest.get(&key) {
+            Some((kept, _)) if *kept >= fetched => Some((fetched, path)),
+            _ => newest.insert(key, (fetched, path)),
+        };
+        if let Some((at, path)) = older {
+            if older_than(at, ttl) {
+                stale.push(path);
+            }
+        }
+    }
+    for path in stale {
+        let _ = fs::remove_file(path);
+    }
+}
+
+// Saving is best-effort: a cache that can't be written just means the next run refetches.
+pub(crate) fn store(url: &Url, payload: &Value) {
+    let Some(cache) = CACHE.get() else {
+        return;
+    };
+    if let Freshness::Recent { ttl, .. } = cache.freshness {
+        PRUNED.call_once(|| prune(&cache.dir, ttl));
+    }
+    let entry = Entry { url: url.to_string(), payload: payload.clone() };
+    if let Ok(text) = serde_json::to_string(&entry) {
+        let _ = fs::write(cache.dir.join(file_name(url)), text);
+    }
+}
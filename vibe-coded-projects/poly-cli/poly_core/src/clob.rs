@@ -0,0 +1,103 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::PolyError;
+use crate::http::get_json;
+use crate::model::string_f64;
+
+pub const CLOB_URL: &str = "https://clob.polymarket.com";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Level {
+    #[serde(deserialize_with = "string_f64")]
+    pub price: f64,
+    #[serde(deserialize_with = "string_f64")]
+    pub size: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderBook {
+    pub market: Option<String>,
+    pub asset_id: Option<String>,
+    pub timestamp: Option<String>,
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+impl OrderBook {
+    // The CLOB does not guarantee level ordering, so normalise to best-first on both sides.
+    pub fn sort_levels(&mut self) {
+        let by_price = |a: &Level, b: &Level| a.price.partial_cmp(&b.price).unwrap_or(Ordering::Equal);
+        self.bids.sort_by(|a, b| by_price(b, a));
+        self.asks.sort_by(by_price);
+    }
+
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|l| l.price)
+    }
+
+    pub fn midpoint(&self) -> Option<f64> {
+        Some((self.best_bid()? + self.best_ask()?) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()? - self.best_bid()?)
+    }
+}
+
+// One sample from /prices-history: unix seconds and the outcome price at that time.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PricePoint {
+    pub t: i64,
+    pub p: f64,
+}
+
+#[derive(Deserialize)]
+struct PriceHistory {
+    history: Vec<PricePoint>,
+}
+
+// Price samples for a token between two unix timestamps, one every `fidelity_minutes`, oldest first.
+pub async fn fetch_price_history(
+    token_id: &str,
+    start_ts: i64,
+    end_ts: i64,
+    fidelity_minutes: i64,
+) -> Result<Vec<PricePoint>, PolyError> {
+    let payload = get_json(
+        &format!("{CLOB_URL}/prices-history"),
+        &[
+            ("market", token_id.to_string()),
+            ("startTs", start_ts.to_string()),
+            ("endTs", end_ts.to_string()),
+            ("fidelity", fidelity_minutes.max(1).to_string()),
+        ],
+    )
+    .await?;
+
+    if !payload.is_object() {
+        return Err(PolyError::Shape("expected price history object"));
+    }
+
+    let mut history: PriceHistory = serde_json::from_value(payload)?;
+    history.history.sort_by_key(|point| point.t);
+    Ok(history.history)
+}
+
+pub async fn fetch_orderbook(token_id: &str) -> Result<OrderBook, PolyError> {
+    let payload = get_json(&format!("{CLOB_URL}/book"), &[("token_id", token_id.to_string())]).await?;
+
+    if !payload.is_object() {
+        return Err(PolyError::Shape("expected orderbook object"));
+    }
+
+    let mut book: OrderBook = serde_json::from_value(payload)?;
+    book.sort_levels();
+    Ok(book)
+}
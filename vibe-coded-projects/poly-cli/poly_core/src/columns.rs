@@ -0,0 +1,235 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::format::sparkline;
+use crate::model::Row;
+use crate::render::SPARKLINE_WIDTH;
+use crate::venue::Venue;
+
+// One column of market-row output. The table, HTML, and the machine-readable formats all lay
+// rows out from the same list of these, so `--columns` means the same thing everywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Rank,
+    Venue,
+    Title,
+    Event,
+    Slug,
+    Url,
+    Price,
+    Volume,
+    Volume24h,
+    Change,
+    Liquidity,
+    End,
+    Result,
+    Trend,
+    Momentum,
+    Spike,
+    // Best bid and ask together; two fields in the machine-readable formats.
+    Quote,
+    Spread,
+    Mid,
+}
+
+impl Column {
+    // What every row view shows when nothing else is asked for.
+    pub const DEFAULT: &'static [Column] = &[
+        Column::Rank,
+        Column::Title,
+        Column::Price,
+        Column::Volume,
+        Column::Volume24h,
+        Column::Change,
+        Column::Liquidity,
+        Column::End,
+    ];
+
+    pub const ALL: &'static [Column] = &[
+        Column::Rank,
+        Column::Venue,
+        Column::Title,
+        Column::Event,
+        Column::Slug,
+        Column::Url,
+        Column::Price,
+        Column::Volume,
+        Column::Volume24h,
+        Column::Change,
+        Column::Liquidity,
+        Column::End,
+        Column::Result,
+        Column::Trend,
+        Column::Momentum,
+        Column::Spike,
+        Column::Quote,
+        Column::Spread,
+        Column::Mid,
+    ];
+
+    // The default columns plus whichever optional ones the rows have data for.
+    pub fn for_rows(rows: &[Row]) -> Vec<Column> {
+        let mut columns = Column::DEFAULT.to_vec();
+        // The resolution column only appears when closed markets are on screen.
+        if rows.iter().any(|r| r.resolution.is_some()) {
+            columns.push(Column::Result);
+        }
+        // Likewise the trend column only appears once sparkline data has been fetched.
+        if rows.iter().any(|r| !r.trend.is_empty()) {
+            columns.push(Column::Trend);
+        }
+        if rows.iter().any(|r| r.momentum.is_some()) {
+            columns.push(Column::Momentum);
+        }
+        if rows.iter().any(|r| r.spike.is_some()) {
+            columns.push(Column::Spike);
+        }
+        // Rows only carry a venue worth showing once another venue is mixed in.
+        if rows.iter().any(|r| r.venue != Venue::Polymarket) {
+            columns.insert(1, Column::Venue);
+        }
+        // And the quote columns once CLOB books have been fetched.
+        if rows.iter().any(|r| r.best_bid.is_some() || r.best_ask.is_some()) {
+            columns.extend([Column::Quote, Column::Spread, Column::Mid]);
+        }
+        columns
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Column::Rank => "#",
+            Column::Venue => "Venue",
+            Column::Title => "Market",
+            Column::Event => "Event",
+            Column::Slug => "Slug",
+            Column::Url => "URL",
+            Column::Price => "Price",
+            Column::Volume => "Total Volume",
+            Column::Volume24h => "24h Volume",
+            Column::Change => "24h Change",
+            Column::Liquidity => "Liquidity",
+            Column::End => "End",
+            Column::Result => "Result",
+            Column::Trend => "Trend",
+            Column::Momentum => "Mom",
+            Column::Spike => "Spike",
+            Column::Quote => "Bid / Ask",
+            Column::Spread => "Spread",
+            Column::Mid => "Mid",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "rank" | "#" => Ok(Column::Rank),
+            "venue" => Ok(Column::Venue),
+            "title" | "market" => Ok(Column::Title),
+            "event" => Ok(Column::Event),
+            "slug" => Ok(Column::Slug),
+            "url" => Ok(Column::Url),
+            "price" => Ok(Column::Price),
+            "volume" => Ok(Column::Volume),
+            "volume24h" => Ok(Column::Volume24h),
+            "change" => Ok(Column::Change),
+            "liquidity" => Ok(Column::Liquidity),
+            "end" => Ok(Column::End),
+            "result" => Ok(Column::Result),
+            "trend" => Ok(Column::Trend),
+            "momentum" => Ok(Column::Momentum),
+            "spike" => Ok(Column::Spike),
+            "quote" => Ok(Column::Quote),
+            "spread" => Ok(Column::Spread),
+            "mid" => Ok(Column::Mid),
+            other => Err(format!(
+                "unknown column '{other}' (expected {})",
+                Column::ALL.iter().map(Column::to_string).collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Column::Rank => "rank",
+            Column::Venue => "venue",
+            Column::Title => "title",
+            Column::Event => "event",
+            Column::Slug => "slug",
+            Column::Url => "url",
+            Column::Price => "price",
+            Column::Volume => "volume",
+            Column::Volume24h => "volume24h",
+            Column::Change => "change",
+            Column::Liquidity => "liquidity",
+            Column::End => "end",
+            Column::Result => "result",
+            Column::Trend => "trend",
+            Column::Momentum => "momentum",
+            Column::Spike => "spike",
+            Column::Quote => "quote",
+            Column::Spread => "spread",
+            Column::Mid => "mid",
+        };
+        f.write_str(name)
+    }
+}
+
+// A row cut down to the chosen columns, in their order. Field names match `Row`'s own, so a
+// selection is a subset of the full JSON and CSV output plus `rank` and `url`.
+pub struct Selected<'a> {
+    pub rank: usize,
+    pub row: &'a Row,
+    pub columns: &'a [Column],
+}
+
+pub fn select<'a>(rows: &'a [Row], columns: &'a [Column]) -> Vec<Selected<'a>> {
+    rows.iter()
+        .enumerate()
+        .map(|(idx, row)| Selected { rank: idx + 1, row, columns })
+        .collect()
+}
+
+impl Serialize for Selected<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = self.columns.iter().map(|c| if *c == Column::Quote { 2 } else { 1 }).sum();
+        let row = self.row;
+        let mut state = serializer.serialize_struct("Row", fields)?;
+        for column in self.columns {
+            match column {
+                Column::Rank => state.serialize_field("rank", &self.rank)?,
+                Column::Venue => state.serialize_field("venue", &row.venue)?,
+                Column::Title => state.serialize_field("title", &row.title)?,
+                Column::Event => state.serialize_field("event", &row.event)?,
+                Column::Slug => state.serialize_field("slug", &row.slug)?,
+                Column::Url => state.serialize_field("url", &row.url())?,
+                Column::Price => state.serialize_field("price", &row.price)?,
+                Column::Volume => state.serialize_field("volume", &row.volume)?,
+                Column::Volume24h => state.serialize_field("volume24h", &row.volume_24h)?,
+                Column::Change => state.serialize_field("change24hPct", &row.change_24h_pct)?,
+                Column::Liquidity => state.serialize_field("liquidity", &row.liquidity)?,
+                Column::End => state.serialize_field("endDate", &row.end_date)?,
+                Column::Result => state.serialize_field("resolution", &row.resolution)?,
+                // CSV has no room for a list, so the trend goes out as the sparkline it renders as.
+                Column::Trend => state.serialize_field("trend", &sparkline(&row.trend, SPARKLINE_WIDTH))?,
+                Column::Momentum => state.serialize_field("momentum", &row.momentum)?,
+                Column::Spike => state.serialize_field("spike", &row.spike)?,
+                Column::Quote => {
+                    state.serialize_field("bestBid", &row.best_bid)?;
+                    state.serialize_field("bestAsk", &row.best_ask)?;
+                }
+                Column::Spread => state.serialize_field("spread", &row.spread)?,
+                Column::Mid => state.serialize_field("midpoint", &row.midpoint)?,
+            }
+        }
+        state.end()
+    }
+}
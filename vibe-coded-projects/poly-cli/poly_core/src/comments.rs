@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::PolyError;
+use crate::http::get_json;
+use crate::model::lenient_string;
+
+pub const COMMENTS_URL: &str = "https://gamma-api.polymarket.com/comments";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CommentProfile {
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub display_username_public: Option<bool>,
+    pub proxy_wallet: Option<String>,
+}
+
+// A comment on an event's discussion; replies carry their parent's id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Comment {
+    #[serde(deserialize_with = "lenient_string")]
+    pub id: Option<String>,
+    pub body: Option<String>,
+    #[serde(rename = "parentCommentID", deserialize_with = "lenient_string")]
+    pub parent_comment_id: Option<String>,
+    pub user_address: Option<String>,
+    pub created_at: Option<String>,
+    pub reaction_count: Option<u64>,
+    pub profile: Option<CommentProfile>,
+}
+
+impl Comment {
+    // The public username, else the pseudonym, else the wallet address.
+    pub fn author(&self) -> &str {
+        let profile = self.profile.as_ref();
+        profile
+            .and_then(|p| p.name.as_deref().filter(|_| p.display_username_public != Some(false)))
+            .or_else(|| profile.and_then(|p| p.pseudonym.as_deref()))
+            .or(self.user_address.as_deref())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("anonymous")
+    }
+
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(self.created_at.as_deref()?).ok().map(|at| at.with_timezone(&Utc))
+    }
+
+    pub fn is_reply(&self) -> bool {
+        self.parent_comment_id.is_some()
+    }
+}
+
+// `limit` comments on an event (by its numeric gamma id), newest first, after skipping the
+// newest `offset`.
+pub async fn fetch_comments(event_id: &str, limit: usize, offset: usize) -> Result<Vec<Comment>, PolyError> {
+    let payload = get_json(
+        COMMENTS_URL,
+        &[
+            ("parent_entity_type", "Event".to_string()),
+            ("parent_entity_id", event_id.to_string()),
+            ("limit", limit.to_string()),
+            ("offset", offset.to_string()),
+            ("order", "createdAt".to_string()),
+            ("ascending", "false".to_string()),
+        ],
+    )
+    .await?;
+
+    match payload {
+        Value::Array(_) => Ok(serde_json::from_value(payload)?),
+        _ => Err(PolyError::Shape("expected array")),
+    }
+}
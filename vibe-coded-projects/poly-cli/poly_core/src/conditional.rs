@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::Url;
+use serde_json::Value;
+
+// Requests whose validators are kept. Price-history URLs change with the clock, so a long watch
+// session would otherwise keep every window it ever asked for.
+const MAX_ENTRIES: usize = 512;
+
+// The validators a response came with, and the payload a 304 for them stands in for.
+struct Entry {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    payload: Value,
+    stored: Instant,
+}
+
+static ENTRIES: Mutex<Option<HashMap<Url, Entry>>> = Mutex::new(None);
+
+// Makes a repeat request conditional on the validators of the last response to the same URL.
+pub(crate) fn apply(url: &Url, headers: &mut HeaderMap) {
+    let entries = ENTRIES.lock().unwrap_or_else(PoisonError::into_inner);
+    let Some(entry) = entries.as_ref().and_then(|entries| entries.get(url)) else {
+        return;
+    };
+    if let Some(etag) = &entry.etag {
+        headers.insert(IF_NONE_MATCH, etag.clone());
+    }
+    if let Some(last_modified) = &entry.last_modified {
+        headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+    }
+}
+
+// Keeps a response's validators and payload, if it came with any validators.
+pub(crate) fn remember(url: &Url, headers: &HeaderMap, payload: &Value) {
+    let etag = headers.get(ETAG).cloned();
+    let last_modified = headers.get(LAST_MODIFIED).cloned();
+    if etag.is_none() && last_modified.is_none() {
+        return;
+    }
+
+    let mut entries = ENTRIES.lock().unwrap_or_else(PoisonError::into_inner);
+    let entries = entries.get_or_insert_with(HashMap::new);
+    if entries.len() >= MAX_ENTRIES && !entries.contains_key(url) {
+        let oldest = entries.iter().min_by_key(|(_, entry)| entry.stored).map(|(url, _)| url.clone());
+        if let Some(oldest) = oldest {
+            entries.remove(&oldest);
+        }
+    }
+    let entry = Entry { etag, last_modified, payload: payload.clone(), stored: Instant::now() };
+    entries.insert(url.clone(), entry);
+}
+
+// The payload a 304 Not Modified for this URL refers to.
+pub(crate) fn previous(url: &Url) -> Option<Value> {
+    let entries = ENTRIES.lock().unwrap_or_else(PoisonError::into_inner);
+    entries.as_ref()?.get(url).map(|entry| entry.payload.clone())
+}
@@ -0,0 +1,62 @@
+use serde::Serialize;
+
+use crate::clob::PricePoint;
+
+// Two price histories sampled on a shared clock, oldest first.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Aligned {
+    pub t: Vec<i64>,
+    pub a: Vec<f64>,
+    pub b: Vec<f64>,
+}
+
+// Pairs each sample of `a` with the latest sample of `b` at or before it, so histories whose
+// buckets don't line up exactly still compare like with like. Samples of `a` from before `b`
+// starts are dropped. Both inputs must be sorted by time, as `fetch_price_history` returns them.
+pub fn align_histories(a: &[PricePoint], b: &[PricePoint]) -> Aligned {
+    let mut aligned = Aligned::default();
+    let mut j = 0;
+    for point in a {
+        while j + 1 < b.len() && b[j + 1].t <= point.t {
+            j += 1;
+        }
+        match b.get(j) {
+            Some(other) if other.t <= point.t => {
+                aligned.t.push(point.t);
+                aligned.a.push(point.p);
+                aligned.b.push(other.p);
+            }
+            _ => {}
+        }
+    }
+    aligned
+}
+
+// Pearson correlation coefficient, or None with fewer than two samples or a flat series.
+pub fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let n = xs.len().min(ys.len());
+    if n < 2 {
+        return None;
+    }
+    let (xs, ys) = (&xs[..n], &ys[..n]);
+    let mean_x = xs.iter().sum::<f64>() / n as f64;
+    let mean_y = ys.iter().sum::<f64>() / n as f64;
+
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some(cov / (var_x * var_y).sqrt())
+}
+
+// Sample-to-sample changes, which strip out the shared drift that makes most price levels
+// look correlated.
+pub fn differences(values: &[f64]) -> Vec<f64> {
+    values.windows(2).map(|w| w[1] - w[0]).collect()
+}
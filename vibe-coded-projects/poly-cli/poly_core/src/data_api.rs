@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::PolyError;
+use crate::http::get_json;
+use crate::model::lenient_f64;
+
+pub const DATA_API_URL: &str = "https://data-api.polymarket.com";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Trade {
+    pub proxy_wallet: Option<String>,
+    pub side: Option<String>,
+    pub asset: Option<String>,
+    pub condition_id: Option<String>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub size: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub price: Option<f64>,
+    pub timestamp: Option<i64>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub outcome: Option<String>,
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub transaction_hash: Option<String>,
+}
+
+impl Trade {
+    // Identifies a fill across polls; one transaction can fill several outcome tokens.
+    pub fn key(&self) -> (Option<&str>, Option<&str>) {
+        (self.transaction_hash.as_deref(), self.asset.as_deref())
+    }
+
+    pub fn is_buy(&self) -> bool {
+        self.side.as_deref().is_some_and(|s| s.eq_ignore_ascii_case("buy"))
+    }
+}
+
+// An open outcome-token holding for one wallet, as reported by the data API.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Position {
+    pub proxy_wallet: Option<String>,
+    pub asset: Option<String>,
+    pub condition_id: Option<String>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub size: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub avg_price: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub cur_price: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub initial_value: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub current_value: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub cash_pnl: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub percent_pnl: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub realized_pnl: Option<f64>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub outcome: Option<String>,
+    pub end_date: Option<String>,
+    pub redeemable: Option<bool>,
+}
+
+impl Position {
+    // Mark-to-market value at the current price, falling back to the API's own figure.
+    pub fn value(&self) -> f64 {
+        match (self.size, self.cur_price) {
+            (Some(size), Some(price)) => size * price,
+            _ => self.current_value.unwrap_or(0.0),
+        }
+    }
+
+    pub fn cost(&self) -> f64 {
+        match (self.size, self.avg_price) {
+            (Some(size), Some(price)) => size * price,
+            _ => self.initial_value.unwrap_or(0.0),
+        }
+    }
+
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.value() - self.cost()
+    }
+}
+
+// A wallet holding one outcome of a market, as the data API's /holders lists them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Holder {
+    pub proxy_wallet: Option<String>,
+    pub asset: Option<String>,
+    pub outcome_index: Option<usize>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub amount: Option<f64>,
+    pub name: Option<String>,
+    pub pseudonym: Option<String>,
+    pub display_username_public: Option<bool>,
+    // Filled in by the caller: the wallet's ENS name and its share of the outcome's supply. The
+    // supply is approximated by open interest (see `fetch_open_interest`), so it's an estimate.
+    pub ens: Option<String>,
+    pub supply_pct: Option<f64>,
+}
+
+impl Holder {
+    // ENS name, else the public Polymarket username, else the generated pseudonym.
+    pub fn display_name(&self) -> Option<&str> {
+        let username = self.name.as_deref().filter(|_| self.display_username_public != Some(false));
+        self.ens.as_deref().or(username).or(self.pseudonym.as_deref()).filter(|name| !name.is_empty())
+    }
+}
+
+#[derive(Deserialize)]
+struct HolderGroup {
+    #[serde(default)]
+    holders: Vec<Holder>,
+}
+
+// The largest holders of each outcome of a market, up to `limit` per outcome, largest first.
+pub async fn fetch_holders(condition_id: &str, limit: usize) -> Result<Vec<Holder>, PolyError> {
+    let payload = get_json(
+        &format!("{DATA_API_URL}/holders"),
+        &[("market", condition_id.to_string()), ("limit", limit.to_string())],
+    )
+    .await?;
+
+    let groups: Vec<HolderGroup> = match payload {
+        Value::Array(_) => serde_json::from_value(payload)?,
+        _ => return Err(PolyError::Shape("expected array")),
+    };
+    Ok(groups.into_iter().flat_map(|group| group.holders).collect())
+}
+
+// Open interest in USDC. Every share pair is backed by $1, so this approximates how many shares
+// of each outcome exist; the data API's figure isn't an exact count of minted sets.
+pub async fn fetch_open_interest(condition_id: &str) -> Result<Option<f64>, PolyError> {
+    let payload = get_json(&format!("{DATA_API_URL}/oi"), &[("market", condition_id.to_string())]).await?;
+    let value = payload.get(0).and_then(|entry| entry.get("value")).and_then(Value::as_f64);
+    Ok(value)
+}
+
+// Most recent trades first, as returned by the data API.
+pub async fn fetch_trades(condition_id: &str, limit: usize) -> Result<Vec<Trade>, PolyError> {
+    let payload = get_json(
+        &format!("{DATA_API_URL}/trades"),
+        &[("market", condition_id.to_string()), ("limit", limit.to_string())],
+    )
+    .await?;
+
+    match payload {
+        Value::Array(_) => Ok(serde_json::from_value(payload)?),
+        _ => Err(PolyError::Shape("expected array")),
+    }
+}
+
+// Current holdings for a wallet (proxy) address, largest first as returned by the data API.
+pub async fn fetch_positions(user: &str) -> Result<Vec<Position>, PolyError> {
+    let payload = get_json(
+        &format!("{DATA_API_URL}/positions"),
+        &[("user", user.to_string()), ("sizeThreshold", "0.01".to_string()), ("limit", "500".to_string())],
+    )
+    .await?;
+
+    match payload {
+        Value::Array(_) => Ok(serde_json::from_value(payload)?),
+        _ => Err(PolyError::Shape("expected array")),
+    }
+}
+
+// Page size the data API allows for /trades.
+const TRADES_PAGE: usize = 500;
+
+// A wallet's fills, newest first, paging through /trades until `max` trades or the end of history.
+pub async fn fetch_user_trades(user: &str, max: usize) -> Result<Vec<Trade>, PolyError> {
+    let mut trades: Vec<Trade> = Vec::new();
+    while trades.len() < max {
+        let limit = TRADES_PAGE.min(max - trades.len());
+        let payload = get_json(
+            &format!("{DATA_API_URL}/trades"),
+            &[
+                ("user", user.to_string()),
+                ("limit", limit.to_string()),
+                ("offset", trades.len().to_string()),
+                ("takerOnly", "false".to_string()),
+            ],
+        )
+        .await?;
+
+        let page: Vec<Trade> = match payload {
+            Value::Array(_) => serde_json::from_value(payload)?,
+            _ => return Err(PolyError::Shape("expected array")),
+        };
+        let done = page.len() < limit;
+        trades.extend(page);
+        if done {
+            break;
+        }
+    }
+    Ok(trades)
+}
@@ -0,0 +1,125 @@
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::diff::SnapshotRow;
+use crate::error::PolyError;
+use crate::model::{parse_time, Row};
+use crate::spike::VolumeSample;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS market_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    taken_at TEXT NOT NULL,
+    event TEXT NOT NULL,
+    title TEXT NOT NULL,
+    slug TEXT,
+    token_id TEXT,
+    price REAL,
+    volume REAL NOT NULL,
+    volume_24h REAL NOT NULL,
+    change_24h_pct REAL,
+    liquidity REAL,
+    end_date TEXT,
+    resolution TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_market_snapshots_slug_time ON market_snapshots (slug, taken_at);
+CREATE INDEX IF NOT EXISTS idx_market_snapshots_time ON market_snapshots (taken_at);
+";
+
+pub struct SnapshotDb {
+    conn: Connection,
+}
+
+impl SnapshotDb {
+    // Creates the database file and schema on first use.
+    pub fn open(path: &Path) -> Result<Self, PolyError> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(SnapshotDb { conn })
+    }
+
+    // Inserts all rows of one refresh in a single transaction; returns the number written.
+    pub fn record(&mut self, taken_at: DateTime<Utc>, rows: &[Row]) -> Result<usize, PolyError> {
+        let taken_at = taken_at.to_rfc3339();
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT INTO market_snapshots (
+                    taken_at, event, title, slug, token_id, price, volume, volume_24h,
+                    change_24h_pct, liquidity, end_date, resolution
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            )?;
+            for row in rows {
+                stmt.execute(params![
+                    taken_at,
+                    row.event,
+                    row.title,
+                    row.slug,
+                    row.token_id,
+                    row.price,
+                    row.volume,
+                    row.volume_24h,
+                    row.change_24h_pct,
+                    row.liquidity,
+                    row.end_date,
+                    row.resolution,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(rows.len())
+    }
+
+    // The last refresh recorded at or before `at`, with its time, ranked by volume as the default
+    // dashboard shows them. None when nothing was recorded that early.
+    pub fn snapshot_at(&self, at: DateTime<Utc>) -> Result<Option<(DateTime<Utc>, Vec<SnapshotRow>)>, PolyError> {
+        let taken_at: Option<String> = self
+            .conn
+            .query_row("SELECT MAX(taken_at) FROM market_snapshots WHERE taken_at <= ?1", [at.to_rfc3339()], |row| {
+                row.get(0)
+            })
+            .optional()?
+            .flatten();
+        let Some(taken_at) = taken_at else {
+            return Ok(None);
+        };
+
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT title, slug, price, volume, volume_24h FROM market_snapshots
+             WHERE taken_at = ?1 ORDER BY volume DESC",
+        )?;
+        let rows = stmt
+            .query_map([&taken_at], |row| {
+                Ok(SnapshotRow {
+                    title: row.get(0)?,
+                    slug: row.get(1)?,
+                    price: row.get(2)?,
+                    volume: row.get(3)?,
+                    volume_24h: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(parse_time(&taken_at).map(|at| (at, rows)))
+    }
+
+    // Every market's lifetime volume at each snapshot taken between `since` and `until`.
+    pub fn volume_samples(&self, since: DateTime<Utc>, until: DateTime<Utc>) -> Result<Vec<VolumeSample>, PolyError> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT slug, taken_at, volume FROM market_snapshots
+             WHERE slug IS NOT NULL AND taken_at >= ?1 AND taken_at <= ?2",
+        )?;
+        let rows = stmt.query_map(params![since.to_rfc3339(), until.to_rfc3339()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, f64>(2)?))
+        })?;
+        let mut samples = Vec::new();
+        for row in rows {
+            let (slug, taken_at, volume) = row?;
+            if let Some(taken_at) = parse_time(&taken_at) {
+                samples.push(VolumeSample { slug, taken_at, volume });
+            }
+        }
+        Ok(samples)
+    }
+}
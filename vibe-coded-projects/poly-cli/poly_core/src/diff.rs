@@ -0,0 +1,120 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::PolyError;
+use crate::model::lenient_f64;
+
+// One market as saved by `--format json`/`ndjson` or the snapshot database. Only the fields a
+// diff needs are read, and all but the title may be missing (a `--columns` selection).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SnapshotRow {
+    pub title: String,
+    pub slug: Option<String>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub price: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub volume: Option<f64>,
+    #[serde(rename = "volume24h", deserialize_with = "lenient_f64")]
+    pub volume_24h: Option<f64>,
+}
+
+impl SnapshotRow {
+    // Slugs are stable across refreshes; titles are the fallback when a selection left them out.
+    fn key(&self) -> &str {
+        self.slug.as_deref().unwrap_or(&self.title)
+    }
+}
+
+// A market's place and numbers at both points in time; the side it's missing from is None.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketDelta {
+    pub title: String,
+    pub slug: Option<String>,
+    pub rank_before: Option<usize>,
+    pub rank_after: Option<usize>,
+    pub price_before: Option<f64>,
+    pub price_after: Option<f64>,
+    pub price_change: Option<f64>,
+    pub volume_before: Option<f64>,
+    pub volume_after: Option<f64>,
+    pub volume_change: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    pub top: usize,
+    // In the later top N but not the earlier one, in their new order.
+    pub entered: Vec<MarketDelta>,
+    // In the earlier top N but not the later one, in their old order.
+    pub left: Vec<MarketDelta>,
+    // In both, in their new order.
+    pub stayed: Vec<MarketDelta>,
+}
+
+// Reads a saved snapshot: a JSON array of rows, or one row per line as ndjson (which for a
+// single market is one bare object). Row order is taken as rank, as it was on screen.
+pub fn parse_snapshot(text: &str) -> Result<Vec<SnapshotRow>, PolyError> {
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(items)) => Ok(items.into_iter().map(serde_json::from_value).collect::<Result<_, _>>()?),
+        // A lone row is what ndjson of a one-market snapshot looks like.
+        Ok(row @ Value::Object(_)) => Ok(vec![serde_json::from_value(row)?]),
+        Ok(_) => Err(PolyError::Shape("expected an array of market rows")),
+        // Not one JSON document, so try it as ndjson.
+        Err(_) => Ok(text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?),
+    }
+}
+
+pub fn diff_snapshots(before: &[SnapshotRow], after: &[SnapshotRow], top: usize) -> SnapshotDiff {
+    let index = |rows: &[SnapshotRow]| -> HashMap<String, (usize, SnapshotRow)> {
+        let mut index = HashMap::new();
+        for (idx, row) in rows.iter().enumerate() {
+            index.entry(row.key().to_string()).or_insert_with(|| (idx + 1, row.clone()));
+        }
+        index
+    };
+    let (old, new) = (index(before), index(after));
+    let delta = |key: &str| {
+        let (rank_before, was) = old.get(key).map_or((None, None), |(rank, row)| (Some(*rank), Some(row)));
+        let (rank_after, now) = new.get(key).map_or((None, None), |(rank, row)| (Some(*rank), Some(row)));
+        let shown = now.or(was).expect("key comes from one of the snapshots");
+        let price_before = was.and_then(|r| r.price);
+        let price_after = now.and_then(|r| r.price);
+        let volume_before = was.and_then(|r| r.volume);
+        let volume_after = now.and_then(|r| r.volume);
+        MarketDelta {
+            title: shown.title.clone(),
+            slug: shown.slug.clone(),
+            rank_before,
+            rank_after,
+            price_before,
+            price_after,
+            price_change: price_after.zip(price_before).map(|(now, was)| now - was),
+            volume_before,
+            volume_after,
+            volume_change: volume_after.zip(volume_before).map(|(now, was)| now - was),
+        }
+    };
+    let in_top = |rank: Option<usize>| rank.is_some_and(|rank| rank <= top);
+    // A market listed twice in one snapshot counts once, at its first rank.
+    let top_keys = |rows: &[SnapshotRow]| {
+        let mut seen = HashSet::new();
+        rows.iter()
+            .take(top)
+            .map(|row| row.key().to_string())
+            .filter(|key| seen.insert(key.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    let (stayed, entered): (Vec<_>, Vec<_>) = top_keys(after).iter().map(|key| delta(key)).partition(|d| in_top(d.rank_before));
+    let left = top_keys(before).iter().map(|key| delta(key)).filter(|d| !in_top(d.rank_after)).collect();
+    SnapshotDiff { top, entered, left, stayed }
+}
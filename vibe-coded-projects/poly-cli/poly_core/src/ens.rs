@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use tracing::debug;
+
+use crate::error::PolyError;
+use crate::http::bounded;
+use crate::rpc::{calldata, word, JsonRpc};
+use crate::wallet::{checksum, keccak, parse_address};
+
+// ENS lives on Ethereum mainnet, not Polygon; this public endpoint needs no key.
+pub const DEFAULT_ENS_RPC_URL: &str = "https://eth.llamarpc.com";
+const ENS_REGISTRY: &str = "0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+// EIP-137 namehash. Names are used as the reverse record gives them, already normalised.
+fn namehash(name: &str) -> [u8; 32] {
+    let mut node = [0u8; 32];
+    for label in name.rsplit('.').filter(|label| !label.is_empty()) {
+        let mut joined = node.to_vec();
+        joined.extend_from_slice(&keccak(label.as_bytes()));
+        node = keccak(&joined);
+    }
+    node
+}
+
+// The resolver contract set for a name, if any.
+async fn resolver(rpc: &JsonRpc, node: [u8; 32]) -> Result<Option<String>, PolyError> {
+    let data = rpc.call(ENS_REGISTRY, &calldata("resolver(bytes32)", &[node])).await?;
+    let word = word(&data, 0)?;
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&word[12..]);
+    Ok((address != [0; 20]).then(|| checksum(&address)))
+}
+
+// An ABI-encoded `string` return value: offset, length, then the bytes.
+// Offsets and lengths come from the node, so every sum is checked.
+fn decode_string(data: &[u8]) -> Option<String> {
+    let offset = usize::try_from(u64::from_be_bytes(data.get(24..32)?.try_into().ok()?)).ok()?;
+    let start = offset.checked_add(32)?;
+    let len = usize::try_from(u64::from_be_bytes(data.get(offset.checked_add(24)?..start)?.try_into().ok()?)).ok()?;
+    String::from_utf8(data.get(start..start.checked_add(len)?)?.to_vec()).ok()
+}
+
+// The primary ENS name of `address`, counted only if that name resolves back to the address;
+// anyone can point a reverse record at any name.
+pub async fn reverse_lookup(rpc: &JsonRpc, address: &str) -> Result<Option<String>, PolyError> {
+    let owner = parse_address(address)?;
+    let node = namehash(&format!("{}.addr.reverse", hex::encode(owner)));
+    let Some(reverse_resolver) = resolver(rpc, node).await? else {
+        return Ok(None);
+    };
+    let data = rpc.call(&reverse_resolver, &calldata("name(bytes32)", &[node])).await?;
+    let Some(name) = decode_string(&data).filter(|name| !name.is_empty()) else {
+        return Ok(None);
+    };
+
+    let forward = namehash(&name);
+    let Some(forward_resolver) = resolver(rpc, forward).await? else {
+        return Ok(None);
+    };
+    let data = rpc.call(&forward_resolver, &calldata("addr(bytes32)", &[forward])).await?;
+    Ok((word(&data, 0)?[12..] == owner).then_some(name))
+}
+
+// Primary names for whichever addresses have one, keyed by lowercase address. A lookup that
+// fails just leaves that address out.
+pub async fn lookup_ens_names(rpc: &JsonRpc, addresses: &[String]) -> HashMap<String, String> {
+    let found = bounded(addresses, |address| async move { (address, reverse_lookup(rpc, address).await) }).await;
+    found
+        .into_iter()
+        .filter_map(|(address, name)| match name {
+            Ok(name) => Some((address.to_ascii_lowercase(), name?)),
+            Err(e) => {
+                debug!(%address, error = %e, "ENS lookup failed");
+                None
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,133 @@
+use reqwest::StatusCode;
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PolyError {
+    #[error("http client error: {0}")]
+    Client(#[source] reqwest::Error),
+
+    #[error("request error: {0}")]
+    Request(#[source] reqwest::Error),
+
+    #[error("http status error: {0}")]
+    Status(StatusCode),
+
+    #[error("json decode error: {0}")]
+    Decode(#[from] serde_json::Error),
+
+    #[error("unexpected API response shape ({0})")]
+    Shape(&'static str),
+
+    #[error("websocket error: {0}")]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+
+    // The server ended a price stream the CLI still wanted.
+    #[error("price stream closed by server")]
+    StreamClosed,
+
+    #[error("database error: {0}")]
+    Db(#[from] rusqlite::Error),
+
+    #[error("not found: {0}")]
+    NotFound(String),
+
+    // Only for writing a --record tape; other I/O failures are mapped where they happen.
+    #[error("recording error: {0}")]
+    Tape(#[source] std::io::Error),
+
+    // A bad or missing private key or API credentials.
+    #[error("authentication error: {0}")]
+    Auth(String),
+
+    // The CLOB or a Polygon node turned a request down and said why, e.g. an order below the
+    // minimum size or a reverted contract call.
+    #[error("rejected: {0}")]
+    Rejected(String),
+}
+
+impl PolyError {
+    // The process exit code the CLI reports this error with.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            PolyError::Client(_) | PolyError::Request(_) | PolyError::WebSocket(_) | PolyError::StreamClosed => 3,
+            PolyError::Status(_) | PolyError::Rejected(_) => 4,
+            PolyError::Decode(_) => 5,
+            PolyError::Shape(_) => 6,
+            PolyError::NotFound(_) => 7,
+            PolyError::Db(_) => 8,
+            PolyError::Tape(_) => 9,
+            PolyError::Auth(_) => 11,
+        }
+    }
+
+    // A stable name for the kind of failure, for scripts to branch on.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PolyError::Client(_) => "client",
+            PolyError::Request(_) => "network",
+            PolyError::Status(StatusCode::TOO_MANY_REQUESTS) => "rate_limited",
+            PolyError::Status(_) => "http_status",
+            PolyError::Decode(_) => "decode",
+            PolyError::Shape(_) => "shape",
+            PolyError::WebSocket(_) | PolyError::StreamClosed => "websocket",
+            PolyError::Db(_) => "database",
+            PolyError::NotFound(_) => "not_found",
+            PolyError::Tape(_) => "recording",
+            PolyError::Auth(_) => "auth",
+            PolyError::Rejected(_) => "rejected",
+        }
+    }
+
+    // Network hiccups, rate limiting and server errors are worth another try; a 4xx won't improve.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            PolyError::Request(_) => true,
+            PolyError::Status(status) => status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS,
+            _ => false,
+        }
+    }
+}
+
+// The `error` object the CLI prints for --json. Every field is always present so the shape
+// never changes between kinds of failure.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorReport {
+    // One of PolyError::kind's names, "invalid_argument" for bad flags, or "timeout" when
+    // --max-runtime ran out.
+    pub kind: &'static str,
+    pub message: String,
+    pub exit_code: i32,
+    // The HTTP status for http_status and rate_limited errors.
+    pub status: Option<u16>,
+    // Whether the same request might succeed later.
+    pub retryable: bool,
+}
+
+impl ErrorReport {
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        let message = message.into();
+        ErrorReport { kind: "invalid_argument", message, exit_code: 2, status: None, retryable: false }
+    }
+
+    pub fn timeout(message: impl Into<String>) -> Self {
+        let message = message.into();
+        ErrorReport { kind: "timeout", message, exit_code: 10, status: None, retryable: true }
+    }
+}
+
+impl From<&PolyError> for ErrorReport {
+    fn from(err: &PolyError) -> Self {
+        ErrorReport {
+            kind: err.kind(),
+            message: err.to_string(),
+            exit_code: err.exit_code(),
+            status: match err {
+                PolyError::Status(status) => Some(status.as_u16()),
+                _ => None,
+            },
+            retryable: err.is_transient(),
+        }
+    }
+}
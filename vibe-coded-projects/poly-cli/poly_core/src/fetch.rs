@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::stream::{self, StreamExt};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::clob::fetch_orderbook;
+use crate::error::PolyError;
+use crate::http::{bounded, get_json, MAX_IN_FLIGHT};
+use crate::model::{Event, Market, Row};
+use crate::transport::{HttpFetcher, ReqwestFetcher};
+
+pub const BASE_URL: &str = "https://gamma-api.polymarket.com/events";
+pub const MARKETS_URL: &str = "https://gamma-api.polymarket.com/markets";
+
+// Gamma returns at most this many events per request, whatever `limit` asks for.
+const EVENTS_PAGE: usize = 100;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarketStatus {
+    // Live markets only (active, not closed).
+    #[default]
+    Open,
+    // Live and closed markets together.
+    All,
+    // Closed markets whose winning outcome is known.
+    Resolved,
+}
+
+// The order gamma returns events in, which decides which events a limited fetch sees.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EventOrder {
+    // Highest lifetime volume first.
+    #[default]
+    Volume,
+    // Most recently created first.
+    Newest,
+    // Soonest end date first.
+    Ending,
+    // Latest end date first.
+    Ended,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventQuery {
+    pub limit: usize,
+    pub offset: usize,
+    pub status: MarketStatus,
+    pub order: EventOrder,
+    // Only events whose end date falls in this range.
+    pub end_after: Option<DateTime<Utc>>,
+    pub end_before: Option<DateTime<Utc>>,
+    // Tag slugs or labels (case-insensitive). An event must carry at least one `tags` entry
+    // and none of the `exclude_tags` entries.
+    pub tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+}
+
+impl Default for EventQuery {
+    fn default() -> Self {
+        EventQuery {
+            limit: 150,
+            offset: 0,
+            status: MarketStatus::Open,
+            order: EventOrder::Volume,
+            end_after: None,
+            end_before: None,
+            tags: Vec::new(),
+            exclude_tags: Vec::new(),
+        }
+    }
+}
+
+impl EventQuery {
+    pub fn new(limit: usize) -> Self {
+        EventQuery { limit, ..Default::default() }
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = match self.status {
+            MarketStatus::Open => vec![("active", "true".to_string()), ("closed", "false".to_string())],
+            MarketStatus::All => Vec::new(),
+            MarketStatus::Resolved => vec![("closed", "true".to_string())],
+        };
+        let (order, ascending) = match self.order {
+            EventOrder::Volume => ("volume", false),
+            EventOrder::Newest => ("createdAt", false),
+            EventOrder::Ending => ("endDate", true),
+            EventOrder::Ended => ("endDate", false),
+        };
+        params.extend([
+            ("order", order.to_string()),
+            ("ascending", ascending.to_string()),
+            ("limit", self.limit.to_string()),
+            ("offset", self.offset.to_string()),
+        ]);
+        if let Some(after) = self.end_after {
+            params.push(("end_date_min", after.to_rfc3339_opts(SecondsFormat::Secs, true)));
+        }
+        if let Some(before) = self.end_before {
+            params.push(("end_date_max", before.to_rfc3339_opts(SecondsFormat::Secs, true)));
+        }
+        // Gamma only filters on a single tag server-side; multiple tags are matched locally.
+        if let [tag] = self.tags.as_slice() {
+            params.push(("tag_slug", tag.to_lowercase()));
+        }
+        params
+    }
+
+    pub fn accepts(&self, event: &Event) -> bool {
+        let included = self.tags.is_empty() || self.tags.iter().any(|t| event.has_tag(t));
+        included && !self.exclude_tags.iter().any(|t| event.has_tag(t))
+    }
+}
+
+async fn fetch_event_page(fetcher: &impl HttpFetcher, query: &EventQuery) -> Result<Vec<Event>, PolyError> {
+    let payload = fetcher.get_json(BASE_URL, &query.params()).await?;
+
+    if !payload.is_array() {
+        return Err(PolyError::Shape("expected array"));
+    }
+    Ok(serde_json::from_value(payload)?)
+}
+
+// Up to `query.limit` events, paging by offset past gamma's per-request cap. Pages are fetched
+// concurrently and merged in order, stopping at the first short page. Rankings can shift between
+// pages, so an event or market seen on an earlier page is dropped from later ones.
+pub async fn fetch_events(query: &EventQuery) -> Result<Vec<Event>, PolyError> {
+    fetch_events_with(&ReqwestFetcher::default(), query).await
+}
+
+// `fetch_events` with payloads from `fetcher` instead of the network.
+pub async fn fetch_events_with(fetcher: &impl HttpFetcher, query: &EventQuery) -> Result<Vec<Event>, PolyError> {
+    let pages: Vec<EventQuery> = (0..query.limit)
+        .step_by(EVENTS_PAGE)
+        .map(|start| EventQuery {
+            limit: EVENTS_PAGE.min(query.limit - start),
+            offset: query.offset + start,
+            ..query.clone()
+        })
+        .collect();
+    let mut batches = stream::iter(&pages)
+        .map(|page| async move { (page.limit, fetch_event_page(fetcher, page).await) })
+        .buffered(MAX_IN_FLIGHT);
+
+    let mut events: Vec<Event> = Vec::new();
+    while let Some((limit, batch)) = batches.next().await {
+        let batch = batch?;
+        let done = batch.len() < limit;
+        events.extend(batch);
+        if done {
+            break;
+        }
+    }
+
+    dedupe(&mut events);
+    Ok(events.into_iter().filter(|e| query.accepts(e)).collect())
+}
+
+fn dedupe(events: &mut Vec<Event>) {
+    let mut seen_events = HashSet::new();
+    events.retain(|event| event.id.as_ref().is_none_or(|id| seen_events.insert(id.clone())));
+    let mut seen_markets = HashSet::new();
+    for event in events.iter_mut() {
+        event.markets.retain(|market| market.id.as_ref().is_none_or(|id| seen_markets.insert(id.clone())));
+    }
+}
+
+pub async fn fetch_event(slug: &str) -> Result<Event, PolyError> {
+    let payload = get_json(BASE_URL, &[("slug", slug.to_string())]).await?;
+
+    let events: Vec<Event> = match payload {
+        Value::Array(_) => serde_json::from_value(payload)?,
+        _ => return Err(PolyError::Shape("expected array")),
+    };
+
+    events
+        .into_iter()
+        .next()
+        .ok_or_else(|| PolyError::NotFound(format!("event '{slug}'")))
+}
+
+pub async fn fetch_market(slug: &str) -> Result<Market, PolyError> {
+    let payload = get_json(MARKETS_URL, &[("slug", slug.to_string())]).await?;
+
+    let markets: Vec<Market> = match payload {
+        Value::Array(_) => serde_json::from_value(payload)?,
+        _ => return Err(PolyError::Shape("expected array")),
+    };
+
+    markets
+        .into_iter()
+        .next()
+        .ok_or_else(|| PolyError::NotFound(format!("market '{slug}'")))
+}
+
+// Rows for specific markets in the order their slugs were given, plus the slugs gamma doesn't
+// know (delisted or mistyped), so one bad slug doesn't sink the rest. Each market's embedded
+// parent event supplies the event title that the event-based fetch would have used.
+pub async fn fetch_market_rows(slugs: &[String]) -> Result<(Vec<Row>, Vec<String>), PolyError> {
+    if slugs.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let mut query: Vec<(&str, String)> = slugs.iter().map(|slug| ("slug", slug.clone())).collect();
+    query.push(("limit", slugs.len().to_string()));
+    let payload = get_json(MARKETS_URL, &query).await?;
+
+    let markets: Vec<Market> = match payload {
+        Value::Array(_) => serde_json::from_value(payload)?,
+        _ => return Err(PolyError::Shape("expected array")),
+    };
+
+    let no_event = Event::default();
+    let (mut rows, mut missing) = (Vec::new(), Vec::new());
+    for slug in slugs {
+        match markets.iter().find(|m| m.slug.as_deref() == Some(slug.as_str())) {
+            Some(market) => rows.push(Row::from_market(market.events.first().unwrap_or(&no_event), market)),
+            None => missing.push(slug.clone()),
+        }
+    }
+    Ok((rows, missing))
+}
+
+// The markets trading the given CLOB outcome tokens, in whatever order gamma returns them.
+pub async fn fetch_markets_by_token(token_ids: &[String]) -> Result<Vec<Market>, PolyError> {
+    if token_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut query: Vec<(&str, String)> = token_ids.iter().map(|id| ("clob_token_ids", id.clone())).collect();
+    query.push(("limit", token_ids.len().to_string()));
+    let payload = get_json(MARKETS_URL, &query).await?;
+
+    match payload {
+        Value::Array(_) => Ok(serde_json::from_value(payload)?),
+        _ => Err(PolyError::Shape("expected array")),
+    }
+}
+
+// Fills each row's best bid/ask, spread, and midpoint from the CLOB book, fetching all rows
+// concurrently. A book that can't be fetched leaves the quote empty rather than failing the refresh.
+pub async fn attach_quotes(rows: &mut [Row]) {
+    let books = bounded(rows.iter(), |row| async move {
+        match &row.token_id {
+            Some(token) => fetch_orderbook(token)
+                .await
+                .inspect_err(|e| warn!(%token, error = %e, "no book for quotes"))
+                .ok(),
+            None => None,
+        }
+    })
+    .await;
+
+    for (row, book) in rows.iter_mut().zip(books) {
+        let book = book.unwrap_or_default();
+        row.best_bid = book.best_bid();
+        row.best_ask = book.best_ask();
+        row.spread = book.spread();
+        row.midpoint = book.midpoint();
+    }
+}
+
+pub fn rows_from_events(events: &[Event]) -> Vec<Row> {
+    events
+        .iter()
+        .flat_map(|event| event.markets.iter().map(move |market| Row::from_market(event, market)))
+        .collect()
+}
+
+// Rows come back in feed order; callers pick an order with `sort::sort_rows`.
+pub async fn fetch_markets(query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+    fetch_markets_with(&ReqwestFetcher::default(), query).await
+}
+
+// `fetch_markets` with payloads from `fetcher` instead of the network.
+pub async fn fetch_markets_with(fetcher: &impl HttpFetcher, query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+    let events = fetch_events_with(fetcher, query).await?;
+    let mut rows = rows_from_events(&events);
+    if query.status == MarketStatus::Resolved {
+        rows.retain(|row| row.resolution.is_some());
+    }
+    Ok(rows)
+}
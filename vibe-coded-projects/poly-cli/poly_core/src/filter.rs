@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use crate::model::Row;
+
+// An inclusive range for a market's first-outcome price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceBand {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl PriceBand {
+    // Cheap outcomes the crowd gives little chance.
+    pub const LONGSHOTS: PriceBand = PriceBand { min: 0.0, max: 0.10 };
+    // Outcomes priced as all but settled.
+    pub const NEAR_CERTAIN: PriceBand = PriceBand { min: 0.95, max: 1.0 };
+
+    pub fn contains(self, price: f64) -> bool {
+        self.min <= price && price <= self.max
+    }
+}
+
+// Thresholds applied to rows before the top-N cut. Rows with no reported liquidity fail
+// `min_liquidity`, since an unknown book shouldn't pass a liquidity screen; likewise rows with
+// no price fail `price`.
+#[derive(Debug, Clone, Default)]
+pub struct RowFilter {
+    pub min_volume: Option<f64>,
+    pub min_volume_24h: Option<f64>,
+    pub min_liquidity: Option<f64>,
+    pub price: Option<PriceBand>,
+    // Keep at most this many markets from each event, the highest-volume ones.
+    pub per_event: Option<usize>,
+}
+
+impl RowFilter {
+    pub fn accepts(&self, row: &Row) -> bool {
+        if self.min_volume.is_some_and(|min| row.volume < min) {
+            return false;
+        }
+        if self.min_volume_24h.is_some_and(|min| row.volume_24h < min) {
+            return false;
+        }
+        if let Some(min) = self.min_liquidity {
+            if row.liquidity.is_none_or(|liq| liq < min) {
+                return false;
+            }
+        }
+        if let Some(band) = self.price {
+            if row.price.is_none_or(|price| !band.contains(price)) {
+                return false;
+            }
+        }
+        true
+    }
+
+    // Rows that pass the thresholds and the per-event cap, in their original order.
+    pub fn apply(&self, rows: Vec<Row>) -> Vec<Row> {
+        let rows: Vec<Row> = rows.into_iter().filter(|row| self.accepts(row)).collect();
+        match self.per_event {
+            Some(limit) => limit_per_event(rows, limit),
+            None => rows,
+        }
+    }
+}
+
+fn limit_per_event(rows: Vec<Row>, limit: usize) -> Vec<Row> {
+    let mut by_volume: Vec<usize> = (0..rows.len()).collect();
+    by_volume.sort_by(|&a, &b| rows[b].volume.total_cmp(&rows[a].volume));
+    let mut taken: HashMap<(_, &str), usize> = HashMap::new();
+    let mut keep = vec![false; rows.len()];
+    for idx in by_volume {
+        let row = &rows[idx];
+        let event = row.event_slug.as_deref().unwrap_or(&row.event);
+        let count = taken.entry((row.venue, event)).or_default();
+        if *count < limit {
+            *count += 1;
+            keep[idx] = true;
+        }
+    }
+    rows.into_iter().zip(keep).filter_map(|(row, keep)| keep.then_some(row)).collect()
+}
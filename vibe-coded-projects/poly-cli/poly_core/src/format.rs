@@ -0,0 +1,273 @@
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Local, Utc};
+use chrono_tz::Tz;
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+pub struct C;
+impl C {
+    pub const RESET: &'static str = "\x1b[0m";
+    pub const BOLD: &'static str = "\x1b[1m";
+    pub const DIM: &'static str = "\x1b[2m";
+    pub const CYAN: &'static str = "\x1b[36m";
+    pub const BLUE: &'static str = "\x1b[94m";
+    pub const GREEN: &'static str = "\x1b[92m";
+    pub const RED: &'static str = "\x1b[91m";
+    pub const YELLOW: &'static str = "\x1b[93m";
+    pub const WHITE: &'static str = "\x1b[97m";
+    pub const MAGENTA: &'static str = "\x1b[95m";
+}
+
+pub fn supports_color(no_color: bool) -> bool {
+    if no_color || env::var_os("NO_COLOR").is_some() {
+        return false;
+    }
+    atty::is(atty::Stream::Stdout)
+}
+
+// Columns of the terminal stdout is attached to, or None when it's redirected somewhere.
+pub fn terminal_width() -> Option<usize> {
+    terminal_size::terminal_size().map(|(terminal_size::Width(width), _)| usize::from(width))
+}
+
+pub fn paint(text: &str, color: &str, enabled: bool) -> String {
+    if !enabled {
+        return text.to_string();
+    }
+    format!("{color}{text}{}", C::RESET)
+}
+
+pub fn format_money(value: f64) -> String {
+    format_amount(value, "$")
+}
+
+// `format_money` in another currency, such as Manifold's mana (Ṁ).
+pub fn format_amount(value: f64, symbol: &str) -> String {
+    let abs_value = value.abs();
+    if abs_value >= 1_000_000_000.0 {
+        format!("{symbol}{:.2}B", value / 1_000_000_000.0)
+    } else if abs_value >= 1_000_000.0 {
+        format!("{symbol}{:.2}M", value / 1_000_000.0)
+    } else if abs_value >= 1_000.0 {
+        format!("{symbol}{:.1}K", value / 1_000.0)
+    } else {
+        format!("{symbol}{:.0}", value)
+    }
+}
+
+pub fn format_percent(value: Option<f64>) -> String {
+    match value {
+        None => "n/a".to_string(),
+        Some(v) if v > 0.0 => format!("+{v:.2}%"),
+        Some(v) => format!("{v:.2}%"),
+    }
+}
+
+pub fn format_price(value: Option<f64>) -> String {
+    match value {
+        None => "n/a".to_string(),
+        Some(p) => format!("{:.1}¢", p * 100.0),
+    }
+}
+
+const SPARK_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Squeezes a series into `width` bar characters scaled between its own min and max.
+pub fn sparkline(values: &[f64], width: usize) -> String {
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+    let width = width.min(values.len());
+    let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = high - low;
+
+    (0..width)
+        .map(|i| {
+            let value = values[i * (values.len() - 1) / (width - 1).max(1)];
+            let level = if span > 0.0 { (value - low) / span } else { 0.5 };
+            SPARK_BARS[(level * (SPARK_BARS.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
+// The timezone end dates are shown in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisplayZone {
+    #[default]
+    Utc,
+    Local,
+    Named(Tz),
+}
+
+impl DisplayZone {
+    // `2025-11-04 19:00 EST`; the zone is always spelled out so a time is never mistaken for
+    // another zone's. Local time has no abbreviation to give, so it carries its UTC offset.
+    pub fn format(self, at: DateTime<Utc>) -> String {
+        match self {
+            DisplayZone::Utc => at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            DisplayZone::Local => at.with_timezone(&Local).format("%Y-%m-%d %H:%M %z").to_string(),
+            DisplayZone::Named(tz) => at.with_timezone(&tz).format("%Y-%m-%d %H:%M %Z").to_string(),
+        }
+    }
+
+    // A bare clock time in this zone, for lines stamped as they print.
+    pub fn clock(self, at: DateTime<Utc>) -> String {
+        match self {
+            DisplayZone::Utc => at.format("%H:%M:%S").to_string(),
+            DisplayZone::Local => at.with_timezone(&Local).format("%H:%M:%S").to_string(),
+            DisplayZone::Named(tz) => at.with_timezone(&tz).format("%H:%M:%S").to_string(),
+        }
+    }
+}
+
+impl FromStr for DisplayZone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "utc" | "z" => Ok(DisplayZone::Utc),
+            "local" => Ok(DisplayZone::Local),
+            _ => s.trim().parse::<Tz>().map(DisplayZone::Named).map_err(|_| {
+                format!("unknown timezone '{s}' (expected local, UTC, or an IANA name such as America/New_York)")
+            }),
+        }
+    }
+}
+
+impl fmt::Display for DisplayZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisplayZone::Utc => f.write_str("UTC"),
+            DisplayZone::Local => f.write_str("local"),
+            DisplayZone::Named(tz) => f.write_str(tz.name()),
+        }
+    }
+}
+
+// Process-wide settings for how dates are shown, installed once at startup with `configure_display`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DisplayOptions {
+    pub zone: DisplayZone,
+    // End dates as time left (`in 3d 4h`) or time since (`ended 2h ago`) instead of a timestamp.
+    pub relative_end: bool,
+}
+
+static DISPLAY: OnceLock<DisplayOptions> = OnceLock::new();
+
+// Calling it twice keeps the first options.
+pub fn configure_display(options: DisplayOptions) {
+    let _ = DISPLAY.set(options);
+}
+
+pub fn display_options() -> DisplayOptions {
+    DISPLAY.get().copied().unwrap_or_default()
+}
+
+// The two largest units of a span: `3d 4h`, `5h 12m`, `45m`, `30s`.
+pub fn format_span(span: chrono::Duration) -> String {
+    let secs = span.num_seconds().max(0);
+    let (days, hours, minutes) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+// `in 3d 4h` for a future end, `ended 2h ago` for a past one.
+pub fn format_relative_end(end: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    if end > now {
+        format!("in {}", format_span(end - now))
+    } else {
+        format!("ended {} ago", format_age(now - end))
+    }
+}
+
+pub fn format_end(value: Option<&str>) -> String {
+    let options = display_options();
+    match value {
+        None => "n/a".to_string(),
+        Some(s) => DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map(|end| {
+                if options.relative_end {
+                    format_relative_end(end, Utc::now())
+                } else {
+                    options.zone.format(end)
+                }
+            })
+            .unwrap_or_else(|_| s.to_string()),
+    }
+}
+
+// A coarse age in the largest whole unit: `45s`, `12m`, `3h`, `2d`.
+pub fn format_age(age: chrono::Duration) -> String {
+    let secs = age.num_seconds().max(0);
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+pub fn ansi_regex() -> Regex {
+    Regex::new(r"\x1b\[[0-9;]*m").expect("valid ansi regex")
+}
+
+// Terminal columns the text occupies once ANSI codes are stripped: wide characters such as CJK
+// and most emoji take two, combining marks none.
+pub fn visible_len(text: &str, ansi_re: &Regex) -> usize {
+    ansi_re.replace_all(text, "").width()
+}
+
+pub fn truncate_visible(text: &str, max_len: usize, ansi_re: &Regex) -> String {
+    if max_len == 0 {
+        return String::new();
+    }
+    if visible_len(text, ansi_re) <= max_len {
+        return text.to_string();
+    }
+
+    // Cut on grapheme boundaries so an emoji sequence or a letter with its accents stays whole;
+    // a wide grapheme that would straddle the limit is dropped and the gap left to padding.
+    let plain = ansi_re.replace_all(text, "");
+    let mut out = String::new();
+    let take = if max_len <= 3 { max_len } else { max_len - 3 };
+    let mut used = 0;
+
+    for grapheme in plain.graphemes(true) {
+        let width = grapheme.width();
+        if used + width > take {
+            break;
+        }
+        out.push_str(grapheme);
+        used += width;
+    }
+
+    if max_len > 3 {
+        out.push_str("...");
+    }
+
+    out
+}
+
+pub fn pad_visible(text: &str, width: usize, ansi_re: &Regex) -> String {
+    let truncated = truncate_visible(text, width, ansi_re);
+    let len = visible_len(&truncated, ansi_re);
+    if len >= width {
+        truncated
+    } else {
+        format!("{}{}", truncated, " ".repeat(width - len))
+    }
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::clob::fetch_price_history;
+use crate::error::PolyError;
+use crate::fetch::{fetch_events, EventOrder, EventQuery};
+use crate::http::bounded;
+use crate::model::parse_time;
+
+// How long after creation the opening price is looked for.
+const OPENING_WINDOW_SECS: i64 = 24 * 3600;
+
+// A market created recently, with the first price it traded at next to where it is now.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewMarket {
+    pub created_at: DateTime<Utc>,
+    pub event: String,
+    pub title: String,
+    pub slug: Option<String>,
+    pub opening_price: Option<f64>,
+    pub price: Option<f64>,
+    #[serde(rename = "volume24h")]
+    pub volume_24h: f64,
+    pub liquidity: Option<f64>,
+}
+
+impl NewMarket {
+    pub fn change(&self) -> Option<f64> {
+        Some(self.price? - self.opening_price?)
+    }
+}
+
+// Markets created at or after `since`, newest first, at most `max` of them. Events are fetched
+// newest first, so a new market added to an older event is only seen if that event is within
+// the query's limit.
+pub async fn fetch_new_markets(
+    query: &EventQuery,
+    since: DateTime<Utc>,
+    max: usize,
+) -> Result<Vec<NewMarket>, PolyError> {
+    let query = EventQuery { order: EventOrder::Newest, ..query.clone() };
+    let events = fetch_events(&query).await?;
+
+    let mut fresh: Vec<(NewMarket, Option<String>)> = events
+        .iter()
+        .flat_map(|event| event.markets.iter().map(move |market| (event, market)))
+        .filter_map(|(event, market)| {
+            let created_at = parse_time(market.created_at.as_deref()?)?;
+            (created_at >= since).then(|| {
+                let listing = NewMarket {
+                    created_at,
+                    event: event.display_title().to_string(),
+                    title: market.display_title().to_string(),
+                    slug: market.slug.clone(),
+                    opening_price: None,
+                    price: market.outcome_prices.first().copied(),
+                    volume_24h: market.volume_24hr.unwrap_or(0.0),
+                    liquidity: market.liquidity(),
+                };
+                (listing, market.clob_token_ids.first().cloned())
+            })
+        })
+        .collect();
+    fresh.sort_by(|a, b| b.0.created_at.cmp(&a.0.created_at));
+    fresh.truncate(max);
+
+    let openings = bounded(fresh.iter(), |(listing, token)| async move {
+        let token = token.as_ref()?;
+        let start = listing.created_at.timestamp();
+        let history = fetch_price_history(token, start, start + OPENING_WINDOW_SECS, 60).await.ok()?;
+        history.first().map(|point| point.p)
+    })
+    .await;
+
+    Ok(fresh
+        .into_iter()
+        .zip(openings)
+        .map(|((listing, _), opening_price)| NewMarket { opening_price, ..listing })
+        .collect())
+}
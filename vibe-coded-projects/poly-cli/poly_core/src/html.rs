@@ -0,0 +1,152 @@
+use chrono::Utc;
+
+use crate::columns::Column;
+use crate::format::{format_end, format_percent, format_price, sparkline};
+use crate::model::Row;
+use crate::render::SPARKLINE_WIDTH;
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Roboto, sans-serif; margin: 2rem; background: #0f1115; color: #e6e6e6; }
+h1 { font-size: 1.4rem; margin-bottom: 0.2rem; }
+.meta { color: #8a8f98; margin-bottom: 1.2rem; font-size: 0.9rem; }
+table { border-collapse: collapse; width: 100%; font-size: 0.9rem; }
+th, td { padding: 0.45rem 0.7rem; border-bottom: 1px solid #262a33; text-align: left; }
+th { color: #7aa2f7; cursor: pointer; user-select: none; white-space: nowrap; }
+th:hover { color: #a9c1ff; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+tr:hover td { background: #171a21; }
+.up { color: #4ade80; font-weight: 600; }
+.down { color: #f87171; font-weight: 600; }
+.flat { color: #facc15; }
+.na { color: #6b7280; }
+a { color: #e6e6e6; text-decoration: none; }
+a:hover { text-decoration: underline; }
+"#;
+
+// Sorts on each cell's data-sort value (numeric when both parse), toggling direction per click.
+const SCRIPT: &str = r#"
+document.querySelectorAll("th").forEach((th, idx) => {
+  th.addEventListener("click", () => {
+    const body = th.closest("table").querySelector("tbody");
+    const asc = th.dataset.dir !== "asc";
+    th.dataset.dir = asc ? "asc" : "desc";
+    const key = (row) => row.children[idx].dataset.sort ?? row.children[idx].textContent;
+    Array.from(body.rows)
+      .sort((a, b) => {
+        const x = key(a), y = key(b);
+        const nx = parseFloat(x), ny = parseFloat(y);
+        const cmp = !isNaN(nx) && !isNaN(ny) ? nx - ny : x.localeCompare(y);
+        return asc ? cmp : -cmp;
+      })
+      .forEach((row) => body.appendChild(row));
+  });
+});
+"#;
+
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn sort_attr(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-1e18".to_string())
+}
+
+fn cell(column: Column, idx: usize, row: &Row) -> String {
+    let num = |sort: String, text: String| format!("<td class=\"num\" data-sort=\"{sort}\">{text}</td>");
+    match column {
+        Column::Rank => num((idx + 1).to_string(), (idx + 1).to_string()),
+        Column::Venue => format!("<td>{}</td>", row.venue.label()),
+        Column::Title => {
+            let title = escape_html(&row.title);
+            match row.url() {
+                Some(url) => format!("<td><a href=\"{}\">{title}</a></td>", escape_html(&url)),
+                None => format!("<td>{title}</td>"),
+            }
+        }
+        Column::Event => format!("<td>{}</td>", escape_html(&row.event)),
+        Column::Slug => format!("<td>{}</td>", escape_html(row.slug.as_deref().unwrap_or(""))),
+        Column::Url => match row.url() {
+            Some(url) => format!("<td><a href=\"{url}\">{url}</a></td>", url = escape_html(&url)),
+            None => "<td class=\"na\">n/a</td>".to_string(),
+        },
+        Column::Price => num(sort_attr(row.price), escape_html(&format_price(row.price))),
+        Column::Volume => num(row.volume.to_string(), row.venue.format_money(row.volume)),
+        Column::Volume24h => num(row.volume_24h.to_string(), row.venue.format_money(row.volume_24h)),
+        Column::Change => {
+            let change_class = match row.change_24h_pct {
+                None => "na",
+                Some(v) if v > 0.0 => "up",
+                Some(v) if v < 0.0 => "down",
+                Some(_) => "flat",
+            };
+            format!(
+                "<td class=\"num {change_class}\" data-sort=\"{}\">{}</td>",
+                sort_attr(row.change_24h_pct),
+                format_percent(row.change_24h_pct)
+            )
+        }
+        Column::Liquidity => num(
+            sort_attr(row.liquidity),
+            row.liquidity.map_or_else(|| "n/a".to_string(), |l| row.venue.format_money(l)),
+        ),
+        Column::End => format!(
+            "<td data-sort=\"{}\">{}</td>",
+            escape_html(row.end_date.as_deref().unwrap_or("")),
+            escape_html(&format_end(row.end_date.as_deref()))
+        ),
+        Column::Result => format!("<td>{}</td>", escape_html(row.resolution.as_deref().unwrap_or("open"))),
+        Column::Trend => format!("<td>{}</td>", sparkline(&row.trend, SPARKLINE_WIDTH)),
+        Column::Momentum => format!("<td>{}</td>", row.momentum.map_or("-", |m| m.arrow())),
+        Column::Spike => num(
+            sort_attr(row.spike),
+            row.spike.map_or_else(|| "-".to_string(), |ratio| format!("{ratio:.1}×")),
+        ),
+        Column::Quote => num(
+            sort_attr(row.best_bid),
+            escape_html(&format!("{} / {}", format_price(row.best_bid), format_price(row.best_ask))),
+        ),
+        Column::Spread => num(sort_attr(row.spread), escape_html(&format_price(row.spread))),
+        Column::Mid => num(sort_attr(row.midpoint), escape_html(&format_price(row.midpoint))),
+    }
+}
+
+fn render_row(idx: usize, row: &Row, columns: &[Column]) -> String {
+    let cells: String = columns.iter().map(|c| cell(*c, idx, row)).collect();
+    format!("<tr>{cells}</tr>")
+}
+
+pub fn render_html(rows: &[Row], columns: &[Column], title: &str) -> String {
+    let generated = Utc::now().format("%Y-%m-%d %H:%M:%S UTC");
+    let header_html = columns
+        .iter()
+        .map(|c| format!("<th>{}</th>", escape_html(c.label())))
+        .collect::<Vec<_>>()
+        .join("");
+    let body_html = rows
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| render_row(idx, row, columns))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let title = escape_html(title);
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<div class=\"meta\">Generated {generated} &middot; Source: gamma-api.polymarket.com</div>\n\
+         <table>\n<thead><tr>{header_html}</tr></thead>\n<tbody>\n{body_html}\n</tbody>\n</table>\n\
+         <script>{SCRIPT}</script>\n</body>\n</html>\n"
+    )
+}
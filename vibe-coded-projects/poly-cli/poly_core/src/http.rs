@@ -0,0 +1,296 @@
+use std::future::Future;
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use futures_util::stream::{self, StreamExt};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, USER_AGENT};
+use reqwest::{Client, Method, Proxy, Request, Response, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use crate::cache;
+use crate::conditional;
+use crate::error::PolyError;
+use crate::tape;
+
+// Most requests a single fan-out (event pages, per-market books or histories) keeps in flight.
+pub(crate) const MAX_IN_FLIGHT: usize = 8;
+
+// `f` applied to every item with at most MAX_IN_FLIGHT running at once, results in input order.
+pub(crate) async fn bounded<I, F, Fut>(items: I, f: F) -> Vec<Fut::Output>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    stream::iter(items).map(f).buffered(MAX_IN_FLIGHT).collect().await
+}
+
+// Process-wide settings for API requests, installed once at startup with `configure_http`.
+#[derive(Debug, Clone)]
+pub struct HttpOptions {
+    // Extra attempts after a transient failure (network error, timeout, 429 or 5xx).
+    pub retries: u32,
+    // Wait before the first retry; it doubles for each one after, with jitter.
+    pub retry_backoff: Duration,
+    // Most requests per second across every API (gamma, CLOB, data API, other venues), with
+    // bursts up to one second's worth. None or 0 means unlimited.
+    pub rate_limit: Option<f64>,
+    // Proxy for every request: http://, https://, socks5:// or socks5h:// (DNS through the proxy).
+    // When unset, HTTPS_PROXY, HTTP_PROXY, ALL_PROXY and NO_PROXY from the environment apply.
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        HttpOptions { retries: 2, retry_backoff: Duration::from_millis(500), rate_limit: Some(20.0), proxy: None }
+    }
+}
+
+static OPTIONS: OnceLock<HttpOptions> = OnceLock::new();
+// One client for the whole process, so requests share its connection pool and TLS sessions.
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+// Applies to every request made after it; calling it twice keeps the first options. Fails when
+// the proxy URL isn't usable.
+pub fn configure_http(options: HttpOptions) -> Result<(), PolyError> {
+    let client = build_client(&options)?;
+    if OPTIONS.set(options).is_ok() {
+        let _ = CLIENT.set(client);
+    }
+    Ok(())
+}
+
+fn options() -> &'static HttpOptions {
+    OPTIONS.get_or_init(HttpOptions::default)
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+}
+
+static BUCKET: Mutex<Option<Bucket>> = Mutex::new(None);
+
+// Waits for a token from the shared bucket before a request goes out.
+async fn throttle() {
+    let Some(rate) = options().rate_limit.filter(|rate| *rate > 0.0) else {
+        return;
+    };
+    let burst = rate.max(1.0);
+    loop {
+        let wait = {
+            let mut bucket = BUCKET.lock().unwrap_or_else(PoisonError::into_inner);
+            let bucket = bucket.get_or_insert_with(|| Bucket { tokens: burst, refilled: Instant::now() });
+            let now = Instant::now();
+            bucket.tokens = (bucket.tokens + now.duration_since(bucket.refilled).as_secs_f64() * rate).min(burst);
+            bucket.refilled = now;
+            if bucket.tokens >= 1.0 {
+                bucket.tokens -= 1.0;
+                return;
+            }
+            Duration::from_secs_f64((1.0 - bucket.tokens) / rate)
+        };
+        debug!(wait_ms = wait.as_millis() as u64, "rate limit reached, waiting");
+        tokio::time::sleep(wait).await;
+    }
+}
+
+// Somewhere between half and all of `base * 2^attempt`, so clients that failed together don't
+// retry in lockstep. The clock's nanoseconds are random enough for spreading retries out.
+fn backoff(base: Duration, attempt: u32) -> Duration {
+    let full = base.saturating_mul(2u32.saturating_pow(attempt));
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+    full.mul_f64(0.5 + f64::from(nanos % 1000) / 2000.0)
+}
+
+fn build_client(options: &HttpOptions) -> Result<Client, PolyError> {
+    // Gamma's event listings compress well, which matters for watch mode on a metered link.
+    let mut builder = Client::builder().timeout(Duration::from_secs(20)).gzip(true).brotli(true);
+    if let Some(proxy) = &options.proxy {
+        builder = builder.proxy(Proxy::all(proxy).map_err(PolyError::Client)?);
+    }
+    builder.build().map_err(PolyError::Client)
+}
+
+// A handle on the shared client; cloning it shares the pool. Built from the default options when
+// `configure_http` was never called.
+fn client() -> Result<Client, PolyError> {
+    if let Some(client) = CLIENT.get() {
+        return Ok(client.clone());
+    }
+    let client = build_client(options())?;
+    Ok(CLIENT.get_or_init(|| client).clone())
+}
+
+// GETs a JSON payload, retrying transient failures per the configured `HttpOptions`.
+pub(crate) async fn get_json(url: &str, query: &[(&str, String)]) -> Result<Value, PolyError> {
+    let options = options();
+    let mut attempt = 0;
+    loop {
+        match get_json_once(url, query).await {
+            Err(e) if attempt < options.retries && e.is_transient() => {
+                let delay = backoff(options.retry_backoff, attempt);
+                attempt += 1;
+                let delay_ms = delay.as_millis() as u64;
+                info!(url, attempt, of = options.retries, delay_ms, error = %e, "retrying");
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+async fn execute(client: &Client, request: Request) -> Result<Response, PolyError> {
+    let url = request.url().clone();
+    let started = Instant::now();
+    let response = client.execute(request).await.map_err(|e| {
+        info!(%url, elapsed_ms = started.elapsed().as_millis() as u64, error = %e, "GET failed");
+        PolyError::Request(e)
+    })?;
+    info!(%url, status = response.status().as_u16(), elapsed_ms = started.elapsed().as_millis() as u64, "GET");
+    Ok(response)
+}
+
+async fn get_json_once(url: &str, query: &[(&str, String)]) -> Result<Value, PolyError> {
+    let client = client()?;
+
+    let build = || {
+        client
+            .get(url)
+            .query(query)
+            .header(USER_AGENT, "poly-cli-dashboard/1.0")
+            .header(ACCEPT, "application/json")
+            .build()
+            .map_err(PolyError::Request)
+    };
+    let mut request = build()?;
+    let url = request.url().clone();
+    if let Some(recorded) = tape::replay(&url) {
+        debug!(%url, "GET from tape");
+        return recorded;
+    }
+    if let Some(cached) = cache::lookup(&url) {
+        debug!(%url, "GET from cache");
+        return Ok(cached);
+    }
+    if cache::is_offline() {
+        return Err(PolyError::NotFound(format!("cached response for {url} (offline)")));
+    }
+
+    conditional::apply(&url, request.headers_mut());
+    throttle().await;
+
+    let mut response = execute(&client, request).await?;
+
+    // Not Modified means the last payload for this URL still stands. It can have been evicted
+    // since the request went out, and then the request is sent again without validators.
+    let mut unchanged = None;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        unchanged = conditional::previous(&url);
+        if unchanged.is_none() {
+            throttle().await;
+            response = execute(&client, build()?).await?;
+        }
+    }
+    let payload = match unchanged {
+        Some(previous) => previous,
+        None => {
+            if !response.status().is_success() {
+                return Err(PolyError::Status(response.status()));
+            }
+            let headers = response.headers().clone();
+            let body = response.text().await.map_err(PolyError::Request)?;
+            let payload = serde_json::from_str(&body).inspect_err(|e| {
+                let start: String = body.chars().take(200).collect();
+                warn!(%url, error = %e, body = %start, "response is not valid JSON");
+            })?;
+            conditional::remember(&url, &headers, &payload);
+            payload
+        }
+    };
+    tape::record(&url, &payload)?;
+    cache::store(&url, &payload);
+    Ok(payload)
+}
+
+// An authenticated CLOB call. Never cached, recorded or retried, so an order can't go out twice;
+// a refusal comes back as `Rejected` with the CLOB's own explanation.
+pub(crate) async fn send_json(
+    method: Method,
+    url: &str,
+    query: &[(&str, String)],
+    headers: &[(&'static str, String)],
+    body: Option<String>,
+) -> Result<Value, PolyError> {
+    let mut request = client()?
+        .request(method.clone(), url)
+        .query(query)
+        .header(USER_AGENT, "poly-cli-dashboard/1.0")
+        .header(ACCEPT, "application/json");
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+    if let Some(body) = body {
+        request = request.header(CONTENT_TYPE, "application/json").body(body);
+    }
+    throttle().await;
+
+    let started = Instant::now();
+    let response = request.send().await.map_err(PolyError::Request)?;
+    let status = response.status();
+    info!(%method, url, status = status.as_u16(), elapsed_ms = started.elapsed().as_millis() as u64, "CLOB");
+    let body = response.text().await.map_err(PolyError::Request)?;
+    if !status.is_success() {
+        let reason = serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|v| v.get("error").or_else(|| v.get("errorMsg")).and_then(Value::as_str).map(str::to_string));
+        return Err(match reason {
+            Some(reason) => PolyError::Rejected(reason),
+            None => PolyError::Status(status),
+        });
+    }
+    if body.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    Ok(serde_json::from_str(&body)?)
+}
+
+// A JSON-RPC request to a Polygon node. Hosted node URLs carry an API key, so like webhook URLs
+// they're kept out of logs and errors.
+pub(crate) async fn post_rpc(url: &str, body: &Value) -> Result<Value, PolyError> {
+    let started = Instant::now();
+    let response = client()?
+        .post(url)
+        .header(USER_AGENT, "poly-cli-dashboard/1.0")
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| PolyError::Request(e.without_url()))?;
+    let status = response.status();
+    info!(status = status.as_u16(), elapsed_ms = started.elapsed().as_millis() as u64, "RPC");
+    if !status.is_success() {
+        return Err(PolyError::Status(status));
+    }
+    response.json().await.map_err(|e| PolyError::Request(e.without_url()))
+}
+
+// Webhook and bot URLs embed their credentials, so they're stripped from any error we return.
+pub(crate) async fn post_json<T: Serialize + ?Sized>(url: &str, body: &T) -> Result<(), PolyError> {
+    let body = serde_json::to_string(body)?;
+    let response = client()?
+        .post(url)
+        .header(USER_AGENT, "poly-cli-dashboard/1.0")
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| PolyError::Request(e.without_url()))?;
+    info!(status = response.status().as_u16(), "POST to webhook");
+
+    if !response.status().is_success() {
+        return Err(PolyError::Status(response.status()));
+    }
+    Ok(())
+}
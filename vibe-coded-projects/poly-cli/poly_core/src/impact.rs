@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+use crate::clob::OrderBook;
+use crate::trading::Side;
+
+// What a market order of `size` shares would do to the book right now.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Impact {
+    pub side: Side,
+    pub size: f64,
+    // Less than `size` when the book runs out first.
+    pub filled: f64,
+    pub levels: usize,
+    pub midpoint: Option<f64>,
+    pub avg_price: Option<f64>,
+    pub worst_price: Option<f64>,
+    // USDC paid for a buy, received for a sell, before fees.
+    pub notional: f64,
+    // How much worse than the midpoint the average fill is, per share and as a percentage.
+    pub slippage: Option<f64>,
+    pub slippage_pct: Option<f64>,
+    pub fee: f64,
+}
+
+impl Impact {
+    // USDC out of pocket for a buy, or into it for a sell, after fees.
+    pub fn net(&self) -> f64 {
+        match self.side {
+            Side::Buy => self.notional + self.fee,
+            Side::Sell => self.notional - self.fee,
+        }
+    }
+}
+
+// Walks the asks (buy) or bids (sell) best-first until `size` shares are filled. Polymarket
+// charges takers `fee_rate_bps` scaled by min(price, 1 - price), so fees shrink towards 0 and 1.
+pub fn estimate_impact(book: &OrderBook, side: Side, size: f64, fee_rate_bps: u64) -> Impact {
+    let levels = match side {
+        Side::Buy => &book.asks,
+        Side::Sell => &book.bids,
+    };
+    let rate = fee_rate_bps as f64 / 10_000.0;
+    let (mut filled, mut notional, mut fee, mut used, mut worst) = (0.0, 0.0, 0.0, 0, None);
+    for level in levels {
+        if filled >= size {
+            break;
+        }
+        let take = level.size.min(size - filled);
+        filled += take;
+        notional += take * level.price;
+        fee += take * rate * level.price.min(1.0 - level.price);
+        used += 1;
+        worst = Some(level.price);
+    }
+
+    let midpoint = book.midpoint();
+    let avg_price = (filled > 0.0).then(|| notional / filled);
+    let slippage = match (avg_price, midpoint) {
+        (Some(avg), Some(mid)) => Some(match side {
+            Side::Buy => avg - mid,
+            Side::Sell => mid - avg,
+        }),
+        _ => None,
+    };
+    let slippage_pct = slippage.zip(midpoint).map(|(s, mid)| s / mid * 100.0);
+    Impact {
+        side,
+        size,
+        filled,
+        levels: used,
+        midpoint,
+        avg_price,
+        worst_price: worst,
+        notional,
+        slippage,
+        slippage_pct,
+        fee,
+    }
+}
@@ -0,0 +1,156 @@
+use serde::Deserialize;
+
+use crate::error::PolyError;
+use crate::fetch::{EventQuery, MarketStatus};
+use crate::http::get_json;
+use crate::model::{lenient_f64, Row};
+use crate::venue::Venue;
+
+pub const KALSHI_URL: &str = "https://api.elections.kalshi.com/trade-api/v2";
+
+// Kalshi caps event pages at 200.
+const PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct KalshiEvent {
+    event_ticker: String,
+    title: String,
+    category: Option<String>,
+    markets: Vec<KalshiMarket>,
+}
+
+// Prices are integer cents and liquidity is in cents. Volumes are contract counts, not dollars:
+// a contract pays out $1 but trades below that, so they run higher than the dollars traded.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct KalshiMarket {
+    ticker: String,
+    title: String,
+    yes_sub_title: Option<String>,
+    #[serde(deserialize_with = "lenient_f64")]
+    yes_bid: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    yes_ask: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    last_price: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    previous_price: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    volume: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    volume_24h: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    liquidity: Option<f64>,
+    close_time: Option<String>,
+    result: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EventsPage {
+    #[serde(default)]
+    events: Vec<KalshiEvent>,
+    #[serde(default)]
+    cursor: Option<String>,
+}
+
+fn cents(value: Option<f64>) -> Option<f64> {
+    value.map(|c| c / 100.0)
+}
+
+impl KalshiEvent {
+    fn accepts(&self, query: &EventQuery) -> bool {
+        let has = |tag: &String| self.category.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(tag));
+        (query.tags.is_empty() || query.tags.iter().any(has)) && !query.exclude_tags.iter().any(has)
+    }
+}
+
+impl KalshiMarket {
+    fn to_row(&self, event: &KalshiEvent) -> Row {
+        // Multi-market events share a title and tell their markets apart by the yes subtitle.
+        let title = match self.yes_sub_title.as_deref().filter(|s| !s.is_empty() && !self.title.contains(s)) {
+            Some(sub) => format!("{} — {sub}", self.title),
+            None => self.title.clone(),
+        };
+        // An empty side of the book is reported as a 0¢ bid or 100¢ ask.
+        let bid = cents(self.yes_bid).filter(|&b| b > 0.0);
+        let ask = cents(self.yes_ask).filter(|&a| a < 1.0);
+        let midpoint = bid.zip(ask).map(|(bid, ask)| (bid + ask) / 2.0);
+        let price = cents(self.last_price).filter(|&p| p > 0.0).or(midpoint);
+        let change = match (self.last_price, self.previous_price) {
+            (Some(last), Some(previous)) if previous > 0.0 => Some(last - previous),
+            _ => None,
+        };
+
+        Row {
+            event: event.title.clone(),
+            event_slug: Some(event.event_ticker.clone()),
+            title,
+            slug: Some(self.ticker.clone()),
+            token_id: None,
+            price,
+            volume: self.volume.unwrap_or(0.0),
+            volume_24h: self.volume_24h.unwrap_or(0.0),
+            change_24h_pct: change,
+            liquidity: cents(self.liquidity),
+            end_date: self.close_time.clone(),
+            resolution: self.result.clone().filter(|r| !r.is_empty()),
+            best_bid: bid,
+            best_ask: ask,
+            spread: bid.zip(ask).map(|(bid, ask)| ask - bid),
+            midpoint,
+            momentum: None,
+            spike: None,
+            venue: Venue::Kalshi,
+            outcome_prices: price.map(|p| vec![p, 1.0 - p]).unwrap_or_default(),
+            trend: Vec::new(),
+        }
+    }
+}
+
+// Kalshi markets in the shared row shape, paging through events until `query.limit` events are
+// collected. Tags are matched against Kalshi's event categories.
+pub async fn fetch_kalshi_markets(query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+    let status = match query.status {
+        MarketStatus::Open => Some("open"),
+        MarketStatus::All => None,
+        MarketStatus::Resolved => Some("settled"),
+    };
+
+    let mut events = Vec::new();
+    let mut cursor: Option<String> = None;
+    while events.len() < query.limit {
+        let mut params = vec![
+            ("with_nested_markets", "true".to_string()),
+            ("limit", PAGE_SIZE.min(query.limit - events.len()).to_string()),
+        ];
+        if let Some(status) = status {
+            params.push(("status", status.to_string()));
+        }
+        if let Some(cursor) = &cursor {
+            params.push(("cursor", cursor.clone()));
+        }
+
+        let payload = get_json(&format!("{KALSHI_URL}/events"), &params).await?;
+        if !payload.is_object() {
+            return Err(PolyError::Shape("expected Kalshi events object"));
+        }
+        let page: EventsPage = serde_json::from_value(payload)?;
+        let done = page.events.is_empty() || page.cursor.as_deref().is_none_or(str::is_empty);
+        events.extend(page.events);
+        if done {
+            break;
+        }
+        cursor = page.cursor;
+    }
+
+    let mut rows: Vec<Row> = events
+        .iter()
+        .filter(|event| event.accepts(query))
+        .flat_map(|event| event.markets.iter().map(move |market| market.to_row(event)))
+        .collect();
+    if query.status == MarketStatus::Resolved {
+        rows.retain(|row| row.resolution.is_some());
+    }
+    Ok(rows)
+}
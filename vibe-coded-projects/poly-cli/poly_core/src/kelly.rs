@@ -0,0 +1,53 @@
+use serde::Serialize;
+
+// Kelly sizing for a binary outcome bought at `price` that you think wins with `prob`. When
+// `prob` is below the price the edge is in the other outcome, bought at 1 - price.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Kelly {
+    pub prob: f64,
+    pub price: f64,
+    // Whether the edge is in the outcome itself rather than its complement.
+    pub buy_outcome: bool,
+    // Expected profit per $1 staked on the side with the edge.
+    pub edge: f64,
+    // Share of bankroll full Kelly stakes; 0 when there's no edge.
+    pub fraction: f64,
+}
+
+// One fraction of Kelly applied to a bankroll.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stake {
+    pub multiplier: f64,
+    pub fraction: f64,
+    pub stake: f64,
+    pub shares: f64,
+    pub expected_profit: f64,
+}
+
+impl Kelly {
+    // Both arguments must lie strictly between 0 and 1.
+    pub fn new(prob: f64, price: f64) -> Kelly {
+        let buy_outcome = prob >= price;
+        let (win, cost) = if buy_outcome { (prob, price) } else { (1.0 - prob, 1.0 - price) };
+        // f* = (p·b − q) / b with net odds b = (1 − cost) / cost simplifies to this.
+        let fraction = ((win - cost) / (1.0 - cost)).max(0.0);
+        Kelly { prob, price, buy_outcome, edge: win / cost - 1.0, fraction }
+    }
+
+    // Price of the side with the edge.
+    pub fn cost(&self) -> f64 {
+        if self.buy_outcome {
+            self.price
+        } else {
+            1.0 - self.price
+        }
+    }
+
+    pub fn stake(&self, bankroll: f64, multiplier: f64) -> Stake {
+        let fraction = self.fraction * multiplier;
+        let stake = bankroll * fraction;
+        Stake { multiplier, fraction, stake, shares: stake / self.cost(), expected_profit: stake * self.edge }
+    }
+}
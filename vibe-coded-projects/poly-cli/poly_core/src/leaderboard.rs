@@ -0,0 +1,164 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::error::PolyError;
+use crate::http::get_json;
+use crate::model::lenient_f64;
+
+pub const LEADERBOARD_URL: &str = "https://lb-api.polymarket.com";
+
+// The periods the leaderboard is kept for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardWindow {
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl LeaderboardWindow {
+    fn as_str(self) -> &'static str {
+        match self {
+            LeaderboardWindow::Day => "1d",
+            LeaderboardWindow::Week => "7d",
+            LeaderboardWindow::Month => "30d",
+            LeaderboardWindow::All => "all",
+        }
+    }
+}
+
+impl FromStr for LeaderboardWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "1d" | "24h" | "day" => Ok(LeaderboardWindow::Day),
+            "7d" | "1w" | "week" => Ok(LeaderboardWindow::Week),
+            "30d" | "1m" | "month" => Ok(LeaderboardWindow::Month),
+            "all" => Ok(LeaderboardWindow::All),
+            other => Err(format!("unknown window '{other}' (expected 1d, 7d, 30d or all)")),
+        }
+    }
+}
+
+impl fmt::Display for LeaderboardWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LeaderboardMetric {
+    #[default]
+    Profit,
+    Volume,
+}
+
+impl LeaderboardMetric {
+    fn path(self) -> &'static str {
+        match self {
+            LeaderboardMetric::Profit => "profit",
+            LeaderboardMetric::Volume => "volume",
+        }
+    }
+}
+
+impl FromStr for LeaderboardMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "profit" | "pnl" => Ok(LeaderboardMetric::Profit),
+            "volume" | "vol" => Ok(LeaderboardMetric::Volume),
+            other => Err(format!("unknown ranking '{other}' (expected profit or volume)")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Ranked {
+    proxy_wallet: Option<String>,
+    #[serde(deserialize_with = "lenient_f64")]
+    amount: Option<f64>,
+    name: Option<String>,
+    pseudonym: Option<String>,
+}
+
+// One trader's row: their place in the chosen ranking, and both figures where known.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub proxy_wallet: String,
+    pub name: Option<String>,
+    pub profit: Option<f64>,
+    pub volume: Option<f64>,
+}
+
+async fn fetch_ranked(
+    window: LeaderboardWindow,
+    metric: LeaderboardMetric,
+    limit: usize,
+) -> Result<Vec<Ranked>, PolyError> {
+    let payload = get_json(
+        &format!("{LEADERBOARD_URL}/{}", metric.path()),
+        &[("window", window.as_str().to_string()), ("limit", limit.to_string())],
+    )
+    .await?;
+
+    match payload {
+        Value::Array(_) => Ok(serde_json::from_value(payload)?),
+        _ => Err(PolyError::Shape("expected array")),
+    }
+}
+
+// The top `limit` traders by `metric`. The other figure comes from the other ranking, so it's
+// only known for traders who place in both.
+pub async fn fetch_leaderboard(
+    window: LeaderboardWindow,
+    metric: LeaderboardMetric,
+    limit: usize,
+) -> Result<Vec<LeaderboardEntry>, PolyError> {
+    let other = match metric {
+        LeaderboardMetric::Profit => LeaderboardMetric::Volume,
+        LeaderboardMetric::Volume => LeaderboardMetric::Profit,
+    };
+    let (ranked, others) = tokio::join!(fetch_ranked(window, metric, limit), fetch_ranked(window, other, limit));
+    let ranked = ranked?;
+    // The second ranking only fills in a column; losing it isn't worth failing over.
+    let others = others.unwrap_or_else(|e| {
+        warn!(error = %e, ranking = other.path(), "leaderboard column left blank");
+        Vec::new()
+    });
+
+    let entries = ranked
+        .into_iter()
+        .filter_map(|trader| {
+            let wallet = trader.proxy_wallet?;
+            let paired = others
+                .iter()
+                .find(|o| o.proxy_wallet.as_deref().is_some_and(|w| w.eq_ignore_ascii_case(&wallet)))
+                .and_then(|o| o.amount);
+            let (profit, volume) = match metric {
+                LeaderboardMetric::Profit => (trader.amount, paired),
+                LeaderboardMetric::Volume => (paired, trader.amount),
+            };
+            let name = trader.name.filter(|n| !n.is_empty()).or(trader.pseudonym);
+            Some((wallet, name, profit, volume))
+        })
+        .enumerate()
+        .map(|(idx, (proxy_wallet, name, profit, volume))| LeaderboardEntry {
+            rank: idx + 1,
+            proxy_wallet,
+            name,
+            profit,
+            volume,
+        })
+        .collect();
+    Ok(entries)
+}
@@ -0,0 +1,116 @@
+//! Core Polymarket market fetching and formatting used by the `polymarket-dashboard` binary.
+
+pub mod alert;
+pub mod arb;
+pub mod auth;
+pub mod balance;
+pub mod cache;
+pub mod clob;
+pub mod columns;
+pub mod comments;
+mod conditional;
+pub mod correlate;
+pub mod data_api;
+pub mod db;
+pub mod diff;
+pub mod ens;
+pub mod error;
+pub mod fetch;
+pub mod filter;
+pub mod format;
+pub mod fresh;
+pub mod html;
+pub mod impact;
+pub mod kalshi;
+pub mod kelly;
+pub mod leaderboard;
+mod http;
+pub mod manifold;
+pub mod model;
+pub mod output;
+pub mod pnl;
+pub mod query;
+pub mod render;
+pub mod resolved;
+pub mod rpc;
+pub mod search;
+pub mod sort;
+pub mod spike;
+pub mod sports;
+pub mod stream;
+pub mod tags;
+pub mod tape;
+pub mod transport;
+pub mod trading;
+pub mod trend;
+pub mod venue;
+pub mod verify;
+pub mod wallet;
+pub mod webhook;
+
+pub use alert::{Alert, AlertEngine, AlertField, AlertRule, CompareOp, Trigger};
+pub use arb::{find_mispricings, ArbSide, Mispricing};
+pub use auth::{create_api_key, derive_api_key, ApiCreds, ClobAuth};
+pub use balance::{fetch_funding, Approval, Funding, CONDITIONAL_TOKENS, USDC};
+pub use clob::{fetch_orderbook, fetch_price_history, Level, OrderBook, PricePoint, CLOB_URL};
+pub use columns::{select, Column, Selected};
+pub use comments::{fetch_comments, Comment, CommentProfile, COMMENTS_URL};
+pub use correlate::{align_histories, differences, pearson, Aligned};
+pub use data_api::{
+    fetch_holders, fetch_open_interest, fetch_positions, fetch_trades, fetch_user_trades, Holder, Position, Trade,
+    DATA_API_URL,
+};
+pub use db::SnapshotDb;
+pub use diff::{diff_snapshots, parse_snapshot, MarketDelta, SnapshotDiff, SnapshotRow};
+pub use ens::{lookup_ens_names, reverse_lookup, DEFAULT_ENS_RPC_URL};
+pub use error::{ErrorReport, PolyError};
+pub use fetch::{
+    attach_quotes, fetch_event, fetch_events, fetch_events_with, fetch_market, fetch_market_rows, fetch_markets,
+    fetch_markets_by_token, fetch_markets_with, rows_from_events, EventOrder, EventQuery, MarketStatus, BASE_URL,
+    MARKETS_URL,
+};
+pub use filter::{PriceBand, RowFilter};
+pub use format::{
+    configure_display, display_options, format_age, format_end, format_money, format_percent, format_price,
+    format_span, paint, sparkline, supports_color, terminal_width, DisplayOptions, DisplayZone, C,
+};
+pub use fresh::{fetch_new_markets, NewMarket};
+pub use kalshi::{fetch_kalshi_markets, KALSHI_URL};
+pub use kelly::{Kelly, Stake};
+pub use leaderboard::{fetch_leaderboard, LeaderboardEntry, LeaderboardMetric, LeaderboardWindow, LEADERBOARD_URL};
+pub use manifold::{fetch_manifold_markets, MANIFOLD_URL};
+pub use model::{Event, Market, Row, Tag};
+pub use html::render_html;
+pub use impact::{estimate_impact, Impact};
+pub use http::{configure_http, HttpOptions};
+pub use output::{append_csv_log, read_csv_log_volumes, write_csv, write_ndjson, OutputFormat};
+pub use pnl::{compute_pnl, MarketPnl};
+pub use query::{Condition, Query};
+pub use render::{
+    render_arb, render_comment_line, render_comparison, render_event_detail, render_holders, render_leaderboard,
+    render_market_detail, render_matchups, render_new_markets, render_open_orders, render_orderbook, render_pnl,
+    render_positions, render_price_chart, render_resolutions, render_snapshot_diff, render_table, render_table_with,
+    render_tags, render_trade_line, render_venue_matches, render_xarb, TableLayout, CLOSING_SOON_HOURS, SPARKLINE_WIDTH,
+};
+pub use resolved::{fetch_resolutions, Resolution};
+pub use rpc::{JsonRpc, DEFAULT_RPC_URL};
+pub use search::{search_rows, Matcher};
+pub use sort::{compare_rows, sort_rows, SortKey, SortSpec};
+pub use spike::{attach_spikes, VolumeBaseline, VolumeSample, BASELINE_DAYS};
+pub use sports::{fetch_leagues, fetch_matchups, League, Matchup, SPORTS_URL};
+pub use stream::{MarketChannel, PriceUpdate, WS_MARKET_URL};
+pub use tags::{summarize_tags, TagSummary};
+pub use transport::{FixtureFetcher, HttpFetcher, ReqwestFetcher};
+pub use trading::{
+    attach_order_titles, cancel_all_orders, cancel_order, fetch_open_orders, fetch_order_market, post_order,
+    sign_order, sign_order_with_salt, Cancelled, OpenOrder, OrderMarket, OrderRequest, PlacedOrder, Quote, Side,
+    SignatureType, SignedOrder,
+};
+pub use trend::{attach_momentum, attach_trends, HistoryCache, Momentum};
+pub use venue::{
+    fetch_venue_markets, find_price_gaps, match_markets, title_similarity, Kalshi, Manifold, MarketSource, Polymarket,
+    Venue, VenueMatch,
+};
+pub use verify::{fetch_payouts, verify_market, Verdict, Verification};
+pub use wallet::{Wallet, CHAIN_ID};
+pub use webhook::{post_webhook, WebhookEvent};
@@ -0,0 +1,125 @@
+use chrono::{DateTime, SecondsFormat};
+use serde::Deserialize;
+
+use crate::error::PolyError;
+use crate::fetch::{EventQuery, MarketStatus};
+use crate::http::get_json;
+use crate::model::{lenient_f64, Row};
+use crate::venue::Venue;
+
+pub const MANIFOLD_URL: &str = "https://api.manifold.markets/v0";
+
+// Manifold caps search pages at 1000.
+const PAGE_SIZE: usize = 1000;
+
+// Volumes and liquidity are play money (mana), shown with Ṁ rather than $.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ManifoldMarket {
+    question: String,
+    slug: String,
+    creator_username: String,
+    #[serde(deserialize_with = "lenient_f64")]
+    probability: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    volume: Option<f64>,
+    #[serde(rename = "volume24Hours", deserialize_with = "lenient_f64")]
+    volume_24h: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    total_liquidity: Option<f64>,
+    close_time: Option<i64>,
+    resolution: Option<String>,
+    // Topic slugs, which is what --tag and --exclude-tag name.
+    group_slugs: Vec<String>,
+}
+
+impl ManifoldMarket {
+    fn accepts(&self, query: &EventQuery) -> bool {
+        let has = |tag: &String| self.group_slugs.iter().any(|slug| slug.eq_ignore_ascii_case(tag));
+        (query.tags.is_empty() || query.tags.iter().any(has)) && !query.exclude_tags.iter().any(has)
+    }
+
+    fn to_row(&self) -> Row {
+        // Manifold has no events; each question stands alone.
+        let end_date = self
+            .close_time
+            .and_then(DateTime::from_timestamp_millis)
+            .map(|at| at.to_rfc3339_opts(SecondsFormat::Secs, true));
+        // Only YES/NO resolutions name a winner; MKT and CANCEL don't.
+        let resolution = self.resolution.as_deref().and_then(|r| match r {
+            "YES" => Some("Yes".to_string()),
+            "NO" => Some("No".to_string()),
+            _ => None,
+        });
+
+        Row {
+            event: self.question.clone(),
+            // Each question is its own event.
+            event_slug: Some(self.slug.clone()),
+            title: self.question.clone(),
+            // Market pages live under the creator's username.
+            slug: Some(format!("{}/{}", self.creator_username, self.slug)),
+            token_id: None,
+            price: self.probability,
+            volume: self.volume.unwrap_or(0.0),
+            volume_24h: self.volume_24h.unwrap_or(0.0),
+            change_24h_pct: None,
+            liquidity: self.total_liquidity,
+            end_date,
+            resolution,
+            best_bid: None,
+            best_ask: None,
+            spread: None,
+            midpoint: None,
+            momentum: None,
+            spike: None,
+            venue: Venue::Manifold,
+            outcome_prices: self.probability.map(|p| vec![p, 1.0 - p]).unwrap_or_default(),
+            trend: Vec::new(),
+        }
+    }
+}
+
+// Binary Manifold markets in the shared row shape, busiest first, at most `query.limit` of them.
+// Manifold filters by a single topic, so that's sent for a lone --tag; several tags and any
+// --exclude-tag are applied to each page as it arrives.
+pub async fn fetch_manifold_markets(query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+    let filter = match query.status {
+        MarketStatus::Open => "open",
+        MarketStatus::All => "all",
+        MarketStatus::Resolved => "resolved",
+    };
+
+    let mut markets: Vec<ManifoldMarket> = Vec::new();
+    while markets.len() < query.limit {
+        let page_size = PAGE_SIZE.min(query.limit - markets.len());
+        let mut params = vec![
+            ("term", String::new()),
+            ("sort", "24-hour-vol".to_string()),
+            ("filter", filter.to_string()),
+            ("contractType", "BINARY".to_string()),
+            ("limit", page_size.to_string()),
+            ("offset", markets.len().to_string()),
+        ];
+        if let [tag] = query.tags.as_slice() {
+            params.push(("topicSlug", tag.to_lowercase()));
+        }
+
+        let payload = get_json(&format!("{MANIFOLD_URL}/search-markets"), &params).await?;
+        if !payload.is_array() {
+            return Err(PolyError::Shape("expected Manifold markets list"));
+        }
+        let page: Vec<ManifoldMarket> = serde_json::from_value(payload)?;
+        let done = page.len() < page_size;
+        markets.extend(page.into_iter().filter(|market| market.accepts(query)));
+        if done {
+            break;
+        }
+    }
+
+    let mut rows: Vec<Row> = markets.iter().map(ManifoldMarket::to_row).collect();
+    if query.status == MarketStatus::Resolved {
+        rows.retain(|row| row.resolution.is_some());
+    }
+    Ok(rows)
+}
@@ -0,0 +1,380 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::trend::Momentum;
+use crate::venue::Venue;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Event {
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    pub end_date: Option<String>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub volume: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub liquidity: Option<f64>,
+    #[serde(deserialize_with = "null_default")]
+    pub tags: Vec<Tag>,
+    #[serde(deserialize_with = "null_default")]
+    pub markets: Vec<Market>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tag {
+    pub id: Option<String>,
+    pub label: Option<String>,
+    pub slug: Option<String>,
+}
+
+// Gamma sends several overlapping volume/change fields (often both as numbers and strings),
+// so each one is kept separately and resolved by the accessors below rather than aliased.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Market {
+    pub id: Option<String>,
+    pub condition_id: Option<String>,
+    pub question: Option<String>,
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    #[serde(deserialize_with = "json_list")]
+    pub outcomes: Vec<String>,
+    #[serde(deserialize_with = "json_price_list")]
+    pub outcome_prices: Vec<f64>,
+    #[serde(deserialize_with = "json_list")]
+    pub clob_token_ids: Vec<String>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub best_bid: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub best_ask: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub last_trade_price: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub liquidity_num: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub liquidity: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub volume_num: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub volume: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub volume_clob: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub volume_amm: Option<f64>,
+    #[serde(rename = "volume24hr", deserialize_with = "lenient_f64")]
+    pub volume_24hr: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub one_day_price_change: Option<f64>,
+    #[serde(deserialize_with = "lenient_f64")]
+    pub one_day_price_change_percent: Option<f64>,
+    pub end_date_iso: Option<String>,
+    pub end_date: Option<String>,
+    pub closed: Option<bool>,
+    pub uma_resolution_status: Option<String>,
+    pub created_at: Option<String>,
+    pub closed_time: Option<String>,
+    // Sports markets only: kick-off and the kind of line (moneyline, spreads, totals).
+    pub game_start_time: Option<String>,
+    pub sports_market_type: Option<String>,
+    // Parent events; only embedded when the market is fetched on its own via /markets.
+    #[serde(deserialize_with = "null_default")]
+    pub events: Vec<Event>,
+}
+
+impl Event {
+    pub fn display_title(&self) -> &str {
+        self.title
+            .as_deref()
+            .or(self.slug.as_deref())
+            .unwrap_or("Untitled Event")
+    }
+
+    pub fn has_tag(&self, wanted: &str) -> bool {
+        self.tags.iter().any(|tag| {
+            [tag.slug.as_deref(), tag.label.as_deref()]
+                .into_iter()
+                .flatten()
+                .any(|name| name.eq_ignore_ascii_case(wanted))
+        })
+    }
+}
+
+impl Market {
+    pub fn display_title(&self) -> &str {
+        self.question
+            .as_deref()
+            .or(self.title.as_deref())
+            .or(self.slug.as_deref())
+            .unwrap_or("Untitled Market")
+    }
+
+    pub fn total_volume(&self) -> f64 {
+        self.volume_num
+            .or(self.volume)
+            .or(self.volume_clob)
+            .or(self.volume_amm)
+            .unwrap_or(0.0)
+    }
+
+    pub fn liquidity(&self) -> Option<f64> {
+        self.liquidity_num.or(self.liquidity)
+    }
+
+    // Pairs each outcome label with its price; missing prices are reported as None.
+    pub fn outcome_quotes(&self) -> Vec<(&str, Option<f64>)> {
+        self.outcomes
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), self.outcome_prices.get(i).copied()))
+            .collect()
+    }
+
+    pub fn change_24h_pct(&self) -> Option<f64> {
+        self.one_day_price_change
+            .or(self.one_day_price_change_percent)
+            .map(normalize_change)
+    }
+
+    // When trading stopped: the close time once known, otherwise the scheduled end.
+    pub fn closed_at(&self) -> Option<DateTime<Utc>> {
+        self.closed_time.as_deref().and_then(parse_time).or_else(|| parse_time(self.end()?))
+    }
+
+    // A closed market settles with the winning outcome priced at (or within rounding of) 1.0.
+    pub fn resolved_outcome(&self) -> Option<&str> {
+        if self.closed != Some(true) {
+            return None;
+        }
+        self.outcome_quotes()
+            .into_iter()
+            .find(|(_, price)| price.is_some_and(|p| p >= 0.99))
+            .map(|(name, _)| name)
+    }
+
+    pub fn end(&self) -> Option<&str> {
+        self.end_date_iso.as_deref().or(self.end_date.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Row {
+    pub event: String,
+    // The event's own key on its venue: Polymarket's event slug or Kalshi's event ticker. Titles
+    // can repeat across events, so rows are grouped by event on this instead.
+    #[serde(skip)]
+    pub event_slug: Option<String>,
+    pub title: String,
+    pub slug: Option<String>,
+    #[serde(rename = "tokenId")]
+    pub token_id: Option<String>,
+    pub price: Option<f64>,
+    pub volume: f64,
+    #[serde(rename = "volume24h")]
+    pub volume_24h: f64,
+    #[serde(rename = "change24hPct")]
+    pub change_24h_pct: Option<f64>,
+    pub liquidity: Option<f64>,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<String>,
+    pub resolution: Option<String>,
+    // Top of the CLOB book for the first outcome; only filled when quotes are requested.
+    #[serde(rename = "bestBid")]
+    pub best_bid: Option<f64>,
+    #[serde(rename = "bestAsk")]
+    pub best_ask: Option<f64>,
+    pub spread: Option<f64>,
+    pub midpoint: Option<f64>,
+    // Last hour's move against the last 24h; only filled when momentum is requested.
+    pub momentum: Option<Momentum>,
+    // 24h volume as a multiple of the trailing daily average; only set when it crosses the
+    // requested spike threshold.
+    pub spike: Option<f64>,
+    pub venue: Venue,
+    // Every outcome's price in feed order; `price` is the first of these.
+    #[serde(skip)]
+    pub outcome_prices: Vec<f64>,
+    // Recent prices, oldest first, for the sparkline column; display-only and filled on request.
+    #[serde(skip)]
+    pub trend: Vec<f64>,
+}
+
+impl Row {
+    // Link to the market on its venue's site.
+    pub fn url(&self) -> Option<String> {
+        self.slug.as_deref().map(|slug| self.venue.market_url(slug))
+    }
+
+    pub fn from_market(event: &Event, market: &Market) -> Self {
+        let event_title = event.display_title();
+        let title = market
+            .question
+            .as_deref()
+            .or(market.title.as_deref())
+            .or(market.slug.as_deref())
+            .unwrap_or(event_title)
+            .to_string();
+
+        Row {
+            event: event_title.to_string(),
+            event_slug: event.slug.clone().or_else(|| event.id.clone()),
+            title,
+            slug: market.slug.clone().or_else(|| event.slug.clone()),
+            token_id: market.clob_token_ids.first().cloned(),
+            price: market.outcome_prices.first().copied(),
+            volume: market.total_volume(),
+            volume_24h: market.volume_24hr.unwrap_or(0.0),
+            change_24h_pct: market.change_24h_pct(),
+            liquidity: market.liquidity(),
+            end_date: market.end().map(str::to_string),
+            resolution: market.resolved_outcome().map(str::to_string),
+            best_bid: None,
+            best_ask: None,
+            spread: None,
+            midpoint: None,
+            momentum: None,
+            spike: None,
+            venue: Venue::Polymarket,
+            outcome_prices: market.outcome_prices.clone(),
+            trend: Vec::new(),
+        }
+    }
+}
+
+impl Row {
+    pub fn end_time(&self) -> Option<DateTime<Utc>> {
+        parse_time(self.end_date.as_deref()?)
+    }
+
+    // Still open but ending within `window` from now.
+    pub fn ends_within(&self, window: Duration) -> bool {
+        let now = Utc::now();
+        let limit = now.checked_add_signed(window);
+        self.end_time().is_some_and(|end| end >= now && limit.is_none_or(|limit| end <= limit))
+    }
+}
+
+// Gamma mixes RFC 3339 timestamps with Postgres-style ones such as `2024-11-06 05:30:07+00`.
+pub(crate) fn parse_time(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    DateTime::parse_from_rfc3339(text)
+        .or_else(|_| DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z"))
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+fn normalize_change(val: f64) -> f64 {
+    if (-1.0..=1.0).contains(&val) {
+        val * 100.0
+    } else {
+        val
+    }
+}
+
+pub(crate) fn lenient_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Num {
+        Number(f64),
+        Text(String),
+    }
+
+    Ok(match Option::<Num>::deserialize(deserializer)? {
+        None => None,
+        Some(Num::Number(n)) => Some(n),
+        Some(Num::Text(s)) if s.trim().is_empty() => None,
+        Some(Num::Text(s)) => s
+            .trim()
+            .parse::<f64>()
+            .inspect_err(|_| warn!(value = %s, "ignoring a non-numeric number field"))
+            .ok(),
+    })
+}
+
+// Ids that some endpoints send as numbers and others as strings.
+pub(crate) fn lenient_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) => Some(s),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+fn null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+// Like `lenient_f64`, but for fields that must be present and numeric (CLOB price levels).
+pub(crate) fn string_f64<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    lenient_f64(deserializer)?.ok_or_else(|| serde::de::Error::custom("expected a number"))
+}
+
+// Gamma encodes list fields such as `outcomes` as a JSON string ("[\"Yes\", \"No\"]"),
+// but a plain array is accepted too.
+fn json_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        List(Vec<String>),
+        Encoded(String),
+    }
+
+    match Option::<Raw>::deserialize(deserializer)? {
+        None => Ok(Vec::new()),
+        Some(Raw::List(items)) => Ok(items),
+        Some(Raw::Encoded(text)) if text.trim().is_empty() => Ok(Vec::new()),
+        Some(Raw::Encoded(text)) => serde_json::from_str(&text).map_err(serde::de::Error::custom),
+    }
+}
+
+// Prices line up with `outcomes` by index, so a malformed one can't simply be dropped: the list
+// stops at it, leaving that outcome and the ones after it without a price rather than failing
+// the whole market.
+fn json_price_list<'de, D>(deserializer: D) -> Result<Vec<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let items = match Option::<Value>::deserialize(deserializer)? {
+        None => return Ok(Vec::new()),
+        Some(Value::Array(items)) => items,
+        Some(Value::String(text)) if text.trim().is_empty() => return Ok(Vec::new()),
+        Some(Value::String(text)) => serde_json::from_str(&text).map_err(serde::de::Error::custom)?,
+        Some(other) => {
+            return Err(serde::de::Error::custom(format!("expected price list, got {other}")))
+        }
+    };
+
+    let prices: Vec<f64> = items
+        .iter()
+        .map_while(|item| match item {
+            Value::Number(n) => n.as_f64(),
+            Value::String(p) => p.trim().parse::<f64>().ok().filter(|p| p.is_finite()),
+            _ => None,
+        })
+        .collect();
+    if prices.len() < items.len() {
+        warn!(value = %items[prices.len()], "dropping outcome prices from a non-numeric entry onward");
+    }
+    Ok(prices)
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::{parse_time, Row};
+use crate::spike::VolumeSample;
+use crate::trend::Momentum;
+use crate::venue::Venue;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Html,
+    Ndjson,
+    Yaml,
+}
+
+impl OutputFormat {
+    // Formats that only make sense for flat lists of rows, not nested documents.
+    pub const ROW_ONLY: &'static [OutputFormat] = &[OutputFormat::Csv, OutputFormat::Html, OutputFormat::Ndjson];
+
+    // Formats that can keep appending output across watch-mode refreshes.
+    pub fn is_continuous(self) -> bool {
+        matches!(self, OutputFormat::Table | OutputFormat::Ndjson)
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "html" => Ok(OutputFormat::Html),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            other => Err(format!(
+                "unknown format '{other}' (expected table, json, csv, html, ndjson, yaml)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Html => "html",
+            OutputFormat::Ndjson => "ndjson",
+            OutputFormat::Yaml => "yaml",
+        };
+        f.write_str(name)
+    }
+}
+
+// Header comes from the serde field names, so CSV columns match the JSON keys.
+pub fn write_csv<T: Serialize, W: io::Write>(items: &[T], out: W) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_writer(out);
+    for item in items {
+        writer.serialize(item)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// The columns of a `--log-csv` line that volume history needs.
+#[derive(Deserialize)]
+struct LoggedVolume {
+    timestamp: String,
+    slug: Option<String>,
+    volume: f64,
+}
+
+// Every market's lifetime volume at each refresh logged to `path` between `since` and `until`.
+// Lines that don't parse, such as ones cut short by a crash mid-write, are skipped.
+pub fn read_csv_log_volumes(
+    path: &Path,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Vec<VolumeSample>, csv::Error> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let samples = reader
+        .deserialize::<LoggedVolume>()
+        .filter_map(Result::ok)
+        .filter_map(|line| {
+            let taken_at = parse_time(&line.timestamp)?;
+            let slug = line.slug.filter(|slug| !slug.is_empty())?;
+            (since <= taken_at && taken_at <= until).then_some(VolumeSample { slug, taken_at, volume: line.volume })
+        })
+        .collect();
+    Ok(samples)
+}
+
+// One JSON object per line. Each object gets a `timestamp` field so snapshots appended over
+// a watch session can be told apart downstream.
+pub fn write_ndjson<T: Serialize, W: io::Write>(items: &[T], timestamp: &str, mut out: W) -> io::Result<()> {
+    for item in items {
+        let mut value = serde_json::to_value(item)?;
+        if let Value::Object(map) = &mut value {
+            map.insert("timestamp".to_string(), Value::String(timestamp.to_string()));
+        }
+        serde_json::to_writer(&mut out, &value)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+// One market at one refresh in a `--log-csv` file. The columns are fixed rather than following
+// `Row`, so a log keeps one header however long it grows across upgrades.
+#[derive(Serialize)]
+struct LogLine<'a> {
+    timestamp: &'a str,
+    venue: Venue,
+    slug: Option<&'a str>,
+    event: &'a str,
+    title: &'a str,
+    price: Option<f64>,
+    volume: f64,
+    #[serde(rename = "volume24h")]
+    volume_24h: f64,
+    #[serde(rename = "change24hPct")]
+    change_24h_pct: Option<f64>,
+    liquidity: Option<f64>,
+    #[serde(rename = "endDate")]
+    end_date: Option<&'a str>,
+    resolution: Option<&'a str>,
+    momentum: Option<Momentum>,
+}
+
+// Appends one line per row, all stamped with `timestamp`, to the CSV file at `path`. The header
+// is written only when the file is new or empty.
+pub fn append_csv_log(path: &Path, rows: &[Row], timestamp: &str) -> Result<(), csv::Error> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let is_new = file.metadata()?.len() == 0;
+    let mut writer = csv::WriterBuilder::new().has_headers(is_new).from_writer(file);
+    for row in rows {
+        writer.serialize(LogLine {
+            timestamp,
+            venue: row.venue,
+            slug: row.slug.as_deref(),
+            event: &row.event,
+            title: &row.title,
+            price: row.price,
+            volume: row.volume,
+            volume_24h: row.volume_24h,
+            change_24h_pct: row.change_24h_pct,
+            liquidity: row.liquidity,
+            end_date: row.end_date.as_deref(),
+            resolution: row.resolution.as_deref(),
+            momentum: row.momentum,
+        })?;
+    }
+    writer.flush()?;
+    Ok(())
+}
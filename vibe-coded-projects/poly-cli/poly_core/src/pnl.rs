@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::data_api::{Position, Trade};
+
+// Realized and unrealized PnL for one outcome token a wallet has traded.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketPnl {
+    pub asset: String,
+    pub title: String,
+    pub outcome: Option<String>,
+    pub shares: f64,
+    pub avg_cost: Option<f64>,
+    pub realized: f64,
+    pub unrealized: f64,
+}
+
+impl MarketPnl {
+    pub fn total(&self) -> f64 {
+        self.realized + self.unrealized
+    }
+}
+
+// Running average-cost book for one token.
+#[derive(Default)]
+struct Book {
+    shares: f64,
+    cost: f64,
+}
+
+// Replays fills oldest-first on an average-cost basis: buys raise the cost basis, sells realize the
+// difference between sale price and average cost. Only sells at or after `since` (unix seconds)
+// count toward realized PnL, but earlier fills still shape the cost basis. Unrealized PnL comes
+// from the current positions. Redemptions of resolved markets are not trades and aren't included.
+pub fn compute_pnl(trades: &[Trade], positions: &[Position], since: Option<i64>) -> Vec<MarketPnl> {
+    let mut ordered: Vec<&Trade> = trades.iter().filter(|t| t.asset.is_some()).collect();
+    ordered.sort_by_key(|t| t.timestamp.unwrap_or(0));
+
+    let mut books: HashMap<&str, Book> = HashMap::new();
+    let mut results: HashMap<String, MarketPnl> = HashMap::new();
+
+    for trade in ordered {
+        let asset = trade.asset.as_deref().unwrap_or_default();
+        let (size, price) = (trade.size.unwrap_or(0.0), trade.price.unwrap_or(0.0));
+        let book = books.entry(asset).or_default();
+        let entry = results.entry(asset.to_string()).or_insert_with(|| MarketPnl {
+            asset: asset.to_string(),
+            title: trade.title.clone().or_else(|| trade.slug.clone()).unwrap_or_default(),
+            outcome: trade.outcome.clone(),
+            ..MarketPnl::default()
+        });
+
+        if trade.is_buy() {
+            book.shares += size;
+            book.cost += size * price;
+        } else if book.shares > 0.0 {
+            let sold = size.min(book.shares);
+            let avg = book.cost / book.shares;
+            if since.is_none_or(|since| trade.timestamp.unwrap_or(0) >= since) {
+                entry.realized += sold * (price - avg);
+            }
+            book.cost -= sold * avg;
+            book.shares -= sold;
+        }
+    }
+
+    for (asset, book) in &books {
+        if let Some(entry) = results.get_mut(*asset) {
+            entry.shares = book.shares;
+            entry.avg_cost = (book.shares > 0.0).then(|| book.cost / book.shares);
+        }
+    }
+
+    for position in positions {
+        let Some(asset) = position.asset.clone() else {
+            continue;
+        };
+        let entry = results.entry(asset.clone()).or_insert_with(|| MarketPnl {
+            asset,
+            title: position.title.clone().or_else(|| position.slug.clone()).unwrap_or_default(),
+            outcome: position.outcome.clone(),
+            ..MarketPnl::default()
+        });
+        entry.shares = position.size.unwrap_or(entry.shares);
+        entry.avg_cost = position.avg_price.or(entry.avg_cost);
+        entry.unrealized = position.unrealized_pnl();
+    }
+
+    let mut results: Vec<MarketPnl> = results.into_values().collect();
+    results.sort_by(|a, b| b.total().abs().total_cmp(&a.total().abs()));
+    results
+}
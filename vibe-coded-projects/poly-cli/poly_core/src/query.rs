@@ -0,0 +1,123 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+use crate::alert::{parse_threshold, CompareOp};
+
+// The right-hand side of a `--where`: a number compares numerically, anything else is matched
+// as text, case-insensitively, so `venue == kalshi` works.
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Number(f64),
+    Text(String),
+}
+
+// One `--where` expression over a serialized field, e.g. `volume24h > 10k` or `venue != kalshi`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    field: String,
+    op: CompareOp,
+    operand: Operand,
+    source: String,
+}
+
+impl Condition {
+    // Objects without the field, or with it null, never match.
+    pub fn matches(&self, item: &Value) -> bool {
+        let Some(value) = item.get(&self.field).filter(|v| !v.is_null()) else {
+            return false;
+        };
+        match &self.operand {
+            Operand::Number(rhs) => value.as_f64().is_some_and(|lhs| self.op.apply(lhs, *rhs)),
+            Operand::Text(rhs) => {
+                let lhs = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                lhs.eq_ignore_ascii_case(rhs) == (self.op == CompareOp::Eq)
+            }
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (field, op, rhs) =
+            CompareOp::split(s).ok_or_else(|| format!("where '{s}' needs a comparison (>, >=, <, <=, ==, !=)"))?;
+        let field = field.trim();
+        if field.is_empty() || field.contains(char::is_whitespace) {
+            return Err(format!("where '{s}' needs a single field name before the comparison"));
+        }
+        let rhs = rhs.trim();
+        let operand = match parse_threshold(rhs) {
+            Ok(number) => Operand::Number(number),
+            Err(_) if matches!(op, CompareOp::Eq | CompareOp::Ne) => {
+                Operand::Text(rhs.trim_matches(|c| c == '"' || c == '\'').to_string())
+            }
+            Err(e) => return Err(format!("where '{s}': {e}")),
+        };
+        Ok(Condition {
+            field: field.to_string(),
+            op,
+            operand,
+            source: s.split_whitespace().collect::<Vec<_>>().join(" "),
+        })
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+// `--where` filters and a `--select` projection over serialized output, a small stand-in for jq.
+// Field names are the JSON keys, e.g. `volume24h` or `change24hPct`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    pub select: Vec<String>,
+    pub conditions: Vec<Condition>,
+}
+
+impl Query {
+    pub fn is_empty(&self) -> bool {
+        self.select.is_empty() && self.conditions.is_empty()
+    }
+
+    // Keeps the items matching every condition, cut down to the selected fields when there are
+    // any. A selected field an item lacks comes out as null, so every object has the same keys.
+    pub fn apply(&self, items: Vec<Value>) -> Vec<Value> {
+        items
+            .into_iter()
+            .filter(|item| self.conditions.iter().all(|c| c.matches(item)))
+            .map(|item| self.project(item))
+            .collect()
+    }
+
+    // `apply` for a single document: an array is filtered item by item, anything else is one item
+    // that comes out as null when a condition rules it out.
+    pub fn apply_document(&self, document: Value) -> Value {
+        match document {
+            Value::Array(items) => Value::Array(self.apply(items)),
+            item => self.apply(vec![item]).pop().unwrap_or(Value::Null),
+        }
+    }
+
+    fn project(&self, item: Value) -> Value {
+        if self.select.is_empty() {
+            return item;
+        }
+        let Value::Object(fields) = item else {
+            return item;
+        };
+        let picked: Map<String, Value> = self
+            .select
+            .iter()
+            .map(|name| (name.clone(), fields.get(name).cloned().unwrap_or(Value::Null)))
+            .collect();
+        Value::Object(picked)
+    }
+}
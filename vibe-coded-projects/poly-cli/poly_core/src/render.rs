@@ -0,0 +1,1012 @@
+use chrono::{DateTime, Duration, Local};
+use regex::Regex;
+
+use crate::arb::{ArbSide, Mispricing};
+use crate::clob::{OrderBook, PricePoint};
+use crate::columns::Column;
+use crate::comments::Comment;
+use crate::data_api::{Holder, Position, Trade};
+use crate::diff::{MarketDelta, SnapshotDiff};
+use crate::format::{
+    ansi_regex, display_options, format_end, format_money, format_percent, format_price, pad_visible, paint, sparkline,
+    visible_len, C,
+};
+use crate::fresh::NewMarket;
+use crate::leaderboard::{LeaderboardEntry, LeaderboardMetric};
+use crate::model::{Event, Market, Row};
+use crate::pnl::MarketPnl;
+use crate::resolved::Resolution;
+use crate::sports::Matchup;
+use crate::tags::TagSummary;
+use crate::trading::{OpenOrder, Side};
+use crate::trend::Momentum;
+use crate::venue::VenueMatch;
+
+pub const SPARKLINE_WIDTH: usize = 12;
+// Markets ending sooner than this have their end date shown in red.
+pub const CLOSING_SOON_HOURS: i64 = 6;
+
+fn momentum_color(momentum: Momentum) -> &'static str {
+    match momentum.rising() {
+        Some(true) => C::GREEN,
+        Some(false) => C::RED,
+        None => C::DIM,
+    }
+}
+
+// Spreads up to a cent are tight, up to three cents workable, anything wider is hard to trade.
+fn spread_color(spread: Option<f64>) -> &'static str {
+    match spread {
+        None => C::DIM,
+        Some(s) if s <= 0.01 => C::GREEN,
+        Some(s) if s <= 0.03 => C::YELLOW,
+        Some(_) => C::RED,
+    }
+}
+
+// Table width each column pads or truncates to.
+fn column_width(column: Column) -> usize {
+    match column {
+        Column::Rank => 4,
+        Column::Venue => 10,
+        Column::Title => 64,
+        Column::Event => 40,
+        Column::Slug => 40,
+        Column::Url => 60,
+        Column::Price => 7,
+        Column::Volume => 14,
+        Column::Volume24h => 12,
+        Column::Change => 11,
+        Column::Liquidity => 12,
+        Column::End => 22,
+        Column::Result => 12,
+        Column::Trend => SPARKLINE_WIDTH,
+        Column::Momentum => 3,
+        Column::Spike => 6,
+        Column::Quote => 13,
+        Column::Spread => 7,
+        Column::Mid => 7,
+    }
+}
+
+fn table_cell(column: Column, idx: usize, row: &Row, color: bool) -> String {
+    match column {
+        Column::Rank => paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+        Column::Venue => paint(row.venue.label(), C::MAGENTA, color),
+        Column::Title => paint(&row.title, C::WHITE, color),
+        Column::Event => paint(&row.event, C::DIM, color),
+        Column::Slug => paint(row.slug.as_deref().unwrap_or("n/a"), C::DIM, color),
+        Column::Url => paint(&row.url().unwrap_or_else(|| "n/a".to_string()), C::DIM, color),
+        Column::Price => paint(&format_price(row.price), C::YELLOW, color),
+        Column::Volume => paint(&row.venue.format_money(row.volume), C::CYAN, color),
+        Column::Volume24h => paint(&row.venue.format_money(row.volume_24h), C::CYAN, color),
+        Column::Change => {
+            let change_txt = format_percent(row.change_24h_pct);
+            match row.change_24h_pct {
+                None => paint(&change_txt, C::DIM, color),
+                Some(v) if v > 0.0 => paint(&format!("+ {change_txt}"), &(String::from(C::GREEN) + C::BOLD), color),
+                Some(v) if v < 0.0 => paint(
+                    &format!("- {}", change_txt.trim_start_matches('-')),
+                    &(String::from(C::RED) + C::BOLD),
+                    color,
+                ),
+                Some(_) => paint(&change_txt, C::YELLOW, color),
+            }
+        }
+        Column::Liquidity => match row.liquidity {
+            Some(liquidity) => paint(&row.venue.format_money(liquidity), C::CYAN, color),
+            None => paint("n/a", C::DIM, color),
+        },
+        Column::End => {
+            let end_str = format_end(row.end_date.as_deref());
+            if row.ends_within(Duration::hours(CLOSING_SOON_HOURS)) {
+                paint(&end_str, &(String::from(C::RED) + C::BOLD), color)
+            } else {
+                paint(&end_str, C::DIM, color)
+            }
+        }
+        Column::Result => match &row.resolution {
+            Some(outcome) => paint(outcome, &(String::from(C::GREEN) + C::BOLD), color),
+            None => paint("open", C::DIM, color),
+        },
+        Column::Trend => {
+            let rising = row.trend.last() >= row.trend.first();
+            paint(&sparkline(&row.trend, SPARKLINE_WIDTH), if rising { C::GREEN } else { C::RED }, color)
+        }
+        Column::Momentum => match row.momentum {
+            Some(m) => paint(m.arrow(), momentum_color(m), color),
+            None => paint("-", C::DIM, color),
+        },
+        Column::Spike => match row.spike {
+            Some(ratio) => paint(&format!("{ratio:.1}×"), &(String::from(C::YELLOW) + C::BOLD), color),
+            None => paint("-", C::DIM, color),
+        },
+        Column::Quote => format!("{} / {}", format_price(row.best_bid), format_price(row.best_ask)),
+        Column::Spread => paint(&format_price(row.spread), spread_color(row.spread), color),
+        Column::Mid => paint(&format_price(row.midpoint), C::YELLOW, color),
+    }
+}
+
+// Squeezing a text column below this loses too much of it, so the column is dropped instead.
+fn min_width(column: Column) -> usize {
+    match column {
+        Column::Title | Column::Url => 24,
+        Column::Event | Column::Slug => 16,
+        other => column_width(other),
+    }
+}
+
+// Which columns a narrow terminal gives up first. The rank and title are never dropped.
+const COLLAPSE_ORDER: [Column; 17] = [
+    Column::Liquidity,
+    Column::Volume,
+    Column::Slug,
+    Column::Mid,
+    Column::Spread,
+    Column::Event,
+    Column::Url,
+    Column::Result,
+    Column::Trend,
+    Column::Quote,
+    Column::Momentum,
+    Column::Spike,
+    Column::Volume24h,
+    Column::Venue,
+    Column::End,
+    Column::Change,
+    Column::Price,
+];
+
+// Fits the columns into `available` terminal cells: low-priority columns are dropped until the
+// rest fit at their minimum widths, text columns shrink toward those minimums, and any room left
+// over widens the title up to the longest title on screen. A table is never narrowed to nothing:
+// with --columns leaving out the rank and title, the last column standing stays.
+fn fit_columns(columns: &[Column], rows: &[Row], available: usize) -> (Vec<Column>, Vec<usize>) {
+    let separators = |count: usize| 3 * count.saturating_sub(1);
+    let needed = |cols: &[Column]| cols.iter().map(|c| min_width(*c)).sum::<usize>() + separators(cols.len());
+
+    let mut columns = columns.to_vec();
+    for collapse in COLLAPSE_ORDER {
+        if needed(&columns) <= available || columns.iter().all(|c| *c == collapse) {
+            break;
+        }
+        columns.retain(|c| *c != collapse);
+    }
+
+    let mut widths: Vec<usize> = columns.iter().map(|c| column_width(*c)).collect();
+    let total = widths.iter().sum::<usize>() + separators(columns.len());
+    if total > available {
+        let mut deficit = total - available;
+        for shrink in [Column::Url, Column::Event, Column::Slug, Column::Title] {
+            if let Some(i) = columns.iter().position(|c| *c == shrink) {
+                let give = deficit.min(widths[i] - min_width(shrink));
+                widths[i] -= give;
+                deficit -= give;
+            }
+        }
+    } else if let Some(i) = columns.iter().position(|c| *c == Column::Title) {
+        let ansi_re = ansi_regex();
+        let longest = rows.iter().map(|r| visible_len(&r.title, &ansi_re)).max().unwrap_or(0);
+        widths[i] = widths[i].max(longest.min(widths[i] + available - total));
+    }
+    (columns, widths)
+}
+
+// How `render_table_with` lays rows out. The default is `render_table`'s layout.
+#[derive(Debug, Clone, Default)]
+pub struct TableLayout {
+    // Columns in display order; None means the default columns plus whichever extras
+    // (result, trend, momentum, spike, venue, quotes) the rows carry.
+    pub columns: Option<Vec<Column>>,
+    // Terminal width to fit the table into; None keeps every column at its fixed width.
+    pub width: Option<usize>,
+}
+
+// The standard table: fixed-width columns split by " | ", a bold header and a dashed divider.
+struct Grid {
+    widths: Vec<usize>,
+    ansi_re: Regex,
+    color: bool,
+}
+
+impl Grid {
+    fn new(widths: &[usize], color: bool) -> Self {
+        Grid { widths: widths.to_vec(), ansi_re: ansi_regex(), color }
+    }
+
+    fn row(&self, cells: &[String]) -> String {
+        cells
+            .iter()
+            .zip(&self.widths)
+            .map(|(cell, &width)| pad_visible(cell, width, &self.ansi_re))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    fn divider(&self) -> String {
+        let width = self.widths.iter().sum::<usize>() + 3 * self.widths.len().saturating_sub(1);
+        paint(&"-".repeat(width), C::DIM, self.color)
+    }
+
+    // The header line and the divider under it.
+    fn header(&self, labels: &[&str]) -> Vec<String> {
+        let header_color = String::from(C::BLUE) + C::BOLD;
+        let labels: Vec<String> = labels.iter().map(|label| paint(label, &header_color, self.color)).collect();
+        vec![self.row(&labels), self.divider()]
+    }
+}
+
+pub fn render_table(rows: &[Row], top: usize, color: bool) -> String {
+    render_table_with(rows, top, &TableLayout::default(), color)
+}
+
+pub fn render_table_with(rows: &[Row], top: usize, layout: &TableLayout, color: bool) -> String {
+    let top_rows = &rows[..rows.len().min(top)];
+    let columns = layout.columns.clone().unwrap_or_else(|| Column::for_rows(top_rows));
+    let (columns, widths) = match layout.width {
+        Some(available) => fit_columns(&columns, top_rows, available),
+        None => {
+            let widths = columns.iter().map(|c| column_width(*c)).collect();
+            (columns, widths)
+        }
+    };
+    let grid = Grid::new(&widths, color);
+
+    let labels: Vec<&str> = columns.iter().map(|c| c.label()).collect();
+    let mut lines = grid.header(&labels);
+    for (idx, row) in top_rows.iter().enumerate() {
+        let cells: Vec<String> = columns.iter().map(|c| table_cell(*c, idx, row, color)).collect();
+        lines.push(grid.row(&cells));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_event_detail(event: &Event, color: bool) -> String {
+    let mut lines = Vec::new();
+    let label = |text: &str| paint(text, C::DIM, color);
+
+    lines.push(paint(event.display_title(), &(String::from(C::BOLD) + C::CYAN), color));
+    if let Some(slug) = &event.slug {
+        lines.push(format!("{} {}", label("Slug:"), slug));
+    }
+    lines.push(format!(
+        "{} {}  {} {}  {} {}",
+        label("Volume:"),
+        paint(&format_money(event.volume.unwrap_or(0.0)), C::CYAN, color),
+        label("Liquidity:"),
+        paint(&format_money(event.liquidity.unwrap_or(0.0)), C::CYAN, color),
+        label("Ends:"),
+        format_end(event.end_date.as_deref()),
+    ));
+    if let Some(desc) = event.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        lines.push(String::new());
+        lines.push(paint(desc.trim(), C::DIM, color));
+    }
+
+    lines.push(String::new());
+    lines.push(paint(&format!("Markets ({})", event.markets.len()), &(String::from(C::BLUE) + C::BOLD), color));
+
+    for (idx, market) in event.markets.iter().enumerate() {
+        let row = Row::from_market(event, market);
+        lines.push(format!(
+            "{} {}",
+            paint(&format!("{:>3}.", idx + 1), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(&row.title, C::WHITE, color)
+        ));
+
+        let quotes = market.outcome_quotes();
+        if !quotes.is_empty() {
+            let outcomes = quotes
+                .iter()
+                .map(|(name, price)| format!("{name} {}", paint(&format_price(*price), C::YELLOW, color)))
+                .collect::<Vec<_>>()
+                .join("  |  ");
+            lines.push(format!("     {} {}", label("Outcomes:"), outcomes));
+        }
+
+        lines.push(format!(
+            "     {} {}  {} {}  {} {}  {} {}  {} {}",
+            label("Volume:"),
+            format_money(row.volume),
+            label("24h:"),
+            format_money(row.volume_24h),
+            label("Change:"),
+            format_percent(row.change_24h_pct),
+            label("Liquidity:"),
+            market.liquidity().map(format_money).unwrap_or_else(|| "n/a".to_string()),
+            label("Ends:"),
+            format_end(row.end_date.as_deref()),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_market_detail(market: &Market, color: bool) -> String {
+    let mut lines = Vec::new();
+    let label = |text: &str| paint(&format!("{text:<12}"), C::DIM, color);
+    let money = |value: Option<f64>| value.map(format_money).unwrap_or_else(|| "n/a".to_string());
+
+    lines.push(paint(market.display_title(), &(String::from(C::BOLD) + C::CYAN), color));
+    if let Some(slug) = &market.slug {
+        lines.push(format!("{}{}", label("Slug"), slug));
+    }
+    lines.push(String::new());
+
+    for (name, price) in market.outcome_quotes() {
+        lines.push(format!("{}{}", label(name), paint(&format_price(price), C::YELLOW, color)));
+    }
+    lines.push(format!("{}{}", label("Best bid"), format_price(market.best_bid)));
+    lines.push(format!("{}{}", label("Best ask"), format_price(market.best_ask)));
+    lines.push(format!("{}{}", label("Last trade"), format_price(market.last_trade_price)));
+    lines.push(String::new());
+
+    lines.push(format!("{}{}", label("Volume"), format_money(market.total_volume())));
+    lines.push(format!("{}{}", label("24h volume"), money(market.volume_24hr)));
+    lines.push(format!("{}{}", label("24h change"), format_percent(market.change_24h_pct())));
+    lines.push(format!("{}{}", label("Liquidity"), money(market.liquidity())));
+    lines.push(format!("{}{}", label("Ends"), format_end(market.end())));
+
+    if let Some(desc) = market.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        lines.push(String::new());
+        lines.push(paint(desc.trim(), C::DIM, color));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_orderbook(book: &OrderBook, depth: usize, color: bool) -> String {
+    let ansi_re = ansi_regex();
+    let side_widths = [9, 12, 12];
+    let side_width = side_widths.iter().sum::<usize>() + 2 * (side_widths.len() - 1);
+    let mut lines = Vec::new();
+
+    let side = |price: &str, size: &str, cum: &str, price_color: &str| {
+        [
+            pad_visible(&paint(price, price_color, color), side_widths[0], &ansi_re),
+            pad_visible(size, side_widths[1], &ansi_re),
+            pad_visible(&paint(cum, C::DIM, color), side_widths[2], &ansi_re),
+        ]
+        .join("  ")
+    };
+    let header_color = String::from(C::BLUE) + C::BOLD;
+
+    lines.push(format!(
+        "{} | {}",
+        pad_visible(&paint("Bids", &(String::from(C::GREEN) + C::BOLD), color), side_width, &ansi_re),
+        paint("Asks", &(String::from(C::RED) + C::BOLD), color),
+    ));
+    lines.push(format!(
+        "{} | {}",
+        side("Price", "Size", "Cumulative", &header_color),
+        side("Price", "Size", "Cumulative", &header_color),
+    ));
+    lines.push(paint(&"-".repeat(side_width * 2 + 3), C::DIM, color));
+
+    let (mut bid_cum, mut ask_cum) = (0.0, 0.0);
+    for i in 0..depth.min(book.bids.len().max(book.asks.len())) {
+        let bid = match book.bids.get(i) {
+            Some(level) => {
+                bid_cum += level.size;
+                side(&format_price(Some(level.price)), &format!("{:.2}", level.size), &format!("{bid_cum:.2}"), C::GREEN)
+            }
+            None => " ".repeat(side_width),
+        };
+        let ask = match book.asks.get(i) {
+            Some(level) => {
+                ask_cum += level.size;
+                side(&format_price(Some(level.price)), &format!("{:.2}", level.size), &format!("{ask_cum:.2}"), C::RED)
+            }
+            None => String::new(),
+        };
+        lines.push(format!("{bid} | {ask}"));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{} {}  {} {}",
+        paint("Midpoint:", C::DIM, color),
+        format_price(book.midpoint()),
+        paint("Spread:", C::DIM, color),
+        format_price(book.spread()),
+    ));
+
+    lines.join("\n")
+}
+
+pub fn render_trade_line(trade: &Trade, color: bool) -> String {
+    let time = trade
+        .timestamp
+        .and_then(|ts| DateTime::from_timestamp(ts, 0))
+        .map(|dt| dt.with_timezone(&Local).format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "--:--:--".to_string());
+    let side = trade.side.as_deref().unwrap_or("?").to_uppercase();
+    let side_color = if trade.is_buy() { String::from(C::GREEN) + C::BOLD } else { String::from(C::RED) + C::BOLD };
+    let size = trade.size.unwrap_or(0.0);
+    let notional = size * trade.price.unwrap_or(0.0);
+
+    format!(
+        "{}  {}  {:<10}  {:>7}  {:>12}  {}",
+        paint(&time, C::DIM, color),
+        paint(&format!("{side:<4}"), &side_color, color),
+        trade.outcome.as_deref().unwrap_or("-"),
+        format_price(trade.price),
+        format!("{size:.2}"),
+        paint(&format_money(notional), C::CYAN, color),
+    )
+}
+
+fn paint_pnl(value: f64, color: bool) -> String {
+    let text = if value < 0.0 {
+        format!("-{}", format_money(-value))
+    } else {
+        format!("+{}", format_money(value))
+    };
+    let style = if value < 0.0 { String::from(C::RED) + C::BOLD } else { String::from(C::GREEN) + C::BOLD };
+    paint(&text, &style, color)
+}
+
+// One comment on one line: local time, author, and the body with its line breaks folded.
+pub fn render_comment_line(comment: &Comment, color: bool) -> String {
+    let time = comment
+        .created()
+        .map(|at| at.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "--- --:--".to_string());
+    let body = comment.body.as_deref().unwrap_or_default().split_whitespace().collect::<Vec<_>>().join(" ");
+    let reply = if comment.is_reply() { paint("↳ ", C::DIM, color) } else { String::new() };
+    let reactions = match comment.reaction_count {
+        Some(n) if n > 0 => paint(&format!("  ♥{n}"), C::DIM, color),
+        _ => String::new(),
+    };
+    format!(
+        "{}  {reply}{}  {body}{reactions}",
+        paint(&time, C::DIM, color),
+        paint(comment.author(), &(String::from(C::CYAN) + C::BOLD), color),
+    )
+}
+
+pub fn render_positions(positions: &[Position], color: bool) -> String {
+    let headers = ["#", "Market", "Outcome", "Shares", "Entry", "Mark", "Value", "Unrealized PnL"];
+    let grid = Grid::new(&[4, 56, 10, 12, 7, 7, 12, 15], color);
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, pos) in positions.iter().enumerate() {
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(pos.title.as_deref().or(pos.slug.as_deref()).unwrap_or("?"), C::WHITE, color),
+            pos.outcome.clone().unwrap_or_else(|| "-".to_string()),
+            format!("{:.2}", pos.size.unwrap_or(0.0)),
+            format_price(pos.avg_price),
+            paint(&format_price(pos.cur_price), C::YELLOW, color),
+            paint(&format_money(pos.value()), C::CYAN, color),
+            paint_pnl(pos.unrealized_pnl(), color),
+        ]));
+    }
+
+    let value: f64 = positions.iter().map(Position::value).sum();
+    let cost: f64 = positions.iter().map(Position::cost).sum();
+    lines.push(grid.divider());
+    lines.push(format!(
+        "{} {}  {} {}  {} {}",
+        paint("Cost:", C::DIM, color),
+        format_money(cost),
+        paint("Value:", C::DIM, color),
+        paint(&format_money(value), C::CYAN, color),
+        paint("Unrealized:", C::DIM, color),
+        paint_pnl(value - cost, color),
+    ));
+
+    lines.join("\n")
+}
+
+pub fn render_leaderboard(entries: &[LeaderboardEntry], metric: LeaderboardMetric, color: bool) -> String {
+    let headers = ["#", "Trader", "Wallet", "Profit", "Volume"];
+    let grid = Grid::new(&[4, 32, 42, 12, 12], color);
+
+    let mut lines = grid.header(&headers);
+
+    for entry in entries {
+        let profit = entry.profit.map_or_else(|| paint("n/a", C::DIM, color), |p| paint_pnl(p, color));
+        let volume = entry.volume.map_or_else(|| "n/a".to_string(), format_money);
+        let volume = match metric {
+            LeaderboardMetric::Volume => paint(&volume, &(String::from(C::CYAN) + C::BOLD), color),
+            LeaderboardMetric::Profit => paint(&volume, C::CYAN, color),
+        };
+        lines.push(grid.row(&[
+            paint(&entry.rank.to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(entry.name.as_deref().unwrap_or("-"), C::WHITE, color),
+            paint(&entry.proxy_wallet, C::DIM, color),
+            profit,
+            volume,
+        ]));
+    }
+
+    lines.join("\n")
+}
+
+// Game lines: who plays whom, each side's price (the favorite in bold), kick-off and volume.
+pub fn render_matchups(matchups: &[Matchup], color: bool) -> String {
+    let headers = ["Start", "Matchup", "Prices", "Volume"];
+    let grid = Grid::new(&[22, 48, 17, 12], color);
+
+    let mut lines = grid.header(&headers);
+
+    for game in matchups {
+        let favorite = match (game.price_a, game.price_b) {
+            (Some(a), Some(b)) if a > b => Some(true),
+            (Some(a), Some(b)) if b > a => Some(false),
+            _ => None,
+        };
+        let side = |price: Option<f64>, favored: bool| {
+            let style = if favored { String::from(C::YELLOW) + C::BOLD } else { String::from(C::YELLOW) };
+            paint(&format_price(price), &style, color)
+        };
+        lines.push(grid.row(&[
+            paint(&format_end(game.start_time.as_deref()), C::DIM, color),
+            format!(
+                "{} {} {}",
+                paint(&game.team_a, C::WHITE, color),
+                paint("vs", C::DIM, color),
+                paint(&game.team_b, C::WHITE, color)
+            ),
+            format!(
+                "{} / {}",
+                side(game.price_a, favorite == Some(true)),
+                side(game.price_b, favorite == Some(false))
+            ),
+            paint(&format_money(game.volume), C::CYAN, color),
+        ]));
+    }
+
+    lines.join("\n")
+}
+
+// The markets that entered and left the top N, then how the ones that stayed moved.
+pub fn render_snapshot_diff(diff: &SnapshotDiff, color: bool) -> String {
+    let headers = ["#", "Was", "Market", "Price", "Δ Price", "Volume", "Δ Volume"];
+    let grid = Grid::new(&[4, 4, 56, 17, 9, 12, 12], color);
+    let rank = |rank: Option<usize>| rank.map_or_else(|| "-".to_string(), |r| r.to_string());
+    let line = |delta: &MarketDelta| {
+        let price_change = match delta.price_change {
+            Some(change) if change > 0.0 => paint(&format!("+{:.1}¢", change * 100.0), C::GREEN, color),
+            Some(change) if change < 0.0 => paint(&format!("{:.1}¢", change * 100.0), C::RED, color),
+            Some(_) => paint("0.0¢", C::DIM, color),
+            None => paint("n/a", C::DIM, color),
+        };
+        let volume_change = match delta.volume_change {
+            Some(change) if change > 0.0 => paint(&format!("+{}", format_money(change)), C::CYAN, color),
+            Some(change) => paint(&format_money(change), C::DIM, color),
+            None => paint("n/a", C::DIM, color),
+        };
+        let volume = delta.volume_after.or(delta.volume_before).map_or_else(|| "n/a".to_string(), format_money);
+        grid.row(&[
+            paint(&rank(delta.rank_after), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(&rank(delta.rank_before), C::DIM, color),
+            paint(&delta.title, C::WHITE, color),
+            format!("{} → {}", format_price(delta.price_before), format_price(delta.price_after)),
+            price_change,
+            paint(&volume, C::CYAN, color),
+            volume_change,
+        ])
+    };
+
+    let mut lines = Vec::new();
+    let sections = [
+        (format!("Entered the top {}", diff.top), &diff.entered, C::GREEN),
+        (format!("Left the top {}", diff.top), &diff.left, C::RED),
+        (format!("Still in the top {}", diff.top), &diff.stayed, C::CYAN),
+    ];
+    for (heading, deltas, heading_color) in sections {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(paint(&heading, &(String::from(heading_color) + C::BOLD), color));
+        if deltas.is_empty() {
+            lines.push(paint("None", C::DIM, color));
+            continue;
+        }
+        lines.extend(grid.header(&headers));
+        lines.extend(deltas.iter().map(line));
+    }
+
+    lines.join("\n")
+}
+
+// Greedy word wrap into lines of at most `width` columns; a word longer than that gets a line
+// to itself and is cut when padded.
+fn wrap_words(text: &str, width: usize, ansi_re: &Regex) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if visible_len(line, ansi_re) + 1 + visible_len(word, ansi_re) <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+// Markets side by side: one column each, with their titles wrapped over the first few lines so
+// contracts that differ only at the end of the question stay easy to tell apart.
+pub fn render_comparison(rows: &[Row], color: bool) -> String {
+    const LABEL_WIDTH: usize = 12;
+    const MARKET_WIDTH: usize = 28;
+    const TITLE_LINES: usize = 3;
+    let widths: Vec<usize> = std::iter::once(LABEL_WIDTH).chain(rows.iter().map(|_| MARKET_WIDTH)).collect();
+    let grid = Grid::new(&widths, color);
+    let join = |label: String, cells: Vec<String>| grid.row(&[vec![label], cells].concat());
+
+    let mut lines = Vec::new();
+    let titles: Vec<Vec<String>> =
+        rows.iter().map(|row| wrap_words(&row.title, MARKET_WIDTH, &grid.ansi_re)).collect();
+    let title_lines = titles.iter().map(Vec::len).max().unwrap_or(1).clamp(1, TITLE_LINES);
+    for idx in 0..title_lines {
+        let cells = titles
+            .iter()
+            .map(|title| {
+                let mut line = title.get(idx).cloned().unwrap_or_default();
+                // The last line shown carries whatever's left, to be cut with an ellipsis.
+                if idx + 1 == title_lines && title.len() > title_lines {
+                    line = title[idx..].join(" ");
+                }
+                paint(&line, &(String::from(C::WHITE) + C::BOLD), color)
+            })
+            .collect();
+        let label = if idx == 0 { paint("Market", &(String::from(C::BLUE) + C::BOLD), color) } else { String::new() };
+        lines.push(join(label, cells));
+    }
+    lines.push(grid.divider());
+
+    let fields = [
+        ("Price", Column::Price),
+        ("24h Change", Column::Change),
+        ("Volume", Column::Volume),
+        ("24h Volume", Column::Volume24h),
+        ("Liquidity", Column::Liquidity),
+        ("Bid / Ask", Column::Quote),
+        ("Spread", Column::Spread),
+        ("End", Column::End),
+        ("Slug", Column::Slug),
+    ];
+    for (label, column) in fields {
+        let cells = rows.iter().map(|row| table_cell(column, 0, row, color)).collect();
+        lines.push(join(paint(label, &(String::from(C::BLUE) + C::BOLD), color), cells));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_tags(tags: &[TagSummary], color: bool) -> String {
+    let headers = ["Slug", "Label", "Events", "Markets", "Volume"];
+    let grid = Grid::new(&[28, 28, 7, 8, 12], color);
+
+    let mut lines = grid.header(&headers);
+
+    for tag in tags {
+        lines.push(grid.row(&[
+            paint(&tag.slug, &(String::from(C::CYAN) + C::BOLD), color),
+            paint(tag.label.as_deref().unwrap_or("-"), C::WHITE, color),
+            tag.events.to_string(),
+            tag.markets.to_string(),
+            paint(&format_money(tag.volume), C::CYAN, color),
+        ]));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_holders(holders: &[Holder], color: bool) -> String {
+    let headers = ["#", "Holder", "Wallet", "Shares", "% Supply"];
+    let grid = Grid::new(&[4, 32, 42, 14, 9], color);
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, holder) in holders.iter().enumerate() {
+        let name = match holder.display_name() {
+            Some(name) if holder.ens.is_some() => paint(name, &(String::from(C::WHITE) + C::BOLD), color),
+            Some(name) => paint(name, C::WHITE, color),
+            None => paint("-", C::DIM, color),
+        };
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            name,
+            paint(holder.proxy_wallet.as_deref().unwrap_or("?"), C::DIM, color),
+            format!("{:.2}", holder.amount.unwrap_or(0.0)),
+            holder.supply_pct.map_or_else(|| "n/a".to_string(), |pct| format!("{pct:.2}%")),
+        ]));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_open_orders(orders: &[OpenOrder], color: bool) -> String {
+    let headers = ["#", "Market", "Outcome", "Side", "Price", "Filled", "Placed", "Order ID"];
+    let grid = Grid::new(&[4, 48, 10, 4, 7, 17, 22, 66], color);
+    let zone = display_options().zone;
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, order) in orders.iter().enumerate() {
+        let side = match order.side {
+            Side::Buy => paint("BUY", C::GREEN, color),
+            Side::Sell => paint("SELL", C::RED, color),
+        };
+        let placed =
+            DateTime::from_timestamp(order.created_at, 0).map_or_else(|| "-".to_string(), |at| zone.format(at));
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(order.title.as_deref().unwrap_or(&order.asset_id), C::WHITE, color),
+            if order.outcome.is_empty() { "-".to_string() } else { order.outcome.clone() },
+            side,
+            paint(&format_price(Some(order.price)), C::YELLOW, color),
+            format!("{:.2}/{:.2}", order.size_matched, order.original_size),
+            paint(&placed, C::DIM, color),
+            paint(&order.id, C::DIM, color),
+        ]));
+    }
+
+    let committed: f64 = orders.iter().filter(|o| o.side == Side::Buy).map(|o| o.remaining() * o.price).sum();
+    lines.push(grid.divider());
+    lines.push(format!("{} {}", paint("Committed to open buys:", C::DIM, color), format_money(committed)));
+
+    lines.join("\n")
+}
+
+pub fn render_pnl(rows: &[MarketPnl], color: bool) -> String {
+    let headers = ["#", "Market", "Outcome", "Shares", "Avg Cost", "Realized", "Unrealized", "Total"];
+    let grid = Grid::new(&[4, 56, 10, 12, 8, 12, 12, 12], color);
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, row) in rows.iter().enumerate() {
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(&row.title, C::WHITE, color),
+            row.outcome.clone().unwrap_or_else(|| "-".to_string()),
+            format!("{:.2}", row.shares),
+            format_price(row.avg_cost),
+            paint_pnl(row.realized, color),
+            paint_pnl(row.unrealized, color),
+            paint_pnl(row.total(), color),
+        ]));
+    }
+
+    let realized: f64 = rows.iter().map(|r| r.realized).sum();
+    let unrealized: f64 = rows.iter().map(|r| r.unrealized).sum();
+    lines.push(grid.divider());
+    lines.push(format!(
+        "{} {}  {} {}  {} {}",
+        paint("Realized:", C::DIM, color),
+        paint_pnl(realized, color),
+        paint("Unrealized:", C::DIM, color),
+        paint_pnl(unrealized, color),
+        paint("Total:", C::DIM, color),
+        paint_pnl(realized + unrealized, color),
+    ));
+
+    lines.join("\n")
+}
+
+pub fn render_arb(found: &[Mispricing], color: bool) -> String {
+    let headers = ["#", "Market", "Outcome Prices", "Sum", "Edge", "Side", "24h Vol", "Liquidity"];
+    let grid = Grid::new(&[4, 56, 20, 7, 7, 4, 12, 12], color);
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, arb) in found.iter().enumerate() {
+        let prices = arb.outcome_prices.iter().map(|&p| format_price(Some(p))).collect::<Vec<_>>();
+        let side = match arb.side {
+            ArbSide::Buy => paint("buy", C::GREEN, color),
+            ArbSide::Sell => paint("sell", C::RED, color),
+        };
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(&arb.title, C::WHITE, color),
+            prices.join(" + "),
+            format_price(Some(arb.sum)),
+            paint(&format_price(Some(arb.edge)), &(String::from(C::YELLOW) + C::BOLD), color),
+            side,
+            format_money(arb.volume_24h),
+            arb.liquidity.map(format_money).unwrap_or_else(|| "n/a".to_string()),
+        ]));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_new_markets(markets: &[NewMarket], color: bool) -> String {
+    let headers = ["#", "Market", "Created", "Open", "Price", "Move", "24h Volume", "Liquidity"];
+    let grid = Grid::new(&[4, 64, 16, 7, 7, 9, 12, 12], color);
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, market) in markets.iter().enumerate() {
+        let change = match market.change() {
+            Some(c) if c > 0.0 => paint(&format!("+{:.1} pts", c * 100.0), C::GREEN, color),
+            Some(c) if c < 0.0 => paint(&format!("{:.1} pts", c * 100.0), C::RED, color),
+            Some(_) => paint("0.0 pts", C::YELLOW, color),
+            None => paint("n/a", C::DIM, color),
+        };
+        let created = DateTime::<Local>::from(market.created_at).format("%Y-%m-%d %H:%M").to_string();
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(&market.title, C::WHITE, color),
+            paint(&created, C::DIM, color),
+            format_price(market.opening_price),
+            paint(&format_price(market.price), C::YELLOW, color),
+            change,
+            paint(&format_money(market.volume_24h), C::CYAN, color),
+            market.liquidity.map(format_money).unwrap_or_else(|| "n/a".to_string()),
+        ]));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_resolutions(resolved: &[Resolution], color: bool) -> String {
+    let headers = ["#", "Market", "Closed", "Winner", "Final Price", "Called", "Total Volume"];
+    let grid = Grid::new(&[4, 64, 16, 12, 16, 6, 14], color);
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, res) in resolved.iter().enumerate() {
+        let closed = DateTime::<Local>::from(res.closed_at).format("%Y-%m-%d %H:%M").to_string();
+        let final_price = match &res.outcome {
+            Some(outcome) => format!("{outcome} {}", format_price(res.final_price)),
+            None => format_price(res.final_price),
+        };
+        let called = match res.called() {
+            Some(true) => paint("✓", C::GREEN, color),
+            Some(false) => paint("✗", &(String::from(C::RED) + C::BOLD), color),
+            None => paint("-", C::DIM, color),
+        };
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(&res.title, C::WHITE, color),
+            paint(&closed, C::DIM, color),
+            paint(&res.winner, &(String::from(C::GREEN) + C::BOLD), color),
+            paint(&final_price, C::YELLOW, color),
+            called,
+            paint(&format_money(res.volume), C::CYAN, color),
+        ]));
+    }
+
+    let scored: Vec<bool> = resolved.iter().filter_map(Resolution::called).collect();
+    if !scored.is_empty() {
+        let hits = scored.iter().filter(|&&hit| hit).count();
+        lines.push(grid.divider());
+        lines.push(format!(
+            "{} {hits}/{} ({:.0}%)",
+            paint("Final price favored the winner:", C::DIM, color),
+            scored.len(),
+            hits as f64 / scored.len() as f64 * 100.0,
+        ));
+    }
+
+    lines.join("\n")
+}
+
+pub fn render_venue_matches(matches: &[VenueMatch], color: bool) -> String {
+    let (left, right) = matches
+        .first()
+        .map_or(("Left", "Right"), |pair| (pair.left.venue.label(), pair.right.venue.label()));
+    let headers = ["#", left, "Price", right, "Price", "Gap", "Match"];
+    let grid = Grid::new(&[4, 48, 7, 48, 7, 9, 5], color);
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, pair) in matches.iter().enumerate() {
+        let gap = match pair.price_gap() {
+            Some(gap) if gap.abs() >= 0.05 => {
+                paint(&format!("{:+.1} pts", gap * 100.0), &(String::from(C::RED) + C::BOLD), color)
+            }
+            Some(gap) => paint(&format!("{:+.1} pts", gap * 100.0), C::YELLOW, color),
+            None => paint("n/a", C::DIM, color),
+        };
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(&pair.left.title, C::WHITE, color),
+            paint(&format_price(pair.left.price), C::YELLOW, color),
+            paint(&pair.right.title, C::WHITE, color),
+            paint(&format_price(pair.right.price), C::YELLOW, color),
+            gap,
+            paint(&format!("{:.0}%", pair.similarity * 100.0), C::DIM, color),
+        ]));
+    }
+
+    lines.join("\n")
+}
+
+// Cross-venue gaps from `find_price_gaps`: buy where it's cheap, sell where it's rich.
+pub fn render_xarb(gaps: &[VenueMatch], color: bool) -> String {
+    let headers = ["#", "Market", "Buy on", "Price", "Sell on", "Price", "Gap", "Match"];
+    let grid = Grid::new(&[4, 56, 10, 7, 10, 7, 9, 5], color);
+
+    let mut lines = grid.header(&headers);
+
+    for (idx, pair) in gaps.iter().enumerate() {
+        let gap = pair.price_gap().unwrap_or(0.0);
+        lines.push(grid.row(&[
+            paint(&(idx + 1).to_string(), &(String::from(C::CYAN) + C::BOLD), color),
+            paint(&pair.left.title, C::WHITE, color),
+            paint(pair.left.venue.label(), C::MAGENTA, color),
+            paint(&format_price(pair.left.price), C::GREEN, color),
+            paint(pair.right.venue.label(), C::MAGENTA, color),
+            paint(&format_price(pair.right.price), C::RED, color),
+            paint(&format!("{:.1} pts", gap * 100.0), &(String::from(C::YELLOW) + C::BOLD), color),
+            paint(&format!("{:.0}%", pair.similarity * 100.0), C::DIM, color),
+        ]));
+    }
+
+    lines.join("\n")
+}
+
+// Braille cells pack a 2x4 dot grid; these are the bits for (column, row) within one cell.
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+// A braille line chart `width` x `height` characters with the price range on the left axis and
+// the first/last sample times underneath. Points must be oldest first.
+pub fn render_price_chart(points: &[PricePoint], width: usize, height: usize, color: bool) -> String {
+    if points.is_empty() || width == 0 || height == 0 {
+        return paint("No price history in this range", C::DIM, color);
+    }
+
+    let (dots_x, dots_y) = (width * 2, height * 4);
+    let low = points.iter().map(|p| p.p).fold(f64::INFINITY, f64::min);
+    let high = points.iter().map(|p| p.p).fold(f64::NEG_INFINITY, f64::max);
+    let span = (high - low).max(1e-9);
+
+    // Sample the series once per horizontal dot, then draw vertical runs between neighbours so
+    // steep moves stay connected.
+    let ys: Vec<usize> = (0..dots_x)
+        .map(|x| {
+            let idx = x * (points.len() - 1) / (dots_x - 1).max(1);
+            let level = (points[idx].p - low) / span;
+            (dots_y - 1) - (level * (dots_y - 1) as f64).round() as usize
+        })
+        .collect();
+
+    let mut cells = vec![vec![0u32; width]; height];
+    for x in 0..dots_x {
+        let (from, to) = match x {
+            0 => (ys[0], ys[0]),
+            _ => (ys[x - 1].min(ys[x]), ys[x - 1].max(ys[x])),
+        };
+        for y in from..=to {
+            cells[y / 4][x / 2] |= BRAILLE_DOTS[x % 2][y % 4];
+        }
+    }
+
+    let axis_width = 7;
+    let rising = points.last().map(|p| p.p) >= points.first().map(|p| p.p);
+    let line_color = if rising { C::GREEN } else { C::RED };
+    let mut lines = Vec::new();
+    for (row, bits) in cells.iter().enumerate() {
+        let label = match row {
+            0 => format_price(Some(high)),
+            r if r == height - 1 => format_price(Some(low)),
+            _ => String::new(),
+        };
+        let chart: String = bits.iter().map(|&b| char::from_u32(0x2800 + b).unwrap_or(' ')).collect();
+        lines.push(format!(
+            "{} ┤{}",
+            paint(&format!("{label:>axis_width$}"), C::DIM, color),
+            paint(&chart, line_color, color)
+        ));
+    }
+
+    let stamp = |t: i64| {
+        DateTime::from_timestamp(t, 0)
+            .map(|dt| dt.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default()
+    };
+    let (first, last) = (stamp(points[0].t), stamp(points[points.len() - 1].t));
+    let gap = (width + 1).saturating_sub(first.chars().count() + last.chars().count());
+    lines.push(paint(&format!("{:axis_width$}  {first}{}{last}", "", " ".repeat(gap)), C::DIM, color));
+
+    lines.join("\n")
+}
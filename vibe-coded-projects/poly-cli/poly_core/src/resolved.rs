@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::error::PolyError;
+use crate::fetch::{fetch_events, EventOrder, EventQuery, MarketStatus};
+
+// A settled market: which outcome won and where the first outcome last traded before settling.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resolution {
+    pub closed_at: DateTime<Utc>,
+    pub event: String,
+    pub title: String,
+    pub slug: Option<String>,
+    pub winner: String,
+    pub outcome: Option<String>,
+    pub final_price: Option<f64>,
+    pub volume: f64,
+}
+
+impl Resolution {
+    // Whether the final price favored the outcome that went on to win.
+    pub fn called(&self) -> Option<bool> {
+        let price = self.final_price?;
+        let first_won = self.outcome.as_deref() == Some(self.winner.as_str());
+        (price != 0.5).then_some((price > 0.5) == first_won)
+    }
+}
+
+// Markets that closed at or after `since` with a known winner, most recent first, at most `max`.
+pub async fn fetch_resolutions(
+    query: &EventQuery,
+    since: DateTime<Utc>,
+    max: usize,
+) -> Result<Vec<Resolution>, PolyError> {
+    let query = EventQuery { status: MarketStatus::Resolved, order: EventOrder::Ended, ..query.clone() };
+    let events = fetch_events(&query).await?;
+
+    let mut resolved: Vec<Resolution> = events
+        .iter()
+        .flat_map(|event| event.markets.iter().map(move |market| (event, market)))
+        .filter_map(|(event, market)| {
+            let closed_at = market.closed_at().filter(|&at| at >= since)?;
+            Some(Resolution {
+                closed_at,
+                event: event.display_title().to_string(),
+                title: market.display_title().to_string(),
+                slug: market.slug.clone(),
+                winner: market.resolved_outcome()?.to_string(),
+                outcome: market.outcomes.first().cloned(),
+                final_price: market.last_trade_price,
+                volume: market.total_volume(),
+            })
+        })
+        .collect();
+    resolved.sort_by(|a, b| b.closed_at.cmp(&a.closed_at));
+    resolved.truncate(max);
+    Ok(resolved)
+}
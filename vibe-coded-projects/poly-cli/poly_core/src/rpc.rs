@@ -0,0 +1,96 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde_json::{json, Value};
+
+use crate::error::PolyError;
+use crate::http::post_rpc;
+use crate::wallet::keccak;
+
+// A public Polygon endpoint; rate limited, so anything regular should bring its own.
+pub const DEFAULT_RPC_URL: &str = "https://polygon-rpc.com";
+
+// Read-only calls against an Ethereum-style JSON-RPC node: Polygon for Polymarket's contracts,
+// Ethereum mainnet for ENS.
+#[derive(Debug)]
+pub struct JsonRpc {
+    url: String,
+    next_id: AtomicU64,
+}
+
+impl JsonRpc {
+    pub fn new(url: impl Into<String>) -> Self {
+        JsonRpc { url: url.into(), next_id: AtomicU64::new(1) }
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Result<Value, PolyError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let body = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        let mut reply = post_rpc(&self.url, &body).await?;
+        if let Some(error) = reply.get("error") {
+            let message = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+            return Err(PolyError::Rejected(format!("{method}: {message}")));
+        }
+        reply.get_mut("result").map(Value::take).ok_or(PolyError::Shape("RPC reply without result"))
+    }
+
+    // The raw return data of a view call against the latest block.
+    pub(crate) async fn call(&self, to: &str, data: &[u8]) -> Result<Vec<u8>, PolyError> {
+        let params = json!([{ "to": to, "data": format!("0x{}", hex::encode(data)) }, "latest"]);
+        let result = self.request("eth_call", params).await?;
+        decode_hex(&result)
+    }
+
+    // POL (the gas token) held by `address`, in wei.
+    pub(crate) async fn native_balance(&self, address: &str) -> Result<[u8; 32], PolyError> {
+        let result = self.request("eth_getBalance", json!([address, "latest"])).await?;
+        let bytes = decode_hex(&result)?;
+        if bytes.len() > 32 {
+            return Err(PolyError::Shape("balance wider than 256 bits"));
+        }
+        let mut word = [0; 32];
+        word[32 - bytes.len()..].copy_from_slice(&bytes);
+        Ok(word)
+    }
+}
+
+// Quantities come back as 0x hex, odd lengths included (`0x0`).
+fn decode_hex(value: &Value) -> Result<Vec<u8>, PolyError> {
+    let text = value.as_str().ok_or(PolyError::Shape("RPC result is not a string"))?;
+    let digits = text.trim_start_matches("0x");
+    let padded = if digits.len() % 2 == 1 { format!("0{digits}") } else { digits.to_string() };
+    hex::decode(padded).map_err(|_| PolyError::Shape("RPC result is not hex"))
+}
+
+// The 4-byte function selector for a signature such as `balanceOf(address)`.
+pub(crate) fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+// Calldata: the selector followed by 32-byte argument words.
+pub(crate) fn calldata(signature: &str, args: &[[u8; 32]]) -> Vec<u8> {
+    let mut data = selector(signature).to_vec();
+    for arg in args {
+        data.extend_from_slice(arg);
+    }
+    data
+}
+
+// The `index`th 32-byte word of some return data.
+pub(crate) fn word(data: &[u8], index: usize) -> Result<[u8; 32], PolyError> {
+    let slice = data.get(index * 32..(index + 1) * 32).ok_or(PolyError::Shape("contract returned too little data"))?;
+    let mut word = [0; 32];
+    word.copy_from_slice(slice);
+    Ok(word)
+}
+
+// A uint256 scaled down by `decimals`, or None when it's too large to be anything but an
+// "unlimited" approval.
+pub(crate) fn scaled(word: &[u8; 32], decimals: i32) -> Option<f64> {
+    if word[..16].iter().any(|&b| b != 0) {
+        return None;
+    }
+    let mut low = [0; 16];
+    low.copy_from_slice(&word[16..]);
+    Some(u128::from_be_bytes(low) as f64 / 10f64.powi(decimals))
+}
@@ -0,0 +1,35 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::model::Row;
+
+pub enum Matcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    // Substring matching is case-insensitive; regexes are case-insensitive unless they opt out with (?-i).
+    pub fn new(pattern: &str, regex: bool) -> Result<Self, regex::Error> {
+        if regex {
+            let re = RegexBuilder::new(pattern).case_insensitive(true).build()?;
+            Ok(Matcher::Regex(re))
+        } else {
+            Ok(Matcher::Substring(pattern.to_lowercase()))
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Matcher::Substring(needle) => text.to_lowercase().contains(needle),
+            Matcher::Regex(re) => re.is_match(text),
+        }
+    }
+
+    pub fn matches_row(&self, row: &Row) -> bool {
+        self.is_match(&row.title) || self.is_match(&row.event)
+    }
+}
+
+pub fn search_rows(rows: Vec<Row>, matcher: &Matcher) -> Vec<Row> {
+    rows.into_iter().filter(|row| matcher.matches_row(row)).collect()
+}
@@ -0,0 +1,119 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset};
+use serde::Deserialize;
+
+use crate::model::Row;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    Volume,
+    Volume24h,
+    Change,
+    End,
+    Liquidity,
+}
+
+impl SortKey {
+    // Bigger is more interesting for every key except end date, where soonest comes first.
+    pub fn default_descending(self) -> bool {
+        !matches!(self, SortKey::End)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::Volume => "Volume",
+            SortKey::Volume24h => "24h Volume",
+            SortKey::Change => "24h Change",
+            SortKey::End => "End Date",
+            SortKey::Liquidity => "Liquidity",
+        }
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "volume" => Ok(SortKey::Volume),
+            "volume24h" => Ok(SortKey::Volume24h),
+            "change" => Ok(SortKey::Change),
+            "end" => Ok(SortKey::End),
+            "liquidity" => Ok(SortKey::Liquidity),
+            other => Err(format!(
+                "unknown sort key '{other}' (expected volume, volume24h, change, end, liquidity)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SortKey::Volume => "volume",
+            SortKey::Volume24h => "volume24h",
+            SortKey::Change => "change",
+            SortKey::End => "end",
+            SortKey::Liquidity => "liquidity",
+        };
+        f.write_str(name)
+    }
+}
+
+fn end_time(row: &Row) -> Option<DateTime<FixedOffset>> {
+    row.end_date.as_deref().and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+}
+
+fn cmp_f64(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+// Missing values sort last in either direction, so `n/a` rows never crowd the top.
+fn cmp_present<T>(a: Option<T>, b: Option<T>, descending: bool, cmp: impl Fn(&T, &T) -> Ordering) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => cmp(&b, &a),
+        (Some(a), Some(b)) => cmp(&a, &b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+pub fn compare_rows(a: &Row, b: &Row, key: SortKey, descending: bool) -> Ordering {
+    match key {
+        SortKey::Volume => cmp_present(Some(a.volume), Some(b.volume), descending, |x, y| cmp_f64(*x, *y)),
+        SortKey::Volume24h => {
+            cmp_present(Some(a.volume_24h), Some(b.volume_24h), descending, |x, y| cmp_f64(*x, *y))
+        }
+        SortKey::Change => cmp_present(a.change_24h_pct, b.change_24h_pct, descending, |x, y| cmp_f64(*x, *y)),
+        SortKey::End => cmp_present(end_time(a), end_time(b), descending, Ord::cmp),
+        SortKey::Liquidity => cmp_present(a.liquidity, b.liquidity, descending, |x, y| cmp_f64(*x, *y)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortSpec {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl SortSpec {
+    pub fn new(key: SortKey) -> Self {
+        SortSpec { key, descending: key.default_descending() }
+    }
+}
+
+// Comparator chain: later specs only break ties left by earlier ones.
+pub fn sort_rows(rows: &mut [Row], specs: &[SortSpec]) {
+    rows.sort_by(|a, b| {
+        specs
+            .iter()
+            .fold(Ordering::Equal, |ord, spec| {
+                ord.then_with(|| compare_rows(a, b, spec.key, spec.descending))
+            })
+    });
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::model::Row;
+
+// How many days the baseline averages over. The window ends a day before now so a spike in
+// progress doesn't raise its own baseline.
+pub const BASELINE_DAYS: i64 = 7;
+
+// A market's lifetime volume as of one stored snapshot.
+#[derive(Debug, Clone)]
+pub struct VolumeSample {
+    pub slug: String,
+    pub taken_at: DateTime<Utc>,
+    pub volume: f64,
+}
+
+// Average daily volume per market over the trailing week. Lifetime volume only grows, so what
+// traded between a market's first and last snapshot in the window, over the days between them,
+// is its daily average however unevenly the snapshots were taken.
+#[derive(Debug, Clone, Default)]
+pub struct VolumeBaseline {
+    daily: HashMap<String, f64>,
+}
+
+impl VolumeBaseline {
+    // The span of snapshots a baseline as of `now` is built from.
+    pub fn window(now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let until = now - Duration::days(1);
+        (until - Duration::days(BASELINE_DAYS), until)
+    }
+
+    // Markets whose snapshots span less than a day get no baseline: too little history to say
+    // what normal looks like.
+    pub fn from_samples(samples: impl IntoIterator<Item = VolumeSample>) -> Self {
+        let mut spans: HashMap<String, (VolumeSample, VolumeSample)> = HashMap::new();
+        for sample in samples {
+            match spans.get_mut(&sample.slug) {
+                Some((first, last)) => {
+                    if sample.taken_at < first.taken_at {
+                        *first = sample;
+                    } else if sample.taken_at > last.taken_at {
+                        *last = sample;
+                    }
+                }
+                None => {
+                    spans.insert(sample.slug.clone(), (sample.clone(), sample));
+                }
+            }
+        }
+
+        let daily = spans
+            .into_iter()
+            .filter_map(|(slug, (first, last))| {
+                let days = (last.taken_at - first.taken_at).num_seconds() as f64 / 86_400.0;
+                (days >= 1.0).then(|| (slug, (last.volume - first.volume).max(0.0) / days))
+            })
+            .collect();
+        VolumeBaseline { daily }
+    }
+
+    pub fn daily_average(&self, slug: &str) -> Option<f64> {
+        self.daily.get(slug).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.daily.is_empty()
+    }
+}
+
+// Sets each row's `spike` to its 24h volume over its baseline when that's at least `threshold`,
+// and clears it otherwise.
+pub fn attach_spikes(rows: &mut [Row], baseline: &VolumeBaseline, threshold: f64) {
+    for row in rows {
+        row.spike = row
+            .slug
+            .as_deref()
+            .and_then(|slug| baseline.daily_average(slug))
+            .filter(|average| *average > 0.0)
+            .map(|average| row.volume_24h / average)
+            .filter(|ratio| *ratio >= threshold);
+    }
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::PolyError;
+use crate::fetch::BASE_URL;
+use crate::http::get_json;
+use crate::model::{lenient_string, parse_time, Event, Market};
+
+pub const SPORTS_URL: &str = "https://gamma-api.polymarket.com/sports";
+
+// A league gamma lists under /sports, e.g. `nba`. Its games are events in one series.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct League {
+    pub sport: String,
+    #[serde(deserialize_with = "lenient_string")]
+    pub series: Option<String>,
+    // Comma-separated tag ids.
+    pub tags: Option<String>,
+}
+
+// One game as a head-to-head line: both teams with their moneyline prices.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Matchup {
+    pub event: String,
+    pub slug: Option<String>,
+    pub team_a: String,
+    pub team_b: String,
+    pub price_a: Option<f64>,
+    pub price_b: Option<f64>,
+    // RFC 3339; gamma's own format varies.
+    pub start_time: Option<String>,
+    // Across every line on the game, not just the moneyline.
+    pub volume: f64,
+}
+
+impl Matchup {
+    // The game's moneyline: the market gamma tags as one, else the first open two-way market
+    // whose outcomes are teams rather than Yes/No. Futures and props have neither.
+    fn from_event(event: &Event) -> Option<Self> {
+        let open = |m: &&Market| m.closed != Some(true) && m.outcomes.len() == 2;
+        let teams = |m: &&Market| {
+            !m.outcomes.iter().any(|o| o.eq_ignore_ascii_case("yes") || o.eq_ignore_ascii_case("no"))
+        };
+        let market = event
+            .markets
+            .iter()
+            .filter(open)
+            .find(|m| m.sports_market_type.as_deref() == Some("moneyline"))
+            .or_else(|| event.markets.iter().filter(open).find(teams))?;
+
+        let start_time = market
+            .game_start_time
+            .as_deref()
+            .and_then(parse_time)
+            .map(|at| at.to_rfc3339());
+        let volume = event.volume.unwrap_or_else(|| event.markets.iter().map(Market::total_volume).sum());
+        Some(Matchup {
+            event: event.display_title().to_string(),
+            slug: event.slug.clone(),
+            team_a: market.outcomes[0].clone(),
+            team_b: market.outcomes[1].clone(),
+            price_a: market.outcome_prices.first().copied(),
+            price_b: market.outcome_prices.get(1).copied(),
+            start_time,
+            volume,
+        })
+    }
+}
+
+pub async fn fetch_leagues() -> Result<Vec<League>, PolyError> {
+    let payload = get_json(SPORTS_URL, &[]).await?;
+
+    match payload {
+        Value::Array(_) => Ok(serde_json::from_value(payload)?),
+        _ => Err(PolyError::Shape("expected array")),
+    }
+}
+
+// The league's open games among up to `limit` of its events, soonest start first.
+pub async fn fetch_matchups(league: &League, limit: usize) -> Result<Vec<Matchup>, PolyError> {
+    let series = league
+        .series
+        .as_deref()
+        .ok_or_else(|| PolyError::NotFound(format!("a series for league '{}'", league.sport)))?;
+    let payload = get_json(
+        BASE_URL,
+        &[
+            ("series_id", series.to_string()),
+            ("active", "true".to_string()),
+            ("closed", "false".to_string()),
+            ("limit", limit.to_string()),
+        ],
+    )
+    .await?;
+
+    let events: Vec<Event> = match payload {
+        Value::Array(_) => serde_json::from_value(payload)?,
+        _ => return Err(PolyError::Shape("expected array")),
+    };
+    let mut matchups: Vec<Matchup> = events.iter().filter_map(Matchup::from_event).collect();
+    // Start times are all RFC 3339 in UTC, so they sort as text; games without one go last.
+    matchups.sort_by(|a, b| (a.start_time.is_none(), &a.start_time).cmp(&(b.start_time.is_none(), &b.start_time)));
+    Ok(matchups)
+}
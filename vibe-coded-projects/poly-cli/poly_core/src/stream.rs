@@ -0,0 +1,145 @@
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio::time::{interval, Interval, MissedTickBehavior};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tracing::info;
+
+use crate::clob::Level;
+use crate::error::PolyError;
+use crate::model::{lenient_f64, string_f64};
+
+pub const WS_MARKET_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+
+// The market channel drops idle connections, so a text PING is sent on this cadence.
+const PING_EVERY: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceUpdate {
+    pub asset_id: String,
+    pub price: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+enum WsMessage {
+    Book {
+        asset_id: String,
+        #[serde(default)]
+        bids: Vec<Level>,
+        #[serde(default)]
+        asks: Vec<Level>,
+    },
+    PriceChange {
+        #[serde(default)]
+        price_changes: Vec<PriceChange>,
+    },
+    LastTradePrice {
+        asset_id: String,
+        #[serde(deserialize_with = "string_f64")]
+        price: f64,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct PriceChange {
+    asset_id: String,
+    #[serde(default, deserialize_with = "lenient_f64")]
+    best_bid: Option<f64>,
+    #[serde(default, deserialize_with = "lenient_f64")]
+    best_ask: Option<f64>,
+}
+
+fn midpoint(bid: Option<f64>, ask: Option<f64>) -> Option<f64> {
+    match (bid, ask) {
+        (Some(b), Some(a)) => Some((b + a) / 2.0),
+        (b, a) => b.or(a),
+    }
+}
+
+fn updates_from(msg: WsMessage) -> Vec<PriceUpdate> {
+    match msg {
+        WsMessage::Book { asset_id, bids, asks } => {
+            let best_bid = bids.iter().map(|l| l.price).reduce(f64::max);
+            let best_ask = asks.iter().map(|l| l.price).reduce(f64::min);
+            midpoint(best_bid, best_ask)
+                .map(|price| vec![PriceUpdate { asset_id, price }])
+                .unwrap_or_default()
+        }
+        WsMessage::PriceChange { price_changes } => price_changes
+            .into_iter()
+            .filter_map(|c| {
+                midpoint(c.best_bid, c.best_ask).map(|price| PriceUpdate { asset_id: c.asset_id, price })
+            })
+            .collect(),
+        WsMessage::LastTradePrice { asset_id, price } => vec![PriceUpdate { asset_id, price }],
+        WsMessage::Other => Vec::new(),
+    }
+}
+
+// Frames carry either a single event object or an array of them; anything unparseable
+// (e.g. the server's "PONG" replies) yields no updates.
+fn parse_updates(text: &str) -> Vec<PriceUpdate> {
+    let messages = match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(items)) => items,
+        Ok(item @ Value::Object(_)) => vec![item],
+        _ => return Vec::new(),
+    };
+
+    messages
+        .into_iter()
+        .filter_map(|m| serde_json::from_value::<WsMessage>(m).ok())
+        .flat_map(updates_from)
+        .collect()
+}
+
+pub struct MarketChannel {
+    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    ping: Interval,
+}
+
+impl MarketChannel {
+    pub async fn connect(asset_ids: &[String]) -> Result<Self, PolyError> {
+        let (mut ws, _) = connect_async(WS_MARKET_URL).await?;
+        info!(url = WS_MARKET_URL, assets = asset_ids.len(), "connected to price stream");
+        let subscribe = json!({ "assets_ids": asset_ids, "type": "market" });
+        ws.send(Message::Text(subscribe.to_string().into())).await?;
+
+        let mut ping = interval(PING_EVERY);
+        ping.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        ping.reset();
+
+        Ok(MarketChannel { ws, ping })
+    }
+
+    // Waits for the next frame that carries at least one price update. Returns None once
+    // the server closes the connection.
+    pub async fn next_updates(&mut self) -> Option<Result<Vec<PriceUpdate>, PolyError>> {
+        loop {
+            tokio::select! {
+                _ = self.ping.tick() => {
+                    if let Err(e) = self.ws.send(Message::Text("PING".into())).await {
+                        return Some(Err(e.into()));
+                    }
+                }
+                msg = self.ws.next() => match msg? {
+                    Ok(Message::Text(text)) => {
+                        let updates = parse_updates(&text);
+                        if !updates.is_empty() {
+                            return Some(Ok(updates));
+                        }
+                    }
+                    Ok(Message::Close(_)) => return None,
+                    Ok(_) => {}
+                    Err(e) => return Some(Err(e.into())),
+                },
+            }
+        }
+    }
+}
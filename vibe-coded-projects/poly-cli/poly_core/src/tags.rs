@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::model::Event;
+
+// One tag across a set of events: how many events and markets carry it and their combined
+// lifetime volume. An event with several tags counts toward each of them.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagSummary {
+    pub slug: String,
+    pub label: Option<String>,
+    pub events: usize,
+    pub markets: usize,
+    pub volume: f64,
+}
+
+// Tags seen on `events`, highest volume first. Tags without a slug can't be passed to `--tag`
+// by slug, so they're keyed by label instead.
+pub fn summarize_tags(events: &[Event]) -> Vec<TagSummary> {
+    let mut summaries: HashMap<String, TagSummary> = HashMap::new();
+    for event in events {
+        let volume: f64 = event.markets.iter().map(|m| m.total_volume()).sum();
+        let volume = if volume > 0.0 { volume } else { event.volume.unwrap_or(0.0) };
+        for tag in &event.tags {
+            let Some(key) = tag.slug.as_deref().or(tag.label.as_deref()).filter(|k| !k.is_empty()) else {
+                continue;
+            };
+            let summary = summaries.entry(key.to_lowercase()).or_insert_with(|| TagSummary {
+                slug: key.to_string(),
+                label: tag.label.clone(),
+                events: 0,
+                markets: 0,
+                volume: 0.0,
+            });
+            summary.events += 1;
+            summary.markets += event.markets.len();
+            summary.volume += volume;
+        }
+    }
+
+    let mut summaries: Vec<TagSummary> = summaries.into_values().collect();
+    summaries.sort_by(|a, b| b.volume.total_cmp(&a.volume).then_with(|| a.slug.cmp(&b.slug)));
+    summaries
+}
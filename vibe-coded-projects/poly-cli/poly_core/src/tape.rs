@@ -0,0 +1,115 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::PolyError;
+
+// Price-history windows are computed from the clock, so they're ignored when matching a request
+// against a recording; every other query parameter must match exactly.
+const CLOCK_PARAMS: [&str; 2] = ["startTs", "endTs"];
+
+// One recorded API response, stored as `<unix millis>-<seq>-<endpoint>.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    recorded_at: DateTime<Utc>,
+    url: String,
+    payload: Value,
+}
+
+enum Tape {
+    Record { dir: PathBuf, seq: AtomicU64 },
+    // Recorded payloads per request, oldest first.
+    Replay(Mutex<HashMap<String, VecDeque<Value>>>),
+}
+
+// Set at most once, before the first request, by `record_to` or `replay_from`.
+static TAPE: OnceLock<Tape> = OnceLock::new();
+
+// Saves every GET response from here on into `dir`, which is created if missing.
+pub fn record_to(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    install(Tape::Record { dir: dir.to_path_buf(), seq: AtomicU64::new(0) })
+}
+
+// Serves GET requests from a directory written by `record_to` instead of the network. Repeated
+// requests step through the recordings in the order they were made and then keep returning the
+// last one, so a replayed watch session evolves the way the recorded one did.
+pub fn replay_from(dir: &Path) -> io::Result<usize> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    let invalid = |path: &Path, e: &dyn std::fmt::Display| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))
+    };
+    let mut responses: HashMap<String, VecDeque<Value>> = HashMap::new();
+    for path in &files {
+        let entry: Entry = serde_json::from_str(&fs::read_to_string(path)?).map_err(|e| invalid(path, &e))?;
+        let url = Url::parse(&entry.url).map_err(|e| invalid(path, &e))?;
+        responses.entry(replay_key(&url)).or_default().push_back(entry.payload);
+    }
+
+    install(Tape::Replay(Mutex::new(responses)))?;
+    Ok(files.len())
+}
+
+fn install(tape: Tape) -> io::Result<()> {
+    TAPE.set(tape)
+        .map_err(|_| io::Error::new(io::ErrorKind::AlreadyExists, "record/replay is already set up"))
+}
+
+// The request minus its clock parameters, so a price-history window asked for a minute later
+// still finds the earlier response.
+pub(crate) fn replay_key(url: &Url) -> String {
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !CLOCK_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    let mut key = url.clone();
+    key.query_pairs_mut().clear().extend_pairs(pairs);
+    key.to_string()
+}
+
+// The recorded payload for a request when replaying, or None when requests should hit the network.
+pub(crate) fn replay(url: &Url) -> Option<Result<Value, PolyError>> {
+    let Some(Tape::Replay(responses)) = TAPE.get() else {
+        return None;
+    };
+    let mut responses = responses.lock().unwrap_or_else(PoisonError::into_inner);
+    let payload = responses.get_mut(&replay_key(url)).and_then(|queue| match queue.len() {
+        1 => queue.front().cloned(),
+        _ => queue.pop_front(),
+    });
+    Some(payload.ok_or_else(|| PolyError::NotFound(format!("recorded response for {url}"))))
+}
+
+pub(crate) fn record(url: &Url, payload: &Value) -> Result<(), PolyError> {
+    let Some(Tape::Record { dir, seq }) = TAPE.get() else {
+        return Ok(());
+    };
+    let recorded_at = Utc::now();
+    let endpoint = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|segment| !segment.is_empty())
+        .unwrap_or("response");
+    let name = format!(
+        "{}-{:06}-{endpoint}.json",
+        recorded_at.timestamp_millis(),
+        seq.fetch_add(1, Ordering::Relaxed)
+    );
+    let entry = Entry { recorded_at, url: url.to_string(), payload: payload.clone() };
+    fs::write(dir.join(name), serde_json::to_string_pretty(&entry)?).map_err(PolyError::Tape)?;
+    Ok(())
+}
@@ -0,0 +1,403 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::warn;
+
+use crate::auth::ClobAuth;
+use crate::clob::CLOB_URL;
+use crate::error::PolyError;
+use crate::fetch::fetch_markets_by_token;
+use crate::http::get_json;
+use crate::model::string_f64;
+use crate::wallet::{
+    checksum, domain_separator, hash_struct, parse_address, word_address, word_decimal, word_uint, Wallet,
+};
+
+// The exchange contracts orders are signed for; negative-risk markets settle through their own.
+pub(crate) const CTF_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+pub(crate) const NEG_RISK_CTF_EXCHANGE: &str = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+// Cursors for the first page of a CLOB listing and past its last one.
+const FIRST_CURSOR: &str = "MA==";
+const END_CURSOR: &str = "LTE=";
+// Stops a cursor that never ends from paging forever.
+const MAX_ORDER_PAGES: usize = 50;
+
+const ORDER_TYPE: &str = "Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,\
+                          uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,\
+                          uint256 feeRateBps,uint8 side,uint8 signatureType)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl FromStr for Side {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "buy" => Ok(Side::Buy),
+            "sell" => Ok(Side::Sell),
+            other => Err(format!("unknown side '{other}' (expected buy or sell)")),
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        })
+    }
+}
+
+// Who holds the funds an order spends: the signing key itself, or a Polymarket proxy wallet
+// (email/Magic accounts) or Gnosis Safe (browser-wallet accounts) it controls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureType {
+    #[default]
+    Eoa,
+    Proxy,
+    Safe,
+}
+
+impl SignatureType {
+    fn code(self) -> u8 {
+        match self {
+            SignatureType::Eoa => 0,
+            SignatureType::Proxy => 1,
+            SignatureType::Safe => 2,
+        }
+    }
+}
+
+impl FromStr for SignatureType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "eoa" => Ok(SignatureType::Eoa),
+            "proxy" => Ok(SignatureType::Proxy),
+            "safe" => Ok(SignatureType::Safe),
+            other => Err(format!("unknown signature type '{other}' (expected eoa, proxy or safe)")),
+        }
+    }
+}
+
+// A good-til-cancelled limit order as the user asked for it.
+#[derive(Debug, Clone)]
+pub struct OrderRequest {
+    pub token_id: String,
+    pub side: Side,
+    pub price: f64,
+    pub size: f64,
+    // The proxy wallet or Safe holding the funds; None when the key trades for itself.
+    pub funder: Option<String>,
+    pub signature_type: SignatureType,
+}
+
+// What the CLOB requires of orders on one outcome token.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderMarket {
+    pub tick_size: f64,
+    pub neg_risk: bool,
+    pub fee_rate_bps: u64,
+}
+
+impl OrderMarket {
+    // Prices must sit on the tick grid strictly inside (0, 1); sizes are whole hundredths.
+    pub fn check(&self, request: &OrderRequest) -> Result<(), String> {
+        if !request.price.is_finite() || !request.size.is_finite() {
+            return Err("price and size must be numbers".to_string());
+        }
+        let tick = self.tick_size;
+        let ticks = request.price / tick;
+        if (ticks - ticks.round()).abs() > 1e-6 {
+            return Err(format!("price {} is not a multiple of the tick size {tick}", request.price));
+        }
+        if request.price < tick || request.price > 1.0 - tick {
+            return Err(format!("price must be between {tick} and {}", 1.0 - tick));
+        }
+        let cents = request.size * 100.0;
+        if (cents - cents.round()).abs() > 1e-6 {
+            return Err(format!("size {} has more than 2 decimals", request.size));
+        }
+        if hundredths(request.size) == 0 {
+            return Err("size must be at least 0.01 shares".to_string());
+        }
+        if request.signature_type != SignatureType::Eoa && request.funder.is_none() {
+            return Err("proxy and safe signature types need the funder address holding the funds".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn number(payload: &Value, key: &str) -> Option<f64> {
+    match payload.get(key)? {
+        Value::String(s) => s.parse().ok(),
+        other => other.as_f64(),
+    }
+}
+
+// Tick size, exchange and fee rate for a token; the order must be signed to match all three.
+pub async fn fetch_order_market(token_id: &str) -> Result<OrderMarket, PolyError> {
+    let query = [("token_id", token_id.to_string())];
+    let tick = get_json(&format!("{CLOB_URL}/tick-size"), &query).await?;
+    let tick_size = number(&tick, "minimum_tick_size").ok_or(PolyError::Shape("tick-size without minimum_tick_size"))?;
+    let neg_risk = get_json(&format!("{CLOB_URL}/neg-risk"), &query).await?;
+    let neg_risk = neg_risk.get("neg_risk").and_then(Value::as_bool).unwrap_or(false);
+    // Most markets charge nothing; an order signed with the wrong rate is rejected, saying why.
+    let fee_rate_bps = match get_json(&format!("{CLOB_URL}/fee-rate"), &query).await {
+        Ok(fee) => number(&fee, "base_fee").unwrap_or(0.0) as u64,
+        Err(e) => {
+            warn!(token_id, error = %e, "no fee rate; signing with 0");
+            0
+        }
+    };
+    Ok(OrderMarket { tick_size, neg_risk, fee_rate_bps })
+}
+
+// Bid and ask for a market maker, centred on the book's midpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Quote {
+    pub mid: f64,
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl Quote {
+    // `spread` apart around `mid`, rounded outward onto the tick grid and kept inside (0, 1).
+    // None when the market is too close to 0 or 1 to fit both sides.
+    pub fn around(mid: f64, spread: f64, tick: f64) -> Option<Quote> {
+        let on_grid = |ticks: f64| (ticks * tick * 10_000.0).round() / 10_000.0;
+        let bid = on_grid(((mid - spread / 2.0) / tick + 1e-9).floor()).max(tick);
+        let ask = on_grid(((mid + spread / 2.0) / tick - 1e-9).ceil()).min(on_grid((1.0 / tick).round() - 1.0));
+        (bid < ask).then_some(Quote { mid, bid, ask })
+    }
+}
+
+// Sizes go to the exchange in whole hundredths of a share; `check` has already refused any finer.
+fn hundredths(size: f64) -> u128 {
+    (size * 100.0).round().max(0.0) as u128
+}
+
+// A signed order, in the JSON shape POST /order takes.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedOrder {
+    pub salt: u64,
+    pub maker: String,
+    pub signer: String,
+    pub taker: String,
+    pub token_id: String,
+    pub maker_amount: String,
+    pub taker_amount: String,
+    pub expiration: String,
+    pub nonce: String,
+    pub fee_rate_bps: String,
+    pub side: Side,
+    pub signature_type: u8,
+    pub signature: String,
+}
+
+// Builds and signs `request` for `market`, which it should already have passed `check`.
+// Amounts are in millionths (USDC and outcome shares both have 6 decimals): a buy gives
+// price × size USDC for size shares, a sell the reverse.
+pub fn sign_order(wallet: &Wallet, request: &OrderRequest, market: &OrderMarket) -> Result<SignedOrder, PolyError> {
+    let salt = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64 >> 8);
+    sign_order_with_salt(wallet, request, market, salt)
+}
+
+// `sign_order` with a chosen salt, so a signature can be reproduced.
+pub fn sign_order_with_salt(
+    wallet: &Wallet,
+    request: &OrderRequest,
+    market: &OrderMarket,
+    salt: u64,
+) -> Result<SignedOrder, PolyError> {
+    let size = hundredths(request.size);
+    let price = (request.price * 10_000.0).round() as u128;
+    let (shares, usdc) = size
+        .checked_mul(10_000)
+        .zip(size.checked_mul(price))
+        .ok_or_else(|| PolyError::Rejected(format!("size {} is too large to sign", request.size)))?;
+    let (maker_amount, taker_amount) = match request.side {
+        Side::Buy => (usdc, shares),
+        Side::Sell => (shares, usdc),
+    };
+    let signer = wallet.address_bytes();
+    let maker = match &request.funder {
+        Some(funder) => parse_address(funder)?,
+        None => signer,
+    };
+    let exchange = parse_address(if market.neg_risk { NEG_RISK_CTF_EXCHANGE } else { CTF_EXCHANGE })?;
+    let side = match request.side {
+        Side::Buy => 0,
+        Side::Sell => 1,
+    };
+
+    let hash = hash_struct(
+        ORDER_TYPE,
+        &[
+            word_uint(salt.into()),
+            word_address(&maker),
+            word_address(&signer),
+            word_address(&parse_address(ZERO_ADDRESS)?),
+            word_decimal(&request.token_id)?,
+            word_uint(maker_amount),
+            word_uint(taker_amount),
+            word_uint(0),
+            word_uint(0),
+            word_uint(market.fee_rate_bps.into()),
+            word_uint(side),
+            word_uint(request.signature_type.code().into()),
+        ],
+    );
+    let domain = domain_separator("Polymarket CTF Exchange", "1", Some(&exchange));
+    Ok(SignedOrder {
+        salt,
+        maker: checksum(&maker),
+        signer: wallet.address(),
+        taker: ZERO_ADDRESS.to_string(),
+        token_id: request.token_id.clone(),
+        maker_amount: maker_amount.to_string(),
+        taker_amount: taker_amount.to_string(),
+        expiration: "0".to_string(),
+        nonce: "0".to_string(),
+        fee_rate_bps: market.fee_rate_bps.to_string(),
+        side: request.side,
+        signature_type: request.signature_type.code(),
+        signature: wallet.sign_typed_data(&domain, &hash)?,
+    })
+}
+
+// The CLOB's answer to a new order: `live` when it rests on the book, `matched` when it filled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlacedOrder {
+    #[serde(rename = "orderID", default)]
+    pub order_id: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub success: bool,
+    #[serde(rename = "errorMsg", default)]
+    pub error_msg: String,
+}
+
+// Submits a signed good-til-cancelled order.
+pub async fn post_order(auth: &ClobAuth, order: &SignedOrder) -> Result<PlacedOrder, PolyError> {
+    let body = json!({ "order": order, "owner": auth.creds.api_key, "orderType": "GTC" });
+    let payload = auth.send(Method::POST, "/order", &[], Some(&body)).await?;
+    let placed: PlacedOrder = serde_json::from_value(payload)?;
+    if !placed.success && !placed.error_msg.is_empty() {
+        return Err(PolyError::Rejected(placed.error_msg));
+    }
+    // Without an id there's no order to track or cancel, whatever `success` says.
+    if placed.order_id.is_empty() {
+        return Err(PolyError::Rejected("the CLOB accepted the order without returning an order id".to_string()));
+    }
+    Ok(placed)
+}
+
+// A resting order, as GET /data/orders lists them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub id: String,
+    #[serde(default)]
+    pub status: String,
+    // The market's condition id.
+    #[serde(default)]
+    pub market: String,
+    pub asset_id: String,
+    pub side: Side,
+    #[serde(deserialize_with = "string_f64")]
+    pub original_size: f64,
+    #[serde(deserialize_with = "string_f64")]
+    pub size_matched: f64,
+    #[serde(deserialize_with = "string_f64")]
+    pub price: f64,
+    #[serde(default)]
+    pub outcome: String,
+    // Unix seconds.
+    #[serde(default)]
+    pub created_at: i64,
+    // The market question, looked up from gamma by `attach_order_titles`.
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+impl OpenOrder {
+    pub fn remaining(&self) -> f64 {
+        (self.original_size - self.size_matched).max(0.0)
+    }
+}
+
+// Every open order for the credentials' address, optionally only those in one market.
+pub async fn fetch_open_orders(auth: &ClobAuth, market: Option<&str>) -> Result<Vec<OpenOrder>, PolyError> {
+    let mut orders = Vec::new();
+    let mut cursor = FIRST_CURSOR.to_string();
+    for _ in 0..MAX_ORDER_PAGES {
+        let mut query = vec![("next_cursor", cursor.clone())];
+        if let Some(market) = market {
+            query.push(("market", market.to_string()));
+        }
+        let page = auth.send(Method::GET, "/data/orders", &query, None).await?;
+        let data = page.get("data").cloned().ok_or(PolyError::Shape("orders page without data"))?;
+        orders.extend(serde_json::from_value::<Vec<OpenOrder>>(data)?);
+        match page.get("next_cursor").and_then(Value::as_str) {
+            Some(next) if !next.is_empty() && next != END_CURSOR => cursor = next.to_string(),
+            _ => return Ok(orders),
+        }
+    }
+    warn!(pages = MAX_ORDER_PAGES, "open orders still paging; showing what was fetched");
+    Ok(orders)
+}
+
+// Fills in each order's market question. The CLOB only knows token ids, so titles come from
+// gamma in one request; if that fails the orders are shown without them.
+pub async fn attach_order_titles(orders: &mut [OpenOrder]) {
+    let mut tokens: Vec<String> = orders.iter().map(|o| o.asset_id.clone()).collect();
+    tokens.sort();
+    tokens.dedup();
+    let markets = match fetch_markets_by_token(&tokens).await {
+        Ok(m) => m,
+        Err(e) => {
+            warn!(error = %e, "failed to look up order titles");
+            return;
+        }
+    };
+    for order in orders.iter_mut() {
+        order.title = markets
+            .iter()
+            .find(|m| m.clob_token_ids.contains(&order.asset_id))
+            .and_then(|m| m.question.clone().or_else(|| m.title.clone()));
+    }
+}
+
+// What a cancel request did: the order ids it cancelled, and why any others weren't.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cancelled {
+    pub canceled: Vec<String>,
+    pub not_canceled: BTreeMap<String, String>,
+}
+
+pub async fn cancel_order(auth: &ClobAuth, order_id: &str) -> Result<Cancelled, PolyError> {
+    let body = json!({ "orderID": order_id });
+    Ok(serde_json::from_value(auth.send(Method::DELETE, "/order", &[], Some(&body)).await?)?)
+}
+
+pub async fn cancel_all_orders(auth: &ClobAuth) -> Result<Cancelled, PolyError> {
+    Ok(serde_json::from_value(auth.send(Method::DELETE, "/cancel-all", &[], None).await?)?)
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::clob::{fetch_price_history, PricePoint};
+use crate::error::PolyError;
+use crate::http::bounded;
+use crate::model::Row;
+
+// Hourly samples are all the trend columns need, and they only change once an hour.
+const FIDELITY_MINUTES: i64 = 60;
+
+// Recent price histories per token, reused until `ttl` passes so the columns built from them
+// (sparklines, momentum) share one request per market and watch-mode refreshes don't refetch
+// history that can't have changed yet.
+#[derive(Debug)]
+pub struct HistoryCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<(String, i64), (Instant, Vec<PricePoint>)>>,
+}
+
+impl HistoryCache {
+    pub fn new(ttl: Duration) -> Self {
+        HistoryCache { ttl, entries: Mutex::new(HashMap::new()) }
+    }
+
+    // Hourly prices over the last `hours`, oldest first.
+    pub async fn recent(&self, token: &str, hours: i64) -> Result<Vec<PricePoint>, PolyError> {
+        let key = (token.to_string(), hours);
+        {
+            let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some((fetched, points)) = entries.get(&key) {
+                if fetched.elapsed() < self.ttl {
+                    return Ok(points.clone());
+                }
+            }
+        }
+
+        let end = chrono::Utc::now().timestamp();
+        let points = fetch_price_history(token, end - hours * 3600, end, FIDELITY_MINUTES).await?;
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.retain(|_, (fetched, _)| fetched.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), points.clone()));
+        Ok(points)
+    }
+
+    // Histories for every row's token, fetched concurrently. Rows whose history can't be
+    // fetched get None rather than failing the refresh.
+    async fn for_rows(&self, rows: &[Row], hours: i64) -> Vec<Option<Vec<PricePoint>>> {
+        bounded(rows.iter(), |row| async move {
+            match &row.token_id {
+                Some(token) => self.recent(token, hours).await.ok(),
+                None => None,
+            }
+        })
+        .await
+    }
+}
+
+// Fills each row's `trend` with hourly prices over the last `hours`.
+pub async fn attach_trends(cache: &HistoryCache, rows: &mut [Row], hours: i64) {
+    let histories = cache.for_rows(rows, hours).await;
+    for (row, history) in rows.iter_mut().zip(histories) {
+        row.trend = history.unwrap_or_default().into_iter().map(|point| point.p).collect();
+    }
+}
+
+// Fills each row's `momentum` from its last 24 hours of prices.
+pub async fn attach_momentum(cache: &HistoryCache, rows: &mut [Row]) {
+    let histories = cache.for_rows(rows, 24).await;
+    for (row, history) in rows.iter_mut().zip(histories) {
+        row.momentum = history.and_then(|points| Momentum::classify(&points));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Momentum {
+    StrongUp,
+    Up,
+    Flat,
+    Down,
+    StrongDown,
+}
+
+impl Momentum {
+    // Moves smaller than this over the window count as flat.
+    const FLAT: f64 = 0.01;
+
+    // Direction comes from the move across the whole window; it's "strong" when the latest
+    // hour moved the same way faster than the window's average hourly pace.
+    pub fn classify(points: &[PricePoint]) -> Option<Momentum> {
+        let [.., before, last] = points else {
+            return None;
+        };
+        let first = points[0];
+        let window = last.p - first.p;
+        if window.abs() < Self::FLAT {
+            return Some(Momentum::Flat);
+        }
+
+        let hours = ((last.t - first.t) as f64 / 3600.0).max(1.0);
+        let latest = last.p - before.p;
+        let accelerating = latest.signum() == window.signum() && latest.abs() > window.abs() / hours;
+        Some(match (window > 0.0, accelerating) {
+            (true, true) => Momentum::StrongUp,
+            (true, false) => Momentum::Up,
+            (false, false) => Momentum::Down,
+            (false, true) => Momentum::StrongDown,
+        })
+    }
+
+    pub fn arrow(self) -> &'static str {
+        match self {
+            Momentum::StrongUp => "↑↑",
+            Momentum::Up => "↑",
+            Momentum::Flat => "→",
+            Momentum::Down => "↓",
+            Momentum::StrongDown => "↓↓",
+        }
+    }
+
+    pub fn rising(self) -> Option<bool> {
+        match self {
+            Momentum::StrongUp | Momentum::Up => Some(true),
+            Momentum::Flat => None,
+            Momentum::Down | Momentum::StrongDown => Some(false),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::PolyError;
+use crate::fetch::{fetch_markets, EventQuery};
+use crate::format::format_amount;
+use crate::kalshi::fetch_kalshi_markets;
+use crate::manifold::fetch_manifold_markets;
+use crate::model::Row;
+
+// Where a row's market trades.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Venue {
+    #[default]
+    Polymarket,
+    Kalshi,
+    Manifold,
+}
+
+impl Venue {
+    pub const ALL: [Venue; 3] = [Venue::Polymarket, Venue::Kalshi, Venue::Manifold];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Venue::Polymarket => "Polymarket",
+            Venue::Kalshi => "Kalshi",
+            Venue::Manifold => "Manifold",
+        }
+    }
+
+    // Manifold trades play money (mana), so its volumes and liquidity aren't dollars.
+    pub fn currency(self) -> &'static str {
+        match self {
+            Venue::Polymarket | Venue::Kalshi => "$",
+            Venue::Manifold => "Ṁ",
+        }
+    }
+
+    pub fn format_money(self, value: f64) -> String {
+        format_amount(value, self.currency())
+    }
+
+    pub fn market_url(self, slug: &str) -> String {
+        match self {
+            Venue::Polymarket => format!("https://polymarket.com/market/{slug}"),
+            Venue::Kalshi => format!("https://kalshi.com/markets/{}", slug.to_lowercase()),
+            // Manifold slugs carry the creator's username, e.g. "user/will-it-rain".
+            Venue::Manifold => format!("https://manifold.markets/{slug}"),
+        }
+    }
+
+    // Rows from this venue's source, for when the venue is only known at runtime.
+    pub async fn fetch_markets(self, query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+        match self {
+            Venue::Polymarket => Polymarket.fetch_markets(query).await,
+            Venue::Kalshi => Kalshi.fetch_markets(query).await,
+            Venue::Manifold => Manifold.fetch_markets(query).await,
+        }
+    }
+}
+
+impl FromStr for Venue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "polymarket" | "poly" => Ok(Venue::Polymarket),
+            "kalshi" => Ok(Venue::Kalshi),
+            "manifold" => Ok(Venue::Manifold),
+            other => {
+                let names: Vec<String> = Venue::ALL.iter().map(|venue| venue.label().to_lowercase()).collect();
+                Err(format!("unknown venue '{other}' (expected {})", names.join(", ")))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Venue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+// A venue whose markets can be listed in the shared `Row` shape. Implementations map the
+// query's limit, status, and tags onto whatever their API supports and filter the rest locally.
+pub trait MarketSource {
+    fn venue(&self) -> Venue;
+
+    fn fetch_markets(&self, query: &EventQuery) -> impl Future<Output = Result<Vec<Row>, PolyError>> + Send;
+}
+
+pub struct Polymarket;
+
+impl MarketSource for Polymarket {
+    fn venue(&self) -> Venue {
+        Venue::Polymarket
+    }
+
+    async fn fetch_markets(&self, query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+        fetch_markets(query).await
+    }
+}
+
+pub struct Kalshi;
+
+impl MarketSource for Kalshi {
+    fn venue(&self) -> Venue {
+        Venue::Kalshi
+    }
+
+    async fn fetch_markets(&self, query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+        fetch_kalshi_markets(query).await
+    }
+}
+
+pub struct Manifold;
+
+impl MarketSource for Manifold {
+    fn venue(&self) -> Venue {
+        Venue::Manifold
+    }
+
+    async fn fetch_markets(&self, query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+        fetch_manifold_markets(query).await
+    }
+}
+
+// Rows from several venues, fetched concurrently and concatenated in the order given.
+pub async fn fetch_venue_markets(venues: &[Venue], query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+    let results = futures_util::future::join_all(venues.iter().map(|venue| venue.fetch_markets(query))).await;
+    let mut rows = Vec::new();
+    for result in results {
+        rows.extend(result?);
+    }
+    Ok(rows)
+}
+
+// Words too common in market titles to say anything about whether two markets match.
+const STOPWORDS: [&str; 24] = [
+    "a", "an", "and", "at", "be", "before", "by", "does", "for", "in", "is", "of", "on", "or", "the", "this", "to",
+    "will", "win", "with", "than", "more", "less", "what",
+];
+
+fn title_words(row: &Row) -> HashSet<String> {
+    row.title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| !word.is_empty() && !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+// Overlap of the two titles' significant words, from 0 (nothing shared) to 1.
+pub fn title_similarity(a: &Row, b: &Row) -> f64 {
+    jaccard(&title_words(a), &title_words(b))
+}
+
+// The same question listed on two venues, as far as title similarity can tell.
+#[derive(Debug, Clone, Serialize)]
+pub struct VenueMatch {
+    pub left: Row,
+    pub right: Row,
+    pub similarity: f64,
+}
+
+impl VenueMatch {
+    // Right price minus left price, in price units.
+    pub fn price_gap(&self) -> Option<f64> {
+        Some(self.right.price? - self.left.price?)
+    }
+}
+
+// Pairs each `left` row with its most similar `right` row scoring at least `min_similarity`,
+// greedily from the best-scoring pair down so every row is used at most once. Best match first.
+pub fn match_markets(left: &[Row], right: &[Row], min_similarity: f64) -> Vec<VenueMatch> {
+    let right_words: Vec<HashSet<String>> = right.iter().map(title_words).collect();
+    let mut candidates = Vec::new();
+    for (i, row) in left.iter().enumerate() {
+        let words = title_words(row);
+        for (j, other) in right_words.iter().enumerate() {
+            let similarity = jaccard(&words, other);
+            if similarity > 0.0 && similarity >= min_similarity {
+                candidates.push((similarity, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let (mut used_left, mut used_right) = (HashSet::new(), HashSet::new());
+    let mut matches = Vec::new();
+    for (similarity, i, j) in candidates {
+        if used_left.contains(&i) || used_right.contains(&j) {
+            continue;
+        }
+        used_left.insert(i);
+        used_right.insert(j);
+        matches.push(VenueMatch { left: left[i].clone(), right: right[j].clone(), similarity });
+    }
+    matches
+}
+
+// The same question priced differently across venues. Every pair of venues in `venues` (one row
+// set each) is matched, and pairs whose prices differ by at least `threshold` are kept, oriented
+// so `left` is the cheaper side. Widest gap first.
+pub fn find_price_gaps(venues: &[Vec<Row>], min_similarity: f64, threshold: f64) -> Vec<VenueMatch> {
+    let mut gaps = Vec::new();
+    for (i, left) in venues.iter().enumerate() {
+        for right in &venues[i + 1..] {
+            for pair in match_markets(left, right, min_similarity) {
+                let Some(gap) = pair.price_gap() else { continue };
+                if gap.abs() < threshold {
+                    continue;
+                }
+                gaps.push(if gap < 0.0 {
+                    VenueMatch { left: pair.right, right: pair.left, similarity: pair.similarity }
+                } else {
+                    pair
+                });
+            }
+        }
+    }
+    gaps.sort_by(|a, b| b.price_gap().unwrap_or(0.0).total_cmp(&a.price_gap().unwrap_or(0.0)));
+    gaps
+}
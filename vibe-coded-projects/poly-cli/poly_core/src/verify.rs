@@ -0,0 +1,116 @@
+use serde::Serialize;
+
+use crate::balance::CONDITIONAL_TOKENS;
+use crate::error::PolyError;
+use crate::fetch::fetch_market;
+use crate::rpc::{calldata, scaled, word, JsonRpc};
+use crate::wallet::word_uint;
+
+// Far more outcomes than any market has; a larger count from the node is garbage, not a condition.
+const MAX_OUTCOME_SLOTS: f64 = 256.0;
+
+// How gamma's reported outcome compares with the payouts on chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    // Both agree on the winner.
+    Match,
+    // Both are settled, on different winners.
+    Mismatch,
+    // Gamma shows a winner the oracle hasn't reported on chain yet.
+    PendingOnChain,
+    // Payouts are on chain but gamma doesn't show a winner (yet, or because the market split).
+    PendingGamma,
+    Unresolved,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Verification {
+    pub title: String,
+    pub slug: Option<String>,
+    pub condition_id: String,
+    pub outcomes: Vec<String>,
+    pub gamma_closed: bool,
+    pub gamma_winner: Option<String>,
+    pub uma_status: Option<String>,
+    // Each outcome's share of the payout once the oracle has reported; None before that.
+    pub payouts: Option<Vec<f64>>,
+    pub chain_winner: Option<String>,
+    pub verdict: Verdict,
+}
+
+fn condition_word(condition_id: &str) -> Result<[u8; 32], PolyError> {
+    let bytes = hex::decode(condition_id.trim().trim_start_matches("0x")).ok().filter(|b| b.len() == 32);
+    let bytes = bytes.ok_or(PolyError::Shape("condition id is not 32 bytes of hex"))?;
+    let mut word = [0; 32];
+    word.copy_from_slice(&bytes);
+    Ok(word)
+}
+
+fn uint(word: &[u8; 32]) -> Result<f64, PolyError> {
+    scaled(word, 0).ok_or(PolyError::Shape("payout out of range"))
+}
+
+// Payout fractions the Conditional Tokens contract holds for a condition; None until reported.
+pub async fn fetch_payouts(rpc: &JsonRpc, condition_id: &str) -> Result<Option<Vec<f64>>, PolyError> {
+    let condition = condition_word(condition_id)?;
+    let slots = rpc.call(CONDITIONAL_TOKENS, &calldata("getOutcomeSlotCount(bytes32)", &[condition])).await?;
+    let slots = uint(&word(&slots, 0)?)?;
+    if slots > MAX_OUTCOME_SLOTS {
+        return Err(PolyError::Shape("condition with more outcome slots than any market has"));
+    }
+    let slots = slots as usize;
+    if slots == 0 {
+        return Err(PolyError::NotFound(format!("condition {condition_id} on chain")));
+    }
+    let denominator = rpc.call(CONDITIONAL_TOKENS, &calldata("payoutDenominator(bytes32)", &[condition])).await?;
+    let denominator = uint(&word(&denominator, 0)?)?;
+    if denominator == 0.0 {
+        return Ok(None);
+    }
+    let mut payouts = Vec::with_capacity(slots);
+    for index in 0..slots {
+        let args = [condition, word_uint(index as u128)];
+        let numerator = rpc.call(CONDITIONAL_TOKENS, &calldata("payoutNumerators(bytes32,uint256)", &args)).await?;
+        payouts.push(uint(&word(&numerator, 0)?)? / denominator);
+    }
+    Ok(Some(payouts))
+}
+
+// Looks a market up on gamma and checks its resolution against the chain.
+pub async fn verify_market(rpc: &JsonRpc, slug: &str) -> Result<Verification, PolyError> {
+    let market = fetch_market(slug).await?;
+    let condition_id = market
+        .condition_id
+        .clone()
+        .ok_or_else(|| PolyError::NotFound(format!("condition id for market '{slug}'")))?;
+    let payouts = fetch_payouts(rpc, &condition_id).await?;
+
+    let gamma_winner = market.resolved_outcome().map(str::to_string);
+    // A split payout (50/50 on an unclear question) has no single winner.
+    let chain_winner = payouts.as_ref().and_then(|payouts| {
+        let index = payouts.iter().position(|&p| p >= 1.0)?;
+        Some(market.outcomes.get(index).cloned().unwrap_or_else(|| format!("outcome {index}")))
+    });
+    let verdict = match (&gamma_winner, &payouts) {
+        (Some(gamma), Some(_)) if chain_winner.as_ref() == Some(gamma) => Verdict::Match,
+        (Some(_), Some(_)) => Verdict::Mismatch,
+        (Some(_), None) => Verdict::PendingOnChain,
+        (None, Some(_)) => Verdict::PendingGamma,
+        (None, None) => Verdict::Unresolved,
+    };
+
+    Ok(Verification {
+        title: market.display_title().to_string(),
+        slug: market.slug.clone(),
+        condition_id,
+        outcomes: market.outcomes.clone(),
+        gamma_closed: market.closed == Some(true),
+        gamma_winner,
+        uma_status: market.uma_resolution_status.clone(),
+        payouts,
+        chain_winner,
+        verdict,
+    })
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+
+use k256::ecdsa::SigningKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use sha3::{Digest, Keccak256};
+
+use crate::error::PolyError;
+
+// Polygon mainnet, where the CLOB settles.
+pub const CHAIN_ID: u64 = 137;
+
+// An Ethereum key that signs the CLOB's EIP-712 messages: API key requests and orders.
+pub struct Wallet {
+    key: SigningKey,
+    address: [u8; 20],
+}
+
+impl Wallet {
+    // A hex private key, with or without 0x.
+    pub fn from_private_key(hex_key: &str) -> Result<Self, PolyError> {
+        let bytes = hex::decode(hex_key.trim().trim_start_matches("0x"))
+            .map_err(|_| PolyError::Auth("private key is not hex".to_string()))?;
+        let key = SigningKey::from_slice(&bytes)
+            .map_err(|_| PolyError::Auth("private key is not a valid secp256k1 key".to_string()))?;
+        let point = key.verifying_key().to_encoded_point(false);
+        let hash = keccak(&point.as_bytes()[1..]);
+        let mut address = [0; 20];
+        address.copy_from_slice(&hash[12..]);
+        Ok(Wallet { key, address })
+    }
+
+    // EIP-55 checksummed, as the CLOB expects in POLY_ADDRESS.
+    pub fn address(&self) -> String {
+        checksum(&self.address)
+    }
+
+    pub(crate) fn address_bytes(&self) -> [u8; 20] {
+        self.address
+    }
+
+    // A 65-byte r‖s‖v signature over the EIP-712 digest of `struct_hash` in `domain`, as 0x hex.
+    pub(crate) fn sign_typed_data(&self, domain: &[u8; 32], struct_hash: &[u8; 32]) -> Result<String, PolyError> {
+        let mut message = Vec::with_capacity(66);
+        message.extend_from_slice(&[0x19, 0x01]);
+        message.extend_from_slice(domain);
+        message.extend_from_slice(struct_hash);
+        let (signature, recovery) = self
+            .key
+            .sign_prehash_recoverable(&keccak(&message))
+            .map_err(|e| PolyError::Auth(format!("signing failed: {e}")))?;
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(27 + recovery.to_byte());
+        Ok(format!("0x{}", hex::encode(bytes)))
+    }
+}
+
+// Never print the key.
+impl fmt::Debug for Wallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Wallet").field("address", &self.address()).finish_non_exhaustive()
+    }
+}
+
+pub(crate) fn keccak(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+pub(crate) fn checksum(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak(lower.as_bytes());
+    let mixed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(idx, c)| {
+            let nibble = (hash[idx / 2] >> if idx % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{mixed}")
+}
+
+pub(crate) fn parse_address(text: &str) -> Result<[u8; 20], PolyError> {
+    let bytes = hex::decode(text.trim().trim_start_matches("0x")).ok().filter(|b| b.len() == 20);
+    let bytes = bytes.ok_or_else(|| PolyError::Auth(format!("'{text}' is not an address")))?;
+    let mut address = [0; 20];
+    address.copy_from_slice(&bytes);
+    Ok(address)
+}
+
+// EIP-712 encodes every member as one 32-byte word.
+pub(crate) fn word_uint(value: u128) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+// A uint256 written in decimal, such as a CLOB token id, which overflows every native integer.
+pub(crate) fn word_decimal(decimal: &str) -> Result<[u8; 32], PolyError> {
+    let invalid = || PolyError::Auth(format!("'{decimal}' is not a uint256"));
+    if decimal.is_empty() {
+        return Err(invalid());
+    }
+    let mut word = [0u8; 32];
+    for c in decimal.chars() {
+        let mut carry = c.to_digit(10).ok_or_else(invalid)?;
+        for byte in word.iter_mut().rev() {
+            let value = u32::from(*byte) * 10 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(invalid());
+        }
+    }
+    Ok(word)
+}
+
+pub(crate) fn word_address(address: &[u8; 20]) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[12..].copy_from_slice(address);
+    word
+}
+
+pub(crate) fn word_string(text: &str) -> [u8; 32] {
+    keccak(text.as_bytes())
+}
+
+// keccak(typeHash ‖ members), the EIP-712 hashStruct.
+pub(crate) fn hash_struct(type_string: &str, members: &[[u8; 32]]) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(32 * (members.len() + 1));
+    encoded.extend_from_slice(&keccak(type_string.as_bytes()));
+    for member in members {
+        encoded.extend_from_slice(member);
+    }
+    keccak(&encoded)
+}
+
+pub(crate) fn domain_separator(name: &str, version: &str, verifying_contract: Option<&[u8; 20]>) -> [u8; 32] {
+    match verifying_contract {
+        Some(contract) => hash_struct(
+            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+            &[word_string(name), word_string(version), word_uint(CHAIN_ID.into()), word_address(contract)],
+        ),
+        None => hash_struct(
+            "EIP712Domain(string name,string version,uint256 chainId)",
+            &[word_string(name), word_string(version), word_uint(CHAIN_ID.into())],
+        ),
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::alert::Alert;
+use crate::error::PolyError;
+use crate::http::post_json;
+use crate::model::Row;
+
+const ATTEMPTS: u32 = 3;
+const FIRST_BACKOFF: Duration = Duration::from_secs(1);
+
+// Body POSTed to `--webhook` endpoints, tagged with `"type": "alert"` or `"type": "snapshot"`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WebhookEvent<'a> {
+    Alert {
+        timestamp: DateTime<Utc>,
+        alert: &'a Alert,
+    },
+    Snapshot {
+        timestamp: DateTime<Utc>,
+        rows: &'a [Row],
+    },
+}
+
+// POSTs a JSON payload, retrying transient failures with exponential backoff.
+pub async fn post_webhook<T: Serialize + ?Sized>(url: &str, payload: &T) -> Result<(), PolyError> {
+    let mut backoff = FIRST_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match post_json(url, payload).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < ATTEMPTS && e.is_transient() => {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
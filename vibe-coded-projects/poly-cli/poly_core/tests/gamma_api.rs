@@ -0,0 +1,222 @@
+use std::path::PathBuf;
+
+use poly_core::{
+    fetch_markets_with, render_table, EventQuery, FixtureFetcher, PolyError, ReqwestFetcher, Row, BASE_URL,
+};
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn fixture_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+fn events_fixture() -> Value {
+    serde_json::from_str(&std::fs::read_to_string(fixture_path("events.json")).unwrap()).unwrap()
+}
+
+// A gamma stand-in answering every /events request with `response`.
+async fn gamma(response: ResponseTemplate) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET")).and(path("/events")).respond_with(response).mount(&server).await;
+    server
+}
+
+async fn fetch(server: &MockServer, query: &EventQuery) -> Result<Vec<Row>, PolyError> {
+    fetch_markets_with(&ReqwestFetcher::with_origin(&server.uri()), query).await
+}
+
+fn row<'a>(rows: &'a [Row], slug: &str) -> &'a Row {
+    rows.iter().find(|row| row.slug.as_deref() == Some(slug)).unwrap()
+}
+
+// `count` single-market events with ids starting at `first`.
+fn numbered_events(first: usize, count: usize) -> Value {
+    let events: Vec<Value> = (first..first + count)
+        .map(|n| {
+            json!({
+                "id": n.to_string(),
+                "title": format!("Event {n}"),
+                "markets": [{
+                    "id": format!("m{n}"),
+                    "question": format!("Market {n}?"),
+                    "outcomePrices": "[\"0.5\", \"0.5\"]",
+                }],
+            })
+        })
+        .collect();
+    Value::Array(events)
+}
+
+#[tokio::test]
+async fn resolves_volume_and_change_fallbacks() {
+    let server = gamma(ResponseTemplate::new(200).set_body_json(events_fixture())).await;
+    let rows = fetch(&server, &EventQuery::new(10)).await.unwrap();
+    assert_eq!(rows.len(), 4);
+
+    // volumeNum wins over the string `volume`, and fractional day changes become percents.
+    let cuts = row(&rows, "fed-cuts-25");
+    assert_eq!(cuts.event, "Fed decision in December");
+    assert_eq!(cuts.price, Some(0.82));
+    assert_eq!(cuts.volume, 31_250_000.5);
+    assert_eq!(cuts.volume_24h, 1_840_000.0);
+    assert_eq!(cuts.change_24h_pct.map(|c| (c * 10.0).round() / 10.0), Some(3.5));
+    assert_eq!(cuts.liquidity, Some(420_000.0));
+    assert_eq!(cuts.token_id.as_deref(), Some("111"));
+
+    // Without volumeNum the string `volume` is parsed before volumeClob; percent changes pass through.
+    let holds = row(&rows, "fed-holds");
+    assert_eq!(holds.volume, 12_500_000.25);
+    assert_eq!(holds.volume_24h, 95_000.0);
+    assert_eq!(holds.change_24h_pct, Some(-4.2));
+    assert_eq!(holds.liquidity, Some(88_000.0));
+
+    let hikes = row(&rows, "fed-hikes");
+    assert_eq!(hikes.volume, 750_000.0);
+    assert_eq!(hikes.change_24h_pct, None);
+
+    // An empty outcomePrices string means no price rather than a decode error.
+    let nvidia = row(&rows, "nvidia-largest");
+    assert_eq!(nvidia.price, None);
+    assert_eq!(nvidia.volume, 2_500.0);
+}
+
+#[tokio::test]
+async fn renders_fetched_rows() {
+    let server = gamma(ResponseTemplate::new(200).set_body_json(events_fixture())).await;
+    let rows = fetch(&server, &EventQuery::new(10)).await.unwrap();
+    let table = render_table(&rows, 10, false);
+
+    assert!(!table.contains('\x1b'));
+    assert!(table.contains("Fed cuts rates by 25 bps?"));
+    assert!(table.contains("$31.25M"));
+    assert!(table.contains("$12.50M"));
+    assert!(table.contains("$750.0K"));
+    assert!(table.contains("+3.50%"));
+    assert!(table.contains("n/a"));
+}
+
+#[tokio::test]
+async fn sends_a_single_tag_and_filters_locally() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .and(query_param("tag_slug", "economy"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(events_fixture()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let query = EventQuery { tags: vec!["Economy".to_string()], ..EventQuery::new(10) };
+    let rows = fetch(&server, &query).await.unwrap();
+    assert_eq!(rows.len(), 3);
+    assert!(rows.iter().all(|row| row.event == "Fed decision in December"));
+}
+
+#[tokio::test]
+async fn pages_past_the_per_request_cap_and_dedupes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .and(query_param("offset", "0"))
+        .and(query_param("limit", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(numbered_events(0, 100)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // The ranking shifted between requests, so the second page repeats event 99.
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .and(query_param("offset", "100"))
+        .and(query_param("limit", "50"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(numbered_events(99, 30)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let rows = fetch(&server, &EventQuery::new(150)).await.unwrap();
+    assert_eq!(rows.len(), 129);
+    assert_eq!(rows.iter().filter(|row| row.title == "Market 99?").count(), 1);
+    assert_eq!(rows.last().map(|row| row.title.as_str()), Some("Market 128?"));
+}
+
+#[tokio::test]
+async fn malformed_json_is_a_decode_error() {
+    let server = gamma(ResponseTemplate::new(200).set_body_string("<html>upstream down</html>")).await;
+    let err = fetch(&server, &EventQuery::new(10)).await.unwrap_err();
+    assert!(matches!(err, PolyError::Decode(_)), "{err:?}");
+    assert_eq!(err.exit_code(), 5);
+}
+
+#[tokio::test]
+async fn malformed_price_list_is_a_decode_error() {
+    let payload = json!([{ "id": "1", "markets": [{ "id": "m1", "outcomePrices": { "yes": 0.5 } }] }]);
+    let server = gamma(ResponseTemplate::new(200).set_body_json(payload)).await;
+    let err = fetch(&server, &EventQuery::new(10)).await.unwrap_err();
+    assert!(matches!(err, PolyError::Decode(_)), "{err:?}");
+    assert_eq!(err.exit_code(), 5);
+}
+
+#[tokio::test]
+async fn a_malformed_price_leaves_the_market_and_page_intact() {
+    let payload = json!([{ "id": "1", "markets": [
+        { "id": "m1", "slug": "tail", "outcomePrices": "[\"0.4\", \"n/a\"]" },
+        { "id": "m2", "slug": "head", "outcomePrices": ["oops", "0.6"] },
+        { "id": "m3", "slug": "fine", "outcomePrices": "[\"0.7\", \"0.3\"]" },
+    ] }]);
+    let server = gamma(ResponseTemplate::new(200).set_body_json(payload)).await;
+    let rows = fetch(&server, &EventQuery::new(10)).await.unwrap();
+    // Prices stop at the bad entry, so the ones before it still line up with their outcomes.
+    assert_eq!(row(&rows, "tail").outcome_prices, vec![0.4]);
+    assert_eq!(row(&rows, "head").price, None);
+    assert_eq!(row(&rows, "fine").price, Some(0.7));
+}
+
+#[tokio::test]
+async fn an_object_instead_of_a_list_is_a_shape_error() {
+    let server = gamma(ResponseTemplate::new(200).set_body_json(json!({ "error": "rate limited" }))).await;
+    let err = fetch(&server, &EventQuery::new(10)).await.unwrap_err();
+    assert!(matches!(err, PolyError::Shape(_)), "{err:?}");
+    assert_eq!(err.exit_code(), 6);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .respond_with(ResponseTemplate::new(404))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = fetch(&server, &EventQuery::new(10)).await.unwrap_err();
+    assert!(matches!(err, PolyError::Status(status) if status.as_u16() == 404), "{err:?}");
+    assert_eq!(err.exit_code(), 4);
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_then_reported() {
+    let server = MockServer::start().await;
+    // The default options allow two retries after the first attempt.
+    Mock::given(method("GET"))
+        .and(path("/events"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = fetch(&server, &EventQuery::new(10)).await.unwrap_err();
+    assert!(matches!(err, PolyError::Status(status) if status.as_u16() == 503), "{err:?}");
+    assert_eq!(err.exit_code(), 4);
+}
+
+#[tokio::test]
+async fn fixture_fetcher_serves_files_without_a_server() {
+    let fetcher = FixtureFetcher::new().with_file(BASE_URL, &fixture_path("events.json")).unwrap();
+    let rows = fetch_markets_with(&fetcher, &EventQuery::new(10)).await.unwrap();
+    assert_eq!(rows.len(), 4);
+
+    let err = fetch_markets_with(&FixtureFetcher::new(), &EventQuery::new(10)).await.unwrap_err();
+    assert_eq!(err.exit_code(), 7);
+}
@@ -0,0 +1,64 @@
+use poly_core::{sign_order_with_salt, OrderMarket, OrderRequest, Side, SignatureType, Wallet};
+
+// The well-known first Hardhat account, so the vectors can be re-derived anywhere.
+const KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+const TOKEN: &str = "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+const SALT: u64 = 479_249_096_354;
+
+const MARKET: OrderMarket = OrderMarket { tick_size: 0.01, neg_risk: false, fee_rate_bps: 0 };
+
+fn request(side: Side, price: f64, size: f64) -> OrderRequest {
+    OrderRequest {
+        token_id: TOKEN.to_string(),
+        side,
+        price,
+        size,
+        funder: None,
+        signature_type: SignatureType::Eoa,
+    }
+}
+
+// Expected amounts and signatures were produced by a standalone keccak/secp256k1 (RFC 6979,
+// low-s) implementation following py-clob-client's order builder: its amount rounding and
+// EIP-712 Order struct for the CTF exchange on Polygon.
+#[test]
+fn buy_order_matches_reference_signature() {
+    let wallet = Wallet::from_private_key(KEY).unwrap();
+    assert_eq!(wallet.address(), ADDRESS);
+    let order = sign_order_with_salt(&wallet, &request(Side::Buy, 0.55, 10.0), &MARKET, SALT).unwrap();
+    assert_eq!(order.maker, ADDRESS);
+    assert_eq!(order.signer, ADDRESS);
+    assert_eq!(order.maker_amount, "5500000");
+    assert_eq!(order.taker_amount, "10000000");
+    assert_eq!(
+        order.signature,
+        "0x1b8b53b2f50cb713ba471ea05f7e1ec12487928914b17ddac489398efc429f35\
+         4437cafbcb40a299e50331c5bbaa9a9aef900cbaffb56a867821fd75c0a429c11c"
+    );
+}
+
+#[test]
+fn sell_order_matches_reference_signature() {
+    let wallet = Wallet::from_private_key(KEY).unwrap();
+    let order = sign_order_with_salt(&wallet, &request(Side::Sell, 0.37, 12.5), &MARKET, SALT).unwrap();
+    assert_eq!(order.maker_amount, "12500000");
+    assert_eq!(order.taker_amount, "4625000");
+    assert_eq!(
+        order.signature,
+        "0x8b4389ab6c560fd7898f1a327d42293cc7954abcc23d71beaae7addc57bac235\
+         1329c6c8bf6882949c1e8b0b4fef2426283078ce3bf167ebd573e23eed8617031c"
+    );
+}
+
+#[test]
+fn check_rejects_unsignable_requests() {
+    assert!(MARKET.check(&request(Side::Buy, f64::NAN, 10.0)).is_err());
+    assert!(MARKET.check(&request(Side::Buy, 0.5, f64::INFINITY)).is_err());
+    assert!(MARKET.check(&request(Side::Buy, 0.5, 1.005)).is_err());
+    let mut proxy = request(Side::Buy, 0.5, 10.0);
+    proxy.signature_type = SignatureType::Proxy;
+    assert!(MARKET.check(&proxy).is_err());
+    proxy.funder = Some(ADDRESS.to_string());
+    assert!(MARKET.check(&proxy).is_ok());
+}
@@ -0,0 +1,56 @@
+use poly_core::{Condition, Query};
+use serde_json::{json, Value};
+
+fn query(select: &[&str], conditions: &[&str]) -> Query {
+    Query {
+        select: select.iter().map(|s| s.to_string()).collect(),
+        conditions: conditions.iter().map(|c| c.parse().unwrap()).collect(),
+    }
+}
+
+fn items() -> Vec<Value> {
+    vec![
+        json!({ "title": "Rain", "volume24h": 25_000.0, "venue": "polymarket" }),
+        json!({ "title": "Snow", "volume24h": 4_000.0, "venue": "Kalshi" }),
+        json!({ "title": "Hail", "volume24h": null, "venue": "polymarket" }),
+    ]
+}
+
+#[test]
+fn conditions_compare_numbers_and_text() {
+    let kept = query(&[], &["volume24h > 10k"]).apply(items());
+    assert_eq!(kept, vec![items()[0].clone()]);
+
+    // Text matches ignore case; a null field never matches.
+    let kept = query(&[], &["venue == kalshi"]).apply(items());
+    assert_eq!(kept, vec![items()[1].clone()]);
+    assert!(query(&[], &["volume24h < 1m"]).apply(items()).iter().all(|item| !item["volume24h"].is_null()));
+}
+
+#[test]
+fn every_condition_must_hold() {
+    let kept = query(&[], &["venue != kalshi", "volume24h >= 1000"]).apply(items());
+    assert_eq!(kept.len(), 1);
+    assert_eq!(kept[0]["title"], "Rain");
+}
+
+#[test]
+fn select_keeps_listed_fields_filling_missing_with_null() {
+    let kept = query(&["title", "missing"], &[]).apply(items());
+    assert_eq!(kept[0], json!({ "title": "Rain", "missing": null }));
+}
+
+#[test]
+fn documents_filter_arrays_and_null_out_objects() {
+    let q = query(&["title"], &["volume24h > 10k"]);
+    assert_eq!(q.apply_document(Value::Array(items())), json!([{ "title": "Rain" }]));
+    assert_eq!(q.apply_document(items()[0].clone()), json!({ "title": "Rain" }));
+    assert_eq!(q.apply_document(items()[1].clone()), Value::Null);
+}
+
+#[test]
+fn malformed_conditions_are_rejected() {
+    assert!("volume24h".parse::<Condition>().is_err());
+    assert!("> 5".parse::<Condition>().is_err());
+    assert!("volume24h > lots".parse::<Condition>().is_err());
+}
@@ -0,0 +1,129 @@
+use poly_core::format::{ansi_regex, visible_len};
+use poly_core::{render_table, render_table_with, Row, TableLayout, Venue};
+
+fn row(title: &str, price: Option<f64>, volume: f64, volume_24h: f64, change: Option<f64>) -> Row {
+    Row {
+        event: "Fixture event".to_string(),
+        event_slug: None,
+        title: title.to_string(),
+        slug: None,
+        token_id: None,
+        price,
+        volume,
+        volume_24h,
+        change_24h_pct: change,
+        liquidity: None,
+        end_date: None,
+        resolution: None,
+        best_bid: None,
+        best_ask: None,
+        spread: None,
+        midpoint: None,
+        momentum: None,
+        spike: None,
+        venue: Venue::Polymarket,
+        outcome_prices: price.into_iter().collect(),
+        trend: Vec::new(),
+    }
+}
+
+// End dates are years out so the closing-soon highlight never kicks in.
+fn rows() -> Vec<Row> {
+    vec![
+        Row {
+            liquidity: Some(1_500_000.0),
+            end_date: Some("2031-01-15T12:00:00Z".to_string()),
+            ..row(
+                "Will the winner of the 2028 United States presidential election also win the popular vote by more \
+                 than five points?",
+                Some(0.515),
+                1_234_567_890.0,
+                98_765.4,
+                Some(12.345),
+            )
+        },
+        row("Will it rain in London tomorrow?", Some(0.07), 999.4, 0.0, None),
+        Row {
+            liquidity: Some(2_000_000_000.0),
+            end_date: Some("2030-06-30T00:00:00Z".to_string()),
+            ..row("Largest market ever", Some(0.999), 987_654_321_000_000.0, 12_345_678.9, Some(-0.5))
+        },
+        Row {
+            liquidity: Some(0.0),
+            end_date: Some("2030-01-01T09:30:00Z".to_string()),
+            ..row("Unchanged market", None, 0.0, 0.0, Some(0.0))
+        },
+    ]
+}
+
+#[test]
+fn no_color() {
+    insta::assert_snapshot!("no_color", render_table(&rows(), 10, false));
+}
+
+// Escapes are made visible so the snapshot diffs readably.
+#[test]
+fn color() {
+    insta::assert_snapshot!("color", render_table(&rows(), 10, true).replace('\x1b', "␛"));
+}
+
+#[test]
+fn top_limits_the_rows() {
+    let table = render_table(&rows(), 2, false);
+    assert_eq!(table.lines().count(), 4);
+    assert!(!table.contains("Largest market ever"));
+}
+
+// Wide characters take two columns and combining marks none, so every line must still come out
+// the same width in the terminal.
+#[test]
+fn wide_and_combining_titles_stay_aligned() {
+    let rows = vec![
+        row("🇺🇸 Will the US win the most gold medals? 🥇", Some(0.61), 2_000_000.0, 10_000.0, Some(1.0)),
+        row(
+            "日本の次の首相は誰になる？高市早苗、小泉進次郎、林芳正、茂木敏充、小林鷹之、その他の候補者",
+            Some(0.3),
+            500_000.0,
+            0.0,
+            None,
+        ),
+        row(
+            "Will Beyonce\u{301} headline Coachella? 👩🏽‍🎤 Cafe\u{301} edition with a title long enough to truncate",
+            None,
+            1.0,
+            0.0,
+            None,
+        ),
+        row("Plain ASCII market", Some(0.5), 1.0, 1.0, Some(-1.0)),
+    ];
+    let ansi_re = ansi_regex();
+    for color in [false, true] {
+        let table = render_table(&rows, 10, color);
+        let widths: Vec<usize> = table.lines().map(|line| visible_len(line, &ansi_re)).collect();
+        assert!(widths.iter().all(|&w| w == widths[0]), "ragged table (color: {color}): {widths:?}\n{table}");
+    }
+}
+
+// Fitted to a terminal, spare room goes to the title, and a narrow terminal drops the
+// low-priority columns before squeezing what's left into the width.
+#[test]
+fn fits_the_terminal_width() {
+    let ansi_re = ansi_regex();
+    let fitted = |width| render_table_with(&rows(), 10, &TableLayout { width: Some(width), columns: None }, false);
+    let widths = |table: &str| table.lines().map(|line| visible_len(line, &ansi_re)).collect::<Vec<_>>();
+
+    let wide = fitted(240);
+    assert!(wide.contains("popular vote by more than five points?"), "{wide}");
+    assert!(widths(&wide).iter().all(|&w| w <= 240), "{wide}");
+
+    let narrow = fitted(80);
+    let header = narrow.lines().next().unwrap();
+    assert!(header.contains("Market") && header.contains("24h Change") && header.contains("End"), "{narrow}");
+    assert!(!header.contains("Liquidity") && !header.contains("Total Volume"), "{narrow}");
+    assert!(widths(&narrow).iter().all(|&w| w == 80), "{narrow}");
+
+    // Too narrow for anything else, the rank and title still show.
+    let tiny = fitted(20);
+    let header = tiny.lines().next().unwrap();
+    assert!(header.starts_with('#') && header.contains("Market"), "{tiny}");
+}
@@ -0,0 +1,618 @@
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::Arc;
+
+use chrono::{DateTime, Local};
+use futures_util::StreamExt;
+use poly_core::{
+    fetch_market, format_end, format_percent, format_price, sort_rows, sparkline, Alert, Market, Matcher, Row,
+    SortKey, SortSpec, Venue, CLOSING_SOON_HOURS, SPARKLINE_WIDTH,
+};
+use ratatui::crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row as TableRow, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use tokio::sync::{mpsc, Notify};
+
+use crate::commands::ring_bell;
+
+// Messages from the background fetch task to the UI loop. Rows arrive filtered but unsorted;
+// the UI owns sorting so the sort keys can change without a refetch.
+pub enum Update {
+    Rows(Vec<Row>),
+    Alerts(Vec<Alert>),
+    Error(String),
+}
+
+// How the dashboard starts out; sort and the delta columns can be changed from the keyboard.
+pub struct Options {
+    pub heading: String,
+    pub top: usize,
+    pub sort: Vec<SortSpec>,
+    pub deltas: bool,
+    pub beep: bool,
+}
+
+// How many of the latest alerts stay listed above the status bar.
+const RECENT_ALERTS: usize = 5;
+
+// Market details for the side pane, fetched on first Enter and kept for the session.
+enum Detail {
+    Loading,
+    Ready(Box<Market>),
+    Failed(String),
+}
+
+type DetailResult = (String, Result<Market, String>);
+
+// The numbers of one row worth comparing across refreshes.
+#[derive(Clone, Copy)]
+struct Snapshot {
+    price: Option<f64>,
+    volume: f64,
+    volume_24h: f64,
+}
+
+impl Snapshot {
+    fn of(row: &Row) -> Self {
+        Snapshot { price: row.price, volume: row.volume, volume_24h: row.volume_24h }
+    }
+}
+
+fn row_key(row: &Row) -> Option<&str> {
+    row.token_id.as_deref().or(row.slug.as_deref())
+}
+
+fn snapshots(rows: &[Row]) -> HashMap<String, Snapshot> {
+    rows.iter().filter_map(|row| Some((row_key(row)?.to_string(), Snapshot::of(row)))).collect()
+}
+
+struct App {
+    heading: String,
+    top: usize,
+    sort: Vec<SortSpec>,
+    // Everything the last fetch returned; `rows` is the sorted, truncated view of it.
+    fetched: Vec<Row>,
+    rows: Vec<Row>,
+    // Values from the fetch before the current one, used to flash cells that moved.
+    previous: HashMap<String, Snapshot>,
+    // First values seen for each market this session, for the optional delta columns.
+    baseline: HashMap<String, Snapshot>,
+    deltas: bool,
+    alerts: VecDeque<(DateTime<Local>, Alert)>,
+    beep: bool,
+    table: TableState,
+    last_update: Option<DateTime<Local>>,
+    error: Option<String>,
+    refreshing: bool,
+    // Title filter typed after `/`; `searching` is true while keystrokes go to the input box.
+    query: String,
+    searching: bool,
+    // Slug shown in the detail pane, if open.
+    detail: Option<String>,
+    details: HashMap<String, Detail>,
+    detail_tx: mpsc::UnboundedSender<DetailResult>,
+}
+
+impl App {
+    fn apply(&mut self, update: Update) {
+        match update {
+            Update::Rows(rows) => {
+                self.previous = snapshots(&self.fetched);
+                for (key, snapshot) in snapshots(&rows) {
+                    self.baseline.entry(key).or_insert(snapshot);
+                }
+                self.fetched = rows;
+                self.last_update = Some(Local::now());
+                self.error = None;
+                self.rebuild();
+            }
+            Update::Alerts(alerts) => {
+                if self.beep {
+                    ring_bell();
+                }
+                for alert in alerts {
+                    self.alerts.push_back((Local::now(), alert));
+                }
+                while self.alerts.len() > RECENT_ALERTS {
+                    self.alerts.pop_front();
+                }
+                return;
+            }
+            Update::Error(e) => self.error = Some(e),
+        }
+        self.refreshing = false;
+    }
+
+    fn title(&self) -> String {
+        let label = self.sort.iter().map(|s| s.key.label()).collect::<Vec<_>>().join(", ");
+        let direction = match self.sort.first() {
+            Some(spec) if spec.descending => "↓",
+            Some(_) => "↑",
+            None => "",
+        };
+        format!("{} by {label} {direction}", self.heading)
+    }
+
+    fn rebuild(&mut self) {
+        let matcher = Matcher::Substring(self.query.to_lowercase());
+        let mut rows: Vec<Row> =
+            self.fetched.iter().filter(|row| matcher.matches_row(row)).cloned().collect();
+        sort_rows(&mut rows, &self.sort);
+        rows.truncate(self.top);
+        self.rows = rows;
+        let max = self.rows.len().saturating_sub(1);
+        self.table.select(Some(self.table.selected().unwrap_or(0).min(max)));
+    }
+
+    // Pressing the key of the current primary sort flips its direction; any other key
+    // switches to that column with its natural direction.
+    fn sort_by(&mut self, key: SortKey) {
+        let spec = match self.sort.first() {
+            Some(current) if current.key == key => SortSpec { key, descending: !current.descending },
+            _ => SortSpec::new(key),
+        };
+        self.sort = vec![spec];
+        self.rebuild();
+    }
+
+    // Enter on the row already shown closes the pane; on any other row it switches to it,
+    // fetching the market the first time it is opened, or again if that lookup failed.
+    fn toggle_detail(&mut self) {
+        let selected = self.table.selected().and_then(|i| self.rows.get(i));
+        // Details come from gamma, so they're only available for Polymarket rows.
+        let selected = selected.filter(|row| row.venue == Venue::Polymarket);
+        let Some(slug) = selected.and_then(|row| row.slug.clone()) else {
+            return;
+        };
+        if self.detail.as_deref() == Some(slug.as_str()) {
+            self.detail = None;
+            return;
+        }
+        if matches!(self.details.get(&slug), None | Some(Detail::Failed(_))) {
+            self.details.insert(slug.clone(), Detail::Loading);
+            let tx = self.detail_tx.clone();
+            let lookup = slug.clone();
+            tokio::spawn(async move {
+                let result = fetch_market(&lookup).await.map_err(|e| e.to_string());
+                let _ = tx.send((lookup, result));
+            });
+        }
+        self.detail = Some(slug);
+    }
+
+    fn apply_detail(&mut self, (slug, result): DetailResult) {
+        let detail = match result {
+            Ok(market) => Detail::Ready(Box::new(market)),
+            Err(e) => Detail::Failed(e),
+        };
+        self.details.insert(slug, detail);
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let current = self.table.selected().unwrap_or(0) as isize;
+        let last = self.rows.len() as isize - 1;
+        self.table.select(Some((current + delta).clamp(0, last) as usize));
+    }
+}
+
+fn change_style(change: Option<f64>) -> Style {
+    match change {
+        None => Style::default().fg(Color::DarkGray),
+        Some(v) if v > 0.0 => Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        Some(v) if v < 0.0 => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        Some(_) => Style::default().fg(Color::Yellow),
+    }
+}
+
+// Flashes a cell whose value moved since the previous refresh; otherwise uses `style`.
+fn moved_cell(text: String, current: Option<f64>, previous: Option<f64>, style: Style) -> Cell<'static> {
+    match (current, previous) {
+        (Some(now), Some(before)) if now > before => {
+            Cell::from(format!("{text} ▲")).style(Style::default().fg(Color::Black).bg(Color::Green))
+        }
+        (Some(now), Some(before)) if now < before => {
+            Cell::from(format!("{text} ▼")).style(Style::default().fg(Color::White).bg(Color::Red))
+        }
+        _ => Cell::from(text).style(style),
+    }
+}
+
+fn delta_price(now: Option<f64>, start: Option<f64>) -> Cell<'static> {
+    match (now, start) {
+        (Some(now), Some(start)) => {
+            let delta = (now - start) * 100.0;
+            Cell::from(format!("{delta:+.1}¢")).style(change_style(Some(delta)))
+        }
+        _ => Cell::from("n/a").style(change_style(None)),
+    }
+}
+
+fn delta_money(now: f64, start: Option<f64>, venue: Venue) -> Cell<'static> {
+    match start {
+        Some(start) => {
+            let delta = now - start;
+            let sign = if delta < 0.0 { "-" } else { "+" };
+            Cell::from(format!("{sign}{}", venue.format_money(delta.abs()))).style(change_style(Some(delta)))
+        }
+        None => Cell::from("n/a").style(change_style(None)),
+    }
+}
+
+fn detail_lines(slug: &str, detail: Option<&Detail>) -> Vec<Line<'static>> {
+    let label = |text: &str| Span::styled(format!("{text:<12}"), Style::default().fg(Color::DarkGray));
+    let market = match detail {
+        Some(Detail::Ready(market)) => market,
+        Some(Detail::Failed(e)) => return vec![Line::styled(e.clone(), Style::default().fg(Color::Red))],
+        Some(Detail::Loading) | None => {
+            return vec![Line::styled("Loading...", Style::default().fg(Color::DarkGray))];
+        }
+    };
+
+    let mut lines = vec![
+        Line::styled(
+            market.display_title().to_string(),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ),
+        Line::from(vec![label("Slug"), Span::raw(slug.to_string())]),
+        Line::from(vec![label("URL"), Span::raw(format!("https://polymarket.com/market/{slug}"))]),
+        Line::default(),
+    ];
+    for (name, price) in market.outcome_quotes() {
+        lines.push(Line::from(vec![
+            label(name),
+            Span::styled(format_price(price), Style::default().fg(Color::Yellow)),
+        ]));
+    }
+    lines.push(Line::from(vec![label("Ends"), Span::raw(format_end(market.end()))]));
+    if let Some(desc) = market.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        lines.push(Line::default());
+        lines.push(Line::raw(desc.trim().to_string()));
+    }
+    lines
+}
+
+fn draw_detail(frame: &mut Frame, app: &App, slug: &str, area: Rect) {
+    let pane = Paragraph::new(detail_lines(slug, app.details.get(slug)))
+        .block(Block::default().borders(Borders::ALL).title(" Market "))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(pane, area);
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let search_height = if app.searching || !app.query.is_empty() { 1 } else { 0 };
+    let [header_area, search_area, table_area, alerts_area, status_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(search_height),
+        Constraint::Min(3),
+        Constraint::Length(app.alerts.len() as u16),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let table_area = match app.detail.clone() {
+        Some(slug) => {
+            let [table_area, detail_area] = Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(table_area);
+            draw_detail(frame, app, &slug, detail_area);
+            table_area
+        }
+        None => table_area,
+    };
+
+    frame.render_widget(
+        Line::from(Span::styled(
+            app.title(),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        header_area,
+    );
+
+    if search_height > 0 {
+        let mut search = vec![
+            Span::styled("/", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::raw(app.query.as_str()),
+        ];
+        if app.searching {
+            search.push(Span::styled("█", Style::default().fg(Color::Yellow)));
+        }
+        frame.render_widget(Line::from(search), search_area);
+    }
+
+    let mut columns = vec!["#", "Market", "Price", "Total Volume", "24h Volume", "24h Change", "Liquidity", "End"];
+    if app.deltas {
+        columns.extend(["Δ Price", "Δ Volume"]);
+    }
+    let trends = app.rows.iter().any(|row| !row.trend.is_empty());
+    if trends {
+        columns.push("Trend");
+    }
+    let venues = app.rows.iter().any(|row| row.venue != Venue::Polymarket);
+    if venues {
+        columns.insert(1, "Venue");
+    }
+    let momentum = app.rows.iter().any(|row| row.momentum.is_some());
+    if momentum {
+        columns.push("Mom");
+    }
+    let quotes = app.rows.iter().any(|row| row.best_bid.is_some() || row.best_ask.is_some());
+    if quotes {
+        columns.extend(["Bid / Ask", "Spread", "Mid"]);
+    }
+    let header = TableRow::new(columns)
+        .style(Style::default().fg(Color::LightBlue).add_modifier(Modifier::BOLD));
+    let (previous, baseline, deltas) = (&app.previous, &app.baseline, app.deltas);
+    let closing_soon = chrono::Duration::hours(CLOSING_SOON_HOURS);
+    let rows = app.rows.iter().enumerate().map(|(idx, row)| {
+        let before = row_key(row).and_then(|key| previous.get(key));
+        let start = row_key(row).and_then(|key| baseline.get(key));
+        let cyan = Style::default().fg(Color::Cyan);
+        let mut cells = vec![
+            Cell::from((idx + 1).to_string()).style(cyan),
+            Cell::from(row.title.as_str()),
+            moved_cell(
+                format_price(row.price),
+                row.price,
+                before.and_then(|b| b.price),
+                Style::default().fg(Color::Yellow),
+            ),
+            moved_cell(row.venue.format_money(row.volume), Some(row.volume), before.map(|b| b.volume), cyan),
+            moved_cell(
+                row.venue.format_money(row.volume_24h),
+                Some(row.volume_24h),
+                before.map(|b| b.volume_24h),
+                cyan,
+            ),
+            Cell::from(format_percent(row.change_24h_pct)).style(change_style(row.change_24h_pct)),
+            Cell::from(row.liquidity.map_or_else(|| "n/a".to_string(), |l| row.venue.format_money(l))).style(cyan),
+            Cell::from(format_end(row.end_date.as_deref())).style(if row.ends_within(closing_soon) {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            }),
+        ];
+        if deltas {
+            cells.push(delta_price(row.price, start.and_then(|s| s.price)));
+            cells.push(delta_money(row.volume, start.map(|s| s.volume), row.venue));
+        }
+        if trends {
+            let rising = row.trend.last() >= row.trend.first();
+            let color = if rising { Color::Green } else { Color::Red };
+            cells.push(Cell::from(sparkline(&row.trend, SPARKLINE_WIDTH)).style(Style::default().fg(color)));
+        }
+        if venues {
+            cells.insert(1, Cell::from(row.venue.label()).style(Style::default().fg(Color::Magenta)));
+        }
+        if momentum {
+            let (arrow, color) = match row.momentum {
+                Some(m) => match m.rising() {
+                    Some(true) => (m.arrow(), Color::Green),
+                    Some(false) => (m.arrow(), Color::Red),
+                    None => (m.arrow(), Color::DarkGray),
+                },
+                None => ("-", Color::DarkGray),
+            };
+            cells.push(Cell::from(arrow).style(Style::default().fg(color)));
+        }
+        if quotes {
+            let spread_color = match row.spread {
+                None => Color::DarkGray,
+                Some(s) if s <= 0.01 => Color::Green,
+                Some(s) if s <= 0.03 => Color::Yellow,
+                Some(_) => Color::Red,
+            };
+            cells.push(Cell::from(format!("{} / {}", format_price(row.best_bid), format_price(row.best_ask))));
+            cells.push(Cell::from(format_price(row.spread)).style(Style::default().fg(spread_color)));
+            cells.push(Cell::from(format_price(row.midpoint)).style(Style::default().fg(Color::Yellow)));
+        }
+        TableRow::new(cells)
+    });
+    let mut widths = vec![
+        Constraint::Length(4),
+        Constraint::Min(30),
+        Constraint::Length(9),
+        Constraint::Length(15),
+        Constraint::Length(13),
+        Constraint::Length(10),
+        Constraint::Length(12),
+        Constraint::Length(22),
+    ];
+    if deltas {
+        widths.extend([Constraint::Length(9), Constraint::Length(11)]);
+    }
+    if trends {
+        widths.push(Constraint::Length(SPARKLINE_WIDTH as u16));
+    }
+    if venues {
+        widths.insert(1, Constraint::Length(10));
+    }
+    if momentum {
+        widths.push(Constraint::Length(3));
+    }
+    if quotes {
+        widths.extend([Constraint::Length(13), Constraint::Length(7), Constraint::Length(7)]);
+    }
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(table, table_area, &mut app.table);
+
+    let alert_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    let alert_lines: Vec<Line> = app
+        .alerts
+        .iter()
+        .map(|(at, alert)| Line::styled(format!("⚠ {} ALERT {alert}", at.format("%H:%M:%S")), alert_style))
+        .collect();
+    frame.render_widget(Paragraph::new(alert_lines), alerts_area);
+
+    let updated = match app.last_update {
+        Some(t) => format!("Updated {}", t.format("%H:%M:%S")),
+        None => "Loading...".to_string(),
+    };
+    let mut status = vec![
+        Span::styled(updated, Style::default().fg(Color::DarkGray)),
+        Span::raw(format!("  |  {} markets", app.rows.len())),
+    ];
+    if !app.query.is_empty() {
+        status.push(Span::raw(format!(" (of {})", app.fetched.len())));
+    }
+    if app.refreshing {
+        status.push(Span::styled("  |  refreshing", Style::default().fg(Color::Yellow)));
+    }
+    if let Some(err) = &app.error {
+        status.push(Span::styled(format!("  |  {err}"), Style::default().fg(Color::Red)));
+    }
+    let help = if app.searching {
+        "  |  enter keep filter  esc clear"
+    } else {
+        "  |  q quit  r refresh  enter details  / search  v/c/e/l sort  d deltas  ↑/↓ scroll"
+    };
+    status.push(Span::styled(help, Style::default().fg(Color::DarkGray)));
+    frame.render_widget(Line::from(status), status_area);
+}
+
+// Keystrokes while the `/` input box is open edit the query instead of triggering commands.
+fn handle_search_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => {
+            app.query.clear();
+            app.searching = false;
+            app.rebuild();
+        }
+        KeyCode::Enter => app.searching = false,
+        KeyCode::Backspace => {
+            app.query.pop();
+            app.rebuild();
+        }
+        KeyCode::Char(ch) => {
+            app.query.push(ch);
+            app.rebuild();
+        }
+        KeyCode::Down => app.scroll(1),
+        KeyCode::Up => app.scroll(-1),
+        _ => {}
+    }
+}
+
+// Returns false when the user asked to quit.
+fn handle_key(app: &mut App, key: KeyEvent, refresh: &Notify) -> bool {
+    if key.kind != KeyEventKind::Press {
+        return true;
+    }
+    if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        return false;
+    }
+    if app.searching {
+        handle_search_key(app, key);
+        return true;
+    }
+    match key.code {
+        KeyCode::Esc if app.detail.is_some() => app.detail = None,
+        KeyCode::Esc if !app.query.is_empty() => {
+            app.query.clear();
+            app.rebuild();
+        }
+        KeyCode::Char('q') | KeyCode::Esc => return false,
+        KeyCode::Char('/') => app.searching = true,
+        KeyCode::Enter => app.toggle_detail(),
+        KeyCode::Char('r') => {
+            app.refreshing = true;
+            refresh.notify_one();
+        }
+        KeyCode::Char('v') => app.sort_by(SortKey::Volume),
+        KeyCode::Char('c') => app.sort_by(SortKey::Change),
+        KeyCode::Char('e') => app.sort_by(SortKey::End),
+        KeyCode::Char('l') => app.sort_by(SortKey::Liquidity),
+        KeyCode::Char('d') => app.deltas = !app.deltas,
+        KeyCode::Down | KeyCode::Char('j') => app.scroll(1),
+        KeyCode::Up | KeyCode::Char('k') => app.scroll(-1),
+        KeyCode::PageDown => app.scroll(10),
+        KeyCode::PageUp => app.scroll(-10),
+        KeyCode::Home | KeyCode::Char('g') => app.table.select(Some(0)),
+        KeyCode::End | KeyCode::Char('G') => app.scroll(isize::MAX / 2),
+        _ => {}
+    }
+    true
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    updates: &mut mpsc::Receiver<Update>,
+    details: &mut mpsc::UnboundedReceiver<DetailResult>,
+    refresh: &Notify,
+) -> io::Result<()> {
+    let mut events = EventStream::new();
+
+    loop {
+        terminal.draw(|frame| draw(frame, app))?;
+
+        tokio::select! {
+            update = updates.recv() => match update {
+                Some(update) => app.apply(update),
+                None => return Ok(()),
+            },
+            Some(detail) = details.recv() => app.apply_detail(detail),
+            event = events.next() => match event {
+                Some(Ok(Event::Key(key))) => {
+                    if !handle_key(app, key, refresh) {
+                        return Ok(());
+                    }
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e),
+                None => return Ok(()),
+            },
+        }
+    }
+}
+
+// Runs the full-screen dashboard until the user quits. Rows arrive from a background task
+// over `updates`; `refresh` is signalled when the user asks for an immediate refetch.
+pub async fn run(
+    options: Options,
+    mut updates: mpsc::Receiver<Update>,
+    refresh: Arc<Notify>,
+) -> io::Result<()> {
+    let (detail_tx, mut details) = mpsc::unbounded_channel();
+    let mut app = App {
+        heading: options.heading,
+        top: options.top,
+        sort: options.sort,
+        fetched: Vec::new(),
+        rows: Vec::new(),
+        previous: HashMap::new(),
+        baseline: HashMap::new(),
+        deltas: options.deltas,
+        alerts: VecDeque::new(),
+        beep: options.beep,
+        table: TableState::default(),
+        last_update: None,
+        error: None,
+        refreshing: true,
+        query: String::new(),
+        searching: false,
+        detail: None,
+        details: HashMap::new(),
+        detail_tx,
+    };
+
+    let mut terminal = ratatui::init();
+    let _restore = Restore;
+    event_loop(&mut terminal, &mut app, &mut updates, &mut details, &refresh).await
+}
+
+// Puts the terminal back however the dashboard ends, including when its future is dropped
+// part-way, as --max-runtime does.
+struct Restore;
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}